@@ -1,18 +1,54 @@
-use common::{HandlerDeleteItemBody};
+use common::{HandlerDeleteItemBody, HandlerDeleteManifestBody, HandlerDeleteManifestResponse};
 use vertigo::{
     Value,
-    bind, bind_spawn, Resource, Computed, dom, transaction, RequestBuilder, DomNode,
+    bind, bind_spawn, Resource, Computed, dom, transaction, RequestBuilder, DomNode, AutoMap, LazyCache, Context,
 };
-use crate::{components::{AlertBox, ButtonState}, app::{response::check_request_response, App}, data::ListItem};
+use crate::{components::{AlertBox, ButtonState}, app::{response::check_request_response, App}, data::{ListItem, ListItemType, api_url}};
 
 use super::AppIndexAlert;
 
+fn fetch_manifest(_: &AutoMap<(Vec<String>, String), LazyCache<HandlerDeleteManifestResponse>>, key: &(Vec<String>, String)) -> LazyCache<HandlerDeleteManifestResponse> {
+    let (path, hash) = key;
+
+    RequestBuilder::post(api_url("/delete_manifest"))
+        .body_json(HandlerDeleteManifestBody {
+            path: path.clone(),
+            hash: hash.clone(),
+        })
+        .lazy_cache(|status, body| {
+            if status == 200 {
+                Some(body.into::<HandlerDeleteManifestResponse>())
+            } else {
+                None
+            }
+        })
+}
+
+pub fn format_size(size: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+
+    let mut value = size as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{size} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
 #[derive(Clone, PartialEq)]
 pub struct AppIndexAlertDelete {
     app: App,
     pub alert: AppIndexAlert,
     select_item: ListItem,
     progress: Value<bool>,
+    manifest: AutoMap<(Vec<String>, String), LazyCache<HandlerDeleteManifestResponse>>,
 }
 
 impl AppIndexAlertDelete {
@@ -24,10 +60,23 @@ impl AppIndexAlertDelete {
             alert: alert.clone(),
             select_item,
             progress,
+            manifest: AutoMap::new(fetch_manifest),
+        }
+    }
+
+    //Katalog usuwamy razem z zawartością, po potwierdzeniu podsumowania (manifest)
+    fn get_manifest(&self, context: &Context) -> Resource<Option<HandlerDeleteManifestResponse>> {
+        if self.select_item.is_dir.get(context) != ListItemType::Dir {
+            return Resource::Ready(None);
         }
+
+        let id = self.select_item.id.get(context)?;
+        let manifest = self.manifest.get(&(self.select_item.to_vec_path(), id)).get(context)?;
+
+        Resource::Ready(Some(manifest.as_ref().clone()))
     }
 
-    async fn delete_yes(self, app: App, current_hash: String) {
+    async fn delete_yes(self, app: App, current_hash: String, recursive: bool) {
         let progress = transaction(|context| {
             self.progress.get(context)
         });
@@ -39,11 +88,11 @@ impl AppIndexAlertDelete {
         log::info!("usuwamy ...");
         self.progress.set(true);
 
-        let response = RequestBuilder::post("/delete_item")
+        let response = RequestBuilder::post(api_url("/delete_item"))
             .body_json(HandlerDeleteItemBody {
                 path: self.select_item.to_vec_path(),
-                hash: current_hash
-                
+                hash: current_hash,
+                recursive,
             })
             .call()
             .await;
@@ -67,17 +116,25 @@ impl AppIndexAlertDelete {
         let app = self.app.clone();
 
         ButtonState::render(Computed::from(move |context| {
-            let id = state.select_item.id.get(context);
+            let (hash, recursive) = match state.get_manifest(context) {
+                Resource::Ready(Some(manifest)) => (manifest.hash, true),
+                Resource::Ready(None) => {
+                    let Resource::Ready(id) = state.select_item.id.get(context) else {
+                        return ButtonState::disabled("Tak");
+                    };
+
+                    (id, false)
+                },
+                _ => {
+                    return ButtonState::disabled("Tak");
+                }
+            };
 
-            if let Resource::Ready(id) = id {
-                let action = bind_spawn!(state, app, id, async move {
-                    state.delete_yes(app, id).await;
-                });
+            let action = bind_spawn!(state, app, hash, async move {
+                state.delete_yes(app, hash, recursive).await;
+            });
 
-                return ButtonState::active("Tak", action);
-            } else {
-                ButtonState::disabled("Tak")
-            }
+            ButtonState::active("Tak", action)
         }))
     }
 
@@ -115,9 +172,45 @@ impl AppIndexAlertDelete {
 fn render_message(state: &AppIndexAlertDelete) -> DomNode {
     let full_path = state.select_item.to_string_path();
     let message = format!("Czy usunąć -> {full_path} ?");
+
+    let manifest = Computed::from({
+        let state = state.clone();
+        move |context| {
+            match state.get_manifest(context) {
+                Resource::Ready(Some(manifest)) => Some(format!(
+                    "Katalog zostanie usunięty razem z zawartością: plików {}, katalogów {}, rozmiar {}, TODO {}",
+                    manifest.files,
+                    manifest.dirs,
+                    format_size(manifest.size),
+                    manifest.todo,
+                )),
+                Resource::Ready(None) => None,
+                Resource::Loading => Some(String::from("Wczytywanie zawartości katalogu ...")),
+                Resource::Error(message) => Some(format!("Błąd wczytywania zawartości katalogu: {message}")),
+            }
+        }
+    });
+
+    let manifest = manifest.render_value_option(|manifest| {
+        manifest.map(|manifest| dom! {
+            <div>
+                { manifest }
+            </div>
+        })
+    });
+
     dom!{
         <div>
             { message }
+            { manifest }
         </div>
     }
+}
+
+#[test]
+fn test_format_size() {
+    assert_eq!(format_size(0), "0 B");
+    assert_eq!(format_size(1023), "1023 B");
+    assert_eq!(format_size(1536), "1.5 KB");
+    assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
 }
\ No newline at end of file
@@ -1,16 +1,24 @@
-use common::HandlerMoveItemBody;
+use common::{HandlerMoveItemBody, HandlerCopyItemBody};
 use vertigo::{Value, Resource, Computed, bind, css, Css, dom, transaction, Context, bind_spawn, RequestBuilder, DomNode, dom_element, bind_rc};
 
-use crate::{components::{AlertBox, ItemDefault, ItemDotHtml, ButtonState, render_path}, data::{ListItem, ListItemType}, app::{response::check_request_response, App}};
+use crate::{components::{AlertBox, ItemDefault, ItemDotHtml, ButtonState, render_path}, data::{ListItem, ListItemType, api_url}, app::{response::check_request_response, App}};
 
 use super::AppIndexAlert;
 
+//To samo okno wyboru katalogu docelowego służy do przenoszenia i kopiowania
+#[derive(Clone, Copy, PartialEq)]
+pub enum MoveItemMode {
+    Move,
+    Copy,
+}
+
 //TODO - wypozycjonować to okno wyskakujace, zeby górna krawędź nie skakała w sytuacji gdy zmienia się wysokość zawartości
 
 #[derive(Clone, PartialEq)]
 pub struct AppIndexAlertMoveitem {
     pub app: App,
     alert: AppIndexAlert,
+    mode: MoveItemMode,
     item: ListItem,                                     //pełna ściezka do przenoszonego elementu
     hash: String,                                       //hash przenoszonego elementu
     target_dir: Value<ListItem>,                        //nowy katalog do którego będziemy przenosić ten element
@@ -20,7 +28,7 @@ pub struct AppIndexAlertMoveitem {
 }
 
 impl AppIndexAlertMoveitem {
-    pub fn new(app: &App, alert: &AppIndexAlert, item: ListItem, hash: String) -> AppIndexAlertMoveitem {
+    pub fn new(app: &App, alert: &AppIndexAlert, mode: MoveItemMode, item: ListItem, hash: String) -> AppIndexAlertMoveitem {
         let target_dir = Value::new(item.dir());
 
         let new_path = Computed::from({
@@ -37,6 +45,7 @@ impl AppIndexAlertMoveitem {
         AppIndexAlertMoveitem {
             app: app.clone(),
             alert: alert.clone(),
+            mode,
             item,
             hash,
             target_dir,
@@ -46,16 +55,18 @@ impl AppIndexAlertMoveitem {
     }
 
     async fn on_save(&self, new_path: ListItem) -> Result<(), String> {
-        let body: HandlerMoveItemBody = HandlerMoveItemBody {
-            path: self.item.to_vec_path(),
-            hash: self.hash.clone(),
-            new_path: new_path.to_vec_path(),
+        let path = self.item.to_vec_path();
+        let hash = self.hash.clone();
+        let new_path = new_path.to_vec_path();
+
+        let request = match self.mode {
+            MoveItemMode::Move => RequestBuilder::post(api_url("/move_item"))
+                .body_json(HandlerMoveItemBody { path, hash, new_path }),
+            MoveItemMode::Copy => RequestBuilder::post(api_url("/copy_item"))
+                .body_json(HandlerCopyItemBody { path, hash, new_path }),
         };
 
-        let response = RequestBuilder::post("/move_item")
-            .body_json(body)
-            .call()
-            .await;
+        let response = request.call().await;
 
         check_request_response(response)
     }
@@ -237,7 +248,7 @@ fn render_button_yes(state: &AppIndexAlertMoveitem) -> DomNode {
             });
 
             if progress {
-                log::error!("Trwa obecnie przenoszenie elementu");
+                log::error!("Trwa obecnie przenoszenie lub kopiowanie elementu");
                 return;
             }
 
@@ -245,18 +256,29 @@ fn render_button_yes(state: &AppIndexAlertMoveitem) -> DomNode {
             let response = state.on_save(new_path).await;
             state.progress.set(false);
 
-            match response {
-                Ok(()) => {  
+            match (response, state.mode) {
+                (Ok(()), MoveItemMode::Move) => {
                     log::info!("Przenoszenie udane");
                     state.alert.data.git.root.refresh();
                     state.alert.close_modal();
                     state.app.show_message_info("Udane przenoszenie", Some(1000));
                     state.app.data.tab.redirect_item_select_after_delete();
                 },
-                Err(message) => {
+                (Ok(()), MoveItemMode::Copy) => {
+                    log::info!("Kopiowanie udane");
+                    state.alert.data.git.root.refresh();
+                    state.alert.close_modal();
+                    state.app.show_message_info("Udane kopiowanie", Some(1000));
+                },
+                (Err(message), MoveItemMode::Move) => {
                     let message = format!("nie udane przenoszenie {message}");
                     state.app.show_message_error(message.clone(), Some(10000));
                     log::error!("{message}");
+                },
+                (Err(message), MoveItemMode::Copy) => {
+                    let message = format!("nie udane kopiowanie {message}");
+                    state.app.show_message_error(message.clone(), Some(10000));
+                    log::error!("{message}");
                 }
             };
         });
@@ -285,6 +307,10 @@ fn render_button_no(state: &AppIndexAlertMoveitem) -> DomNode {
 fn render_message(state: &AppIndexAlertMoveitem) -> DomNode {    
     let path = state.item.to_string_path();
     let new_path = state.new_path.map(|item| item.to_string_path());
+    let label = match state.mode {
+        MoveItemMode::Move => "Przenoszenie",
+        MoveItemMode::Copy => "Kopiowanie",
+    };
 
     let message = Computed::from({
         let item = state.item.clone();
@@ -361,7 +387,7 @@ fn render_message(state: &AppIndexAlertMoveitem) -> DomNode {
             <table css={wrapper_table()}>
                 <tr>
                     <td css={wrapper_td()}>
-                        "Przenoszenie"
+                        { label }
                     </td>
                     <td css={wrapper_td()}>
                         { path }
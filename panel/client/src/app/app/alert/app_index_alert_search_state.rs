@@ -1,6 +1,7 @@
-use vertigo::{Css, Resource, Computed, Value, bind, Context, dom, DomNode, bind_rc};
+use common::{HandlerSearchBody, HandlerSearchResponse, SearchResultItem};
+use vertigo::{Css, Resource, Computed, Value, bind, Context, dom, dom_element, DomNode, bind_rc, AutoMap, LazyCache, RequestBuilder};
 use vertigo::{css};
-use crate::data::{ListItem, ListItemType};
+use crate::data::{ListItem, ListItemType, api_url};
 use crate::{components::AlertBox, data::{Data}};
 use crate::components::icon;
 
@@ -34,6 +35,22 @@ fn css_result_row() -> Css {
     ")
 }
 
+fn css_result_header() -> Css {
+    css!("
+        font-weight: bold;
+        margin: 10px 0 5px 0;
+    ")
+}
+
+fn css_result_line() -> Css {
+    css!("
+        font-size: 12px;
+        color: #606060;
+        margin: 0 0 5px 20px;
+        white-space: pre-wrap;
+    ")
+}
+
 fn css_result_icon() -> Css {
     css!("
         margin-right: 5px;
@@ -137,6 +154,49 @@ fn new_results(data_state: &Data, phrase: Computed<String>) -> Computed<Vec<List
     })
 }
 
+fn fetch_search(_: &AutoMap<(String, String), LazyCache<Vec<SearchResultItem>>>, key: &(String, String)) -> LazyCache<Vec<SearchResultItem>> {
+    let (_, phrase) = key;
+
+    RequestBuilder::post(api_url("/search"))
+        .body_json(HandlerSearchBody {
+            phrase: phrase.clone(),
+        })
+        .lazy_cache(|status, body| {
+            if status == 200 {
+                Some(body.into::<HandlerSearchResponse>().map(|inner| inner.list))
+            } else {
+                None
+            }
+        })
+}
+
+//wyszukiwanie w treści plików odbywa się po stronie serwera, klucz to (root, fraza)
+fn new_content_results(data_state: &Data, phrase: Computed<String>) -> Computed<Vec<SearchResultItem>> {
+    let data_state = data_state.clone();
+    let cache = AutoMap::new(fetch_search);
+
+    Computed::from(move |context| {
+        let phrase_value = phrase.get(context);
+
+        if split_phrase(phrase_value.clone()).len() < 1 {
+            return Vec::new();
+        }
+
+        let Resource::Ready(root) = data_state.git.root.get_current_root(context) else {
+            return Vec::new();
+        };
+
+        match cache.get(&(root, phrase_value)).get(context) {
+            Resource::Ready(list) => list.as_ref().clone(),
+            Resource::Loading => Vec::new(),
+            Resource::Error(err) => {
+                log::error!("Error search content {:?}", err);
+                Vec::new()
+            }
+        }
+    })
+}
+
 #[derive(Clone, PartialEq)]
 pub struct AppIndexAlertSearch {
     alert: AppIndexAlert,
@@ -145,6 +205,7 @@ pub struct AppIndexAlertSearch {
     // pub 
 
     results: Computed<Vec<ListItem>>,
+    content_results: Computed<Vec<SearchResultItem>>,
 }
 
 impl AppIndexAlertSearch {
@@ -156,10 +217,16 @@ impl AppIndexAlertSearch {
             phrase.to_computed(),
         );
 
+        let content_results = new_content_results(
+            &alert.data,
+            phrase.to_computed(),
+        );
+
         AppIndexAlertSearch {
             alert: alert.clone(),
             phrase,
             results,
+            content_results,
         }
     }
 
@@ -208,9 +275,53 @@ fn render_results(search: &AppIndexAlertSearch) -> DomNode {
         }
     });
 
+    let content_list = search.content_results.render_list(|item| item.path.clone(), {
+        let search = search.clone();
+        move |item| {
+            let list_item = search.alert.data.items.get_from_path(&item.path);
+            let redirect_to_item = search.alert.data.tab.build_redirect_to_item(list_item.clone());
+
+            let on_click = Computed::from(bind!(search, redirect_to_item, |context| {
+                let redirect_to_item = redirect_to_item.get(context);
+
+                bind_rc!(search, || {
+                    search.alert.close_modal();
+                    redirect_to_item();
+                })
+            }));
+
+            let icon_el = icon::icon_render(&list_item);
+            let path = list_item.to_string_path();
+
+            let lines_view = dom_element! { <div /> };
+
+            for line in item.lines.iter() {
+                let text = format!("{}: {}", line.number, line.content);
+
+                lines_view.add_child(dom! {
+                    <div css={css_result_line()}>{text}</div>
+                });
+            }
+
+            dom! {
+                <div on_click={on_click}>
+                    <div css={css_result_row()}>
+                        <div css={css_result_icon()}>
+                            {icon_el}
+                        </div>
+                        {path}
+                    </div>
+                    {lines_view}
+                </div>
+            }
+        }
+    });
+
     dom! {
         <div css={css_result()}>
             {list}
+            <div css={css_result_header()}>"Treść"</div>
+            {content_list}
         </div>
     }
 }
@@ -4,7 +4,7 @@ use crate::app::app::alert::app_index_alert_delete_state::AppIndexAlertDelete;
 use crate::app::app::alert::app_index_alert_search_state::AppIndexAlertSearch;
 use crate::data::{Data, ListItem};
 
-use super::app_index_alert_moveitem_state::AppIndexAlertMoveitem;
+use super::app_index_alert_moveitem_state::{AppIndexAlertMoveitem, MoveItemMode};
 
 #[derive(Clone, PartialEq)]
 enum AlertView {
@@ -66,7 +66,16 @@ impl AppIndexAlert {
             return;
         }
 
-        let state = AppIndexAlertMoveitem::new(app, self, item, hash.clone());
+        let state = AppIndexAlertMoveitem::new(app, self, MoveItemMode::Move, item, hash.clone());
+        self.view.set(AlertView::MoveItem { state });
+    }
+
+    pub fn copy_current(&self, app: &App, item: ListItem, hash: &String) {
+        if self.is_visible() {
+            return;
+        }
+
+        let state = AppIndexAlertMoveitem::new(app, self, MoveItemMode::Copy, item, hash.clone());
         self.view.set(AlertView::MoveItem { state });
     }
 
@@ -4,3 +4,4 @@ mod app_index_alert_search_state;
 mod app_index_alert_delete_state;
 
 pub use app_index_alert_state::AppIndexAlert;
+pub use app_index_alert_delete_state::format_size;
@@ -3,14 +3,22 @@ use std::rc::Rc;
 use vertigo::{Resource, get_driver, transaction, dom, bind, DomNode, Computed};
 use vertigo::Value;
 use crate::components::{message_box, MessageBoxType, stict_to_top, ButtonState};
-use crate::data::{Data, ListItem};
+use crate::data::{Data, ListItem, ContentView, api_url};
+use crate::content::toggle_task;
+use crate::app::response::check_save_response;
+use common::{HandlerSaveContentBody, HandlerSaveContentResponse};
 
 use crate::app::edit_content::AppEditcontent;
 use super::alert::{AppIndexAlert};
+use super::AppUpload;
 use super::app_index_render;
 use crate::app::new_dir::AppNewdir;
 use crate::app::newcontent::AppNewcontent;
 use crate::app::rename_item::AppRenameitem;
+use crate::app::history::AppHistory;
+use crate::app::trash::AppTrash;
+use crate::app::todo::AppTodo;
+use crate::app::login::AppLogin;
 use vertigo::struct_mut::CounterMut;
 
 #[derive(Clone, PartialEq, Eq)]
@@ -28,12 +36,16 @@ enum View {
     RenameItem { state: AppRenameitem },
     NewContent { state: AppNewcontent },
     Mkdir { state: AppNewdir },
+    History { state: AppHistory },
+    Trash { state: AppTrash },
+    Todo { state: AppTodo },
 }
 
 #[derive(Clone)]
 pub struct App {
     pub data: Data,
     pub alert: AppIndexAlert,
+    pub upload: AppUpload,
     view: Value<View>,
 
     next_id: Rc<CounterMut>,
@@ -59,6 +71,7 @@ impl App {
         App {
             data,
             alert,
+            upload: AppUpload::new(),
             view,
             next_id,
             errors: Value::new(Vec::new()),
@@ -106,6 +119,21 @@ impl App {
         self.view.set(View::NewContent { state });
     }
 
+    pub fn redirect_to_history(&self, item: ListItem) {
+        let state = AppHistory::new(self, item);
+        self.view.set(View::History { state });
+    }
+
+    pub fn redirect_to_trash(&self) {
+        let state = AppTrash::new(self);
+        self.view.set(View::Trash { state });
+    }
+
+    pub fn redirect_to_todo(&self) {
+        let state = AppTodo::new(self);
+        self.view.set(View::Todo { state });
+    }
+
     pub fn render_current_rename(&self) -> DomNode {
         ButtonState::render({    
             Computed::from({                
@@ -138,6 +166,50 @@ impl App {
         })
     }
 
+    //Przełącza zadanie "[ ]" / "[x]" w podanej linii aktualnie wyświetlanego pliku
+    pub fn toggle_current_task(&self, line: usize) {
+        let current = transaction(|context| {
+            let item = self.data.tab.select_content.get(context)?;
+            let content = item.get_content(context)?;
+            Some((item, content))
+        });
+
+        let Some((item, ContentView { id, content })) = current else {
+            log::error!("toggle_current_task - brak treści pliku");
+            return;
+        };
+
+        let Some(new_content) = toggle_task(&content, line) else {
+            log::error!("toggle_current_task - linia {line} nie jest zadaniem");
+            return;
+        };
+
+        let app = self.clone();
+
+        get_driver().spawn(async move {
+            let response = get_driver()
+                .request_post(api_url("/save_content"))
+                .body_json(HandlerSaveContentBody {
+                    path: item.to_vec_path(),
+                    prev_hash: id,
+                    new_content,
+                })
+                .call().await;
+
+            match check_save_response(response) {
+                Ok(HandlerSaveContentResponse { conflict: Some(_), .. }) => {
+                    app.show_message_error("Plik został zmieniony w międzyczasie, spróbuj ponownie", Some(5000));
+                },
+                Ok(_) => {},
+                Err(message) => {
+                    app.show_message_error(message, Some(5000));
+                }
+            }
+
+            app.data.git.root.refresh();
+        });
+    }
+
     fn message_add(&self, info: MessageBoxType, message: String) -> u32 {
         let message_id = self.next_id.get_next();
 
@@ -250,8 +322,22 @@ impl App {
 
 
 fn render_view(state: &App) -> DomNode {
-    let app = app_render(state);
-    state.data.tab.open_links.render(app)
+    let session = state.data.session.clone();
+    let is_login_required = Computed::from(move |context| {
+        session.is_login_required(context)
+    });
+
+    is_login_required.render_value({
+        let state = state.clone();
+        move |is_login_required| {
+            if is_login_required {
+                return AppLogin::new(&state.data).render();
+            }
+
+            let app = app_render(&state);
+            state.data.tab.open_links.render(app)
+        }
+    })
 }
 
 fn render_error_one(state: &App, error: Error) -> DomNode {
@@ -321,6 +407,27 @@ fn app_render(app: &App) -> DomNode {
                         </div>
                     }
                 },
+                View::History { state } => {
+                    dom! {
+                        <div id="root">
+                            { state.render() }
+                        </div>
+                    }
+                },
+                View::Trash { state } => {
+                    dom! {
+                        <div id="root">
+                            { state.render() }
+                        </div>
+                    }
+                },
+                View::Todo { state } => {
+                    dom! {
+                        <div id="root">
+                            { state.render() }
+                        </div>
+                    }
+                },
             }
         }
     })
@@ -68,6 +68,7 @@ pub fn app_index_render(app: &App) -> DomNode {
 
     let view_list = render_list(app);
     let view_content = render_content(app);
+    let view_upload = app.upload.render();
 
     let hook_keydown = bind!(app, |event: vertigo::KeyDownEvent| {
         app.keydown(event.code)
@@ -85,6 +86,7 @@ pub fn app_index_render(app: &App) -> DomNode {
                     { view_content }
                 </div>
             </div>
+            { view_upload }
             { view_alert }
         </div>
     }
@@ -1,14 +1,16 @@
 use std::rc::Rc;
 
-use vertigo::{Css, css, bind, Resource, dom, Computed, DomNode, Value};
+use vertigo::{Css, css, bind, Resource, dom, dom_element, Computed, DomElement, DomNode, Value, DropFileEvent, transaction};
 
 use crate::app::App;
+use super::app_render_markdown::render_content_markdown;
 use crate::components::list_items_from_dir;
-use crate::data::{ContentType, ListItem};
+use crate::data::{ContentType, ListItem, ListItemType, AttachmentKind};
 use crate::{
     content::{
-        parse_text,
+        parse_text_blocks,
         ParseTextItem,
+        TextBlock,
         get_thumbnail,
     }
 };
@@ -27,6 +29,16 @@ fn css_content_dir() -> Css {
     ")
 }
 
+fn css_drop_hint() -> Css {
+    css!("
+        margin-top: 10px;
+        padding: 20px;
+        border: 2px dashed #a0a0a0;
+        color: #808080;
+        text-align: center;
+    ")
+}
+
 fn css_content_file_image() -> Css {
     css!("
         width: 100%;
@@ -34,6 +46,23 @@ fn css_content_file_image() -> Css {
     ")
 }
 
+fn css_attachment() -> Css {
+    css!("
+        width: 100%;
+        display: flex;
+        flex-direction: column;
+        gap: 10px;
+    ")
+}
+
+fn css_attachment_preview() -> Css {
+    css!("
+        width: 100%;
+        height: 80vh;
+        border: 0;
+    ")
+}
+
 fn link_css() -> Css {
     css!("
         color: blue;
@@ -57,42 +86,92 @@ fn open_css() -> Css {
     ")
 }
 
-fn render_content_chunk(state: &App, item: &ParseTextItem) -> DomNode {
-    match item {
-        ParseTextItem::Link { url, has_open } => {
-            let url = url.to_string();
+//Link z przełącznikiem otwierającym go w zakładce (OpenLinks)
+pub fn render_link(state: &App, label: String, url: String, has_open: bool) -> DomNode {
+    let link_label = match has_open {
+        true => "(zamknij)",
+        false => "(otwórz)"
+    };
 
-            let link_label = match has_open {
-                true => "(zamknij)",
-                false => "(otwórz)"
-            };
+    let on_click = bind!(state, url, || {
+        state.data.tab.open_links.tabs_toogle(url.clone());
+    });
 
-            let on_click = bind!(state, url, || {
-                state.data.tab.open_links.tabs_toogle(url.clone());
-            });
+    let img = if let Some(thumb) = get_thumbnail(url.as_str()) {
+        dom! {
+            <img css={youtube_css()} src={thumb} />
+        }
+    } else {
+        dom! {
+            <span></span>
+        }
+    };
 
-            let img = if let Some(thumb) = get_thumbnail(url.as_str()) {
-                dom! {
-                    <img css={youtube_css()} src={thumb} />
-                }
-            } else {
-                dom! {
-                    <span></span>
-                }
-            };
+    dom!{
+        <span>
+            <a href={url} target="_blank" css={link_css()}>
+                <span>{label}</span>
+                { img }
+            </a>
+            " "
+            <span on_click={on_click} css={open_css()}>
+                { link_label }
+            </span>
+        </span>
+    }
+}
 
-            dom!{
-                <span>
-                    <a href={url.clone()} target="_blank" css={link_css()}>
-                        <span>{url}</span>
-                        { img }
-                    </a>
-                    " "
-                    <span on_click={on_click} css={open_css()}>
-                        { link_label }
-                    </span>
-                </span>
+fn wiki_link_css(is_broken: bool) -> Css {
+    let css = link_css().extend(css!("
+        cursor: pointer;
+    "));
+
+    match is_broken {
+        true => css.extend(css!("
+            color: red;
+            text-decoration: line-through;
+        ")),
+        false => css,
+    }
+}
+
+//Link "[[ścieżka]]" do innej notatki lub katalogu w repozytorium, nieistniejący cel jest przekreślony
+pub fn render_wiki_link(state: &App, label: String, path: Vec<String>) -> DomNode {
+    let item = state.data.items.get_from_path(&path);
+
+    let css = Computed::from({
+        let item = item.clone();
+        move |context| wiki_link_css(item.is_dir.get(context) == ListItemType::Unknown)
+    });
+
+    let on_click = bind!(state, item, path, || {
+        let is_dir = transaction(|context| item.is_dir.get(context));
+
+        match is_dir {
+            ListItemType::Dir => {
+                state.data.tab.redirect_to(item.clone(), None);
+            },
+            ListItemType::File => {
+                state.data.tab.redirect_to(item.dir(), Some(item.name()));
+            },
+            ListItemType::Unknown => {
+                state.show_message_error(format!("Nie znaleziono {}", path.join("/")), Some(5000));
             }
+        }
+    });
+
+    dom! {
+        <span css={css} on_click={on_click}>{label}</span>
+    }
+}
+
+fn render_content_chunk(state: &App, item: &ParseTextItem) -> DomNode {
+    match item {
+        ParseTextItem::Link { url, has_open } => {
+            render_link(state, url.clone(), url.clone(), *has_open)
+        },
+        ParseTextItem::WikiLink { label, path } => {
+            render_wiki_link(state, label.clone(), path.clone())
         },
         ParseTextItem::Text { text } => {
             let text = text.to_string();
@@ -104,33 +183,131 @@ fn render_content_chunk(state: &App, item: &ParseTextItem) -> DomNode {
     }
 }
 
+fn css_task() -> Css {
+    css!("
+        display: flex;
+    ")
+}
+
+fn css_task_checkbox() -> Css {
+    css!("
+        cursor: pointer;
+        padding-right: 5px;
+        white-space: pre;
+    ")
+}
+
+//Checkbox zadania, kliknięcie zapisuje plik z przełączonym "[ ]" / "[x]"
+pub fn render_task_checkbox(state: &App, line: usize, checked: bool) -> DomNode {
+    let label = match checked {
+        true => "☑",
+        false => "☐",
+    };
+
+    let on_click = bind!(state, || {
+        state.toggle_current_task(line);
+    });
+
+    dom! {
+        <span css={css_task_checkbox()} on_click={on_click}>{label}</span>
+    }
+}
+
+fn render_text_items(state: &App, items: &[ParseTextItem]) -> DomElement {
+    let wrapper = dom_element! { <span /> };
+
+    for item in items {
+        wrapper.add_child(render_content_chunk(state, item));
+    }
+
+    wrapper
+}
+
 fn render_content_text(state: &App, content: Rc<String>) -> DomNode {
-    let chunks = Computed::from({
+    let blocks = Computed::from({
         let state = state.clone();
         move |context| {
-            parse_text(content.as_str(), |url| {
+            parse_text_blocks(content.as_str(), |url| {
                 state.data.tab.open_links.tabs_has(context, url)
             })
         }
     });
 
-    chunks.render_list(
-        |link| link.clone(),
-        {
-            let state = state.clone();
-            move |link| {
-                render_content_chunk(&state, link)
+    blocks.render_value({
+        let state = state.clone();
+        move |blocks| {
+            let wrapper = dom_element! { <div /> };
+
+            for block in blocks.iter() {
+                match block {
+                    TextBlock::Text { items } => {
+                        wrapper.add_child(render_text_items(&state, items));
+                    },
+                    TextBlock::Task { line, prefix, checked, items } => {
+                        let prefix = prefix.clone();
+                        let checkbox = render_task_checkbox(&state, *line, *checked);
+                        let text = render_text_items(&state, items);
+
+                        wrapper.add_child(dom! {
+                            <div css={css_task()}>
+                                <span>{prefix}</span>
+                                {checkbox}
+                                {text}
+                            </div>
+                        });
+                    },
+                }
             }
+
+            wrapper.into()
         }
-    )
+    })
+}
+
+fn render_attachment(url: Rc<String>, name: String, kind: AttachmentKind) -> DomNode {
+    let url = url.as_ref().clone();
+
+    let preview = match kind {
+        AttachmentKind::Pdf => dom! {
+            <iframe css={css_attachment_preview()} src={url.clone()} />
+        },
+        AttachmentKind::Audio => dom! {
+            <audio controls="controls" src={url.clone()} />
+        },
+        AttachmentKind::Video => dom! {
+            <video css={css_content_file_image()} controls="controls" src={url.clone()} />
+        },
+        AttachmentKind::Other => dom! {
+            <div>"Podgląd tego pliku nie jest dostępny"</div>
+        },
+    };
+
+    let label = format!("Pobierz {name}");
+
+    dom! {
+        <div css={css_attachment()}>
+            { preview }
+            <div>
+                <a href={url} download={name} css={link_css()}>{label}</a>
+            </div>
+        </div>
+    }
 }
 
 fn render_dir(state: &App, dir: Computed<ListItem>) -> DomNode {
     let result = list_items_from_dir(&state.data, &dir, false);
 
+    let on_dropfile = bind!(state, dir, |event: DropFileEvent| {
+        let dir = transaction(|context| dir.get(context));
+        state.upload.on_drop(&state, dir, event);
+    });
+
     dom! {
-        <div css={css_content_dir()}>
+        <div css={css_content_dir()} on_dropfile={on_dropfile}>
             { result }
+            <div css={css_drop_hint()}>
+                "Upuść tutaj pliki, aby dodać je do tego katalogu"
+            </div>
         </div>
     }
 }
@@ -176,14 +353,23 @@ pub fn render_content(state: &App) -> DomNode {
                                 </div>
                             }
                         },
-                        ContentType::Image { url } => {
+                        ContentType::Markdown { content } => {
+                            render_content_markdown(&state, content)
+                        },
+                        ContentType::Image { url, original } => {
                             let url = url.as_ref().clone();
+                            let original = original.as_ref().clone();
                             dom! {
                                 <div css={css_content_file()}>
-                                    <img css={css_content_file_image()} src={url} />
+                                    <a href={original} target="_blank">
+                                        <img css={css_content_file_image()} src={url} />
+                                    </a>
                                 </div>
                             }
                         },
+                        ContentType::Attachment { url, name, kind } => {
+                            render_attachment(url, name, kind)
+                        },
                         ContentType::Dir { item } => {
                             let item = Value::new(item).to_computed();
                             render_dir(&state, item)
@@ -1,7 +1,6 @@
-use common::{HandlerAddFilesFile, HandlerAddFiles};
 // use vertigo::dev::NodeRefs;
 use vertigo::{
-    DomElement, bind, DropFileEvent, get_driver, RequestBody, transaction, dom_element,
+    DomElement, bind, DropFileEvent, transaction, dom_element,
 };
 use vertigo::{css};
 use crate::app::App;
@@ -75,67 +74,8 @@ pub fn render_list(state: &App) -> DomElement {
     // let select_dir = state.data.tab.select_dir;
 
     let on_dropfile = bind!(state, |event: DropFileEvent| {
-        get_driver().spawn({
-            let state = state.clone();
-
-            async move {
-                let mut files = Vec::new();
-
-                for item in event.items {
-                    let data = item.data.as_ref().clone();
-
-                    let response = get_driver()
-                        .request_post("/create_blob")
-                        .body(RequestBody::Binary(data))
-                        .call()
-                        .await;
-
-                    let blob_id = match response.into_data::<String>() {
-                        Ok(blob_id) => blob_id,
-                        Err(message) => {
-                            log::error!("Error /create_blob for {} => error={message}", item.name);
-                            return;
-                        }
-                    };
-
-                    files.push((
-                        item.name,
-                        blob_id
-                    ));
-                }
-
-                let path = transaction(|context| {
-                    state.data.tab.select_dir.get(context).to_vec_path()
-                });
-
-                let mut post_files = Vec::new();
-
-                for (file, blob_id) in files {
-                    post_files.push(HandlerAddFilesFile {
-                        name: file,
-                        blob_id,
-                    })
-                }
-
-                let post = HandlerAddFiles {
-                    path,
-                    files: post_files,
-                };
-
-                let response = get_driver()
-                    .request_post("/add_files")
-                    .body_json(post)
-                    .call()
-                    .await.into_data::<String>();
-
-                if response.is_err() {
-                    log::error!("Problem z dodaniem plików: {response:#?}");
-                    return;
-                }
-
-                state.data.git.root.refresh();
-            }
-        });
+        let dir = transaction(|context| state.data.tab.select_dir.get(context));
+        state.upload.on_drop(&state, dir, event);
     });
 
     //dom_ref="wrapper" dom_apply={dom_apply}
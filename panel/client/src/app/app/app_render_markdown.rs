@@ -0,0 +1,250 @@
+use std::rc::Rc;
+
+use vertigo::{Css, css, dom, dom_element, Computed, DomElement, DomNode};
+
+use crate::app::App;
+use crate::content::{parse_markdown, MarkdownBlock, MarkdownInline, MarkdownListItem, ListMarker};
+use super::app_render_content::{render_link, render_wiki_link, render_task_checkbox};
+
+fn css_wrapper() -> Css {
+    css!("
+        width: 100%;
+        font-family: sans-serif;
+        line-height: 1.4;
+    ")
+}
+
+fn css_heading(level: u8) -> Css {
+    let style = css!("
+        margin: 10px 0 5px 0;
+        font-weight: bold;
+    ");
+
+    match level {
+        1 => style.extend(css!("font-size: 26px;")),
+        2 => style.extend(css!("font-size: 22px;")),
+        3 => style.extend(css!("font-size: 18px;")),
+        _ => style.extend(css!("font-size: 16px;")),
+    }
+}
+
+fn css_paragraph() -> Css {
+    css!("
+        margin: 5px 0;
+    ")
+}
+
+fn css_quote() -> Css {
+    css!("
+        margin: 5px 0;
+        padding-left: 10px;
+        border-left: 3px solid #a0a0a0;
+        color: #505050;
+    ")
+}
+
+fn css_list_item(level: usize) -> Css {
+    let style = css!("
+        display: flex;
+        margin: 2px 0;
+    ");
+
+    match level {
+        0 => style,
+        1 => style.extend(css!("padding-left: 20px;")),
+        2 => style.extend(css!("padding-left: 40px;")),
+        _ => style.extend(css!("padding-left: 60px;")),
+    }
+}
+
+fn css_list_marker() -> Css {
+    css!("
+        flex-shrink: 0;
+        min-width: 20px;
+        padding-right: 5px;
+    ")
+}
+
+fn css_code_block() -> Css {
+    css!("
+        margin: 5px 0;
+        padding: 5px;
+        background-color: #f4f4f4;
+        border: 1px solid #c0c0c0;
+        font-family: monospace;
+        white-space: pre;
+        overflow-x: auto;
+    ")
+}
+
+fn css_code_inline() -> Css {
+    css!("
+        padding: 0 2px;
+        background-color: #f4f4f4;
+        font-family: monospace;
+    ")
+}
+
+fn css_table() -> Css {
+    css!("
+        margin: 5px 0;
+        border-collapse: collapse;
+    ")
+}
+
+fn css_table_cell() -> Css {
+    css!("
+        border: 1px solid #a0a0a0;
+        padding: 2px 5px;
+    ")
+}
+
+fn css_rule() -> Css {
+    css!("
+        border: 0;
+        border-top: 1px solid #a0a0a0;
+    ")
+}
+
+fn render_inline(state: &App, wrapper: &DomElement, inline: &[MarkdownInline]) {
+    for item in inline {
+        let node = match item {
+            MarkdownInline::Text { text } => {
+                let text = text.clone();
+                dom! { <span>{text}</span> }
+            },
+            MarkdownInline::Bold { text } => {
+                let text = text.clone();
+                dom! { <strong>{text}</strong> }
+            },
+            MarkdownInline::Italic { text } => {
+                let text = text.clone();
+                dom! { <em>{text}</em> }
+            },
+            MarkdownInline::Code { text } => {
+                let text = text.clone();
+                dom! { <code css={css_code_inline()}>{text}</code> }
+            },
+            MarkdownInline::Link { label, url, has_open } => {
+                render_link(state, label.clone(), url.clone(), *has_open)
+            },
+            MarkdownInline::WikiLink { label, path } => {
+                render_wiki_link(state, label.clone(), path.clone())
+            },
+        };
+
+        wrapper.add_child(node);
+    }
+}
+
+fn render_list_item(state: &App, item: &MarkdownListItem) -> DomNode {
+    let marker = match (&item.checked, &item.marker) {
+        (Some(checked), _) => render_task_checkbox(state, item.line, *checked),
+        (None, ListMarker::Bullet) => dom! { <span>"•"</span> },
+        (None, ListMarker::Number(number)) => {
+            let number = format!("{number}.");
+            dom! { <span>{number}</span> }
+        },
+    };
+
+    let content = dom_element! { <div /> };
+    render_inline(state, &content, &item.inline);
+
+    dom! {
+        <div css={css_list_item(item.level)}>
+            <div css={css_list_marker()}>{marker}</div>
+            {content}
+        </div>
+    }
+}
+
+fn render_table(state: &App, header: &[Vec<MarkdownInline>], rows: &[Vec<Vec<MarkdownInline>>]) -> DomNode {
+    let table = dom_element! { <table css={css_table()} /> };
+
+    let header_row = dom_element! { <tr /> };
+
+    for cell in header {
+        let th = dom_element! { <th css={css_table_cell()} /> };
+        render_inline(state, &th, cell);
+        header_row.add_child(th);
+    }
+
+    table.add_child(header_row);
+
+    for row in rows {
+        let tr = dom_element! { <tr /> };
+
+        for cell in row {
+            let td = dom_element! { <td css={css_table_cell()} /> };
+            render_inline(state, &td, cell);
+            tr.add_child(td);
+        }
+
+        table.add_child(tr);
+    }
+
+    table.into()
+}
+
+fn render_block(state: &App, block: &MarkdownBlock) -> DomNode {
+    match block {
+        MarkdownBlock::Heading { level, inline } => {
+            let heading = dom_element! { <div css={css_heading(*level)} /> };
+            render_inline(state, &heading, inline);
+            heading.into()
+        },
+        MarkdownBlock::Paragraph { inline } => {
+            let paragraph = dom_element! { <div css={css_paragraph()} /> };
+            render_inline(state, &paragraph, inline);
+            paragraph.into()
+        },
+        MarkdownBlock::Quote { inline } => {
+            let quote = dom_element! { <div css={css_quote()} /> };
+            render_inline(state, &quote, inline);
+            quote.into()
+        },
+        MarkdownBlock::List { items } => {
+            let list = dom_element! { <div css={css_paragraph()} /> };
+
+            for item in items {
+                list.add_child(render_list_item(state, item));
+            }
+
+            list.into()
+        },
+        MarkdownBlock::Code { lang: _, code } => {
+            let code = code.clone();
+            dom! { <div css={css_code_block()}>{code}</div> }
+        },
+        MarkdownBlock::Table { header, rows } => {
+            render_table(state, header, rows)
+        },
+        MarkdownBlock::Rule => {
+            dom! { <hr css={css_rule()} /> }
+        },
+    }
+}
+
+pub fn render_content_markdown(state: &App, content: Rc<String>) -> DomNode {
+    let blocks = Computed::from({
+        let state = state.clone();
+        move |context| {
+            parse_markdown(content.as_str(), |url| {
+                state.data.tab.open_links.tabs_has(context, url)
+            })
+        }
+    });
+
+    blocks.render_value({
+        let state = state.clone();
+        move |blocks| {
+            let wrapper = dom_element! { <div css={css_wrapper()} /> };
+
+            for block in blocks.iter() {
+                wrapper.add_child(render_block(&state, block));
+            }
+
+            wrapper.into()
+        }
+    })
+}
@@ -1,13 +1,14 @@
 use vertigo::{
     Css,
     Computed,
-    bind, Resource, dom, DomNode,
+    bind, bind_spawn, Resource, dom, DomNode,
 };
 
 use vertigo::{css};
 use crate::app::App;
+use common::format_timestamp;
 use crate::components::{ButtonState};
-use crate::data::ContentType;
+use crate::data::{ContentType, repo_href};
 
 fn css_footer() -> Css {
     css!("
@@ -18,6 +19,33 @@ fn css_footer() -> Css {
     ")
 }
 
+fn css_repo(current: bool) -> Css {
+    let css = css!("
+        padding: 0 5px;
+        color: black;
+    ");
+
+    if current {
+        css.push_str("font-weight: bold; text-decoration: none;")
+    } else {
+        css
+    }
+}
+
+fn css_sync(error: bool) -> Css {
+    let css = css!("
+        margin-left: auto;
+        padding: 0 5px;
+        white-space: nowrap;
+    ");
+
+    if error {
+        css.push_str("color: red;")
+    } else {
+        css
+    }
+}
+
 #[derive(Clone)]
 pub struct MenuComponent {
     app: App,
@@ -66,7 +94,15 @@ fn render_menu(state: &MenuComponent) -> DomNode {
     let button_delete = render_button_on_delete(state);
     let button_search = render_button_search(state);
     let button_move_item = render_button_move_item(state);
+    let button_copy_item = render_button_copy_item(state);
     let button_todo = render_button_todo(state);
+    let button_history = render_button_history(state);
+    let button_trash = render_button_trash(state);
+    let button_tasks = render_button_tasks(state);
+    let repos = render_repos(state);
+    let sync_status = render_sync_status(state);
+    let button_sync = render_button_sync(state);
+    let button_logout = render_button_logout(state);
 
     dom! {
         <div css={css_footer()}>
@@ -77,7 +113,15 @@ fn render_menu(state: &MenuComponent) -> DomNode {
             { button_delete }
             { button_search }
             { button_move_item }
+            { button_copy_item }
+            { button_history }
+            { button_trash }
             { button_todo}
+            { button_tasks }
+            { sync_status }
+            { repos }
+            { button_sync }
+            { button_logout }
         </div>
     }
 }
@@ -160,7 +204,78 @@ fn render_button_move_item(state: &MenuComponent) -> DomNode {
         return ButtonState::active("Przenieś", on_click);
     }))
 }
+
+fn render_button_copy_item(state: &MenuComponent) -> DomNode {
+    let state = state.clone();
+    let app = state.app;
+
+    ButtonState::render(Computed::from(move |context| {
+        let current_content = app.data.tab.select_content.get(context);
+
+        let Some(current_content) = current_content else {
+            return ButtonState::disabled("Kopiuj");
+        };
+
+        let hash = current_content.id.get(context);
+
+        let Resource::Ready(hash) = &hash else {
+            return ButtonState::disabled("Kopiuj");
+        };
+
+        let on_click = bind!(app, current_content, hash, || {
+            app.alert.copy_current(&app, current_content.clone(), &hash);
+        });
+
+        ButtonState::active("Kopiuj", on_click)
+    }))
+}
     
+fn render_button_trash(state: &MenuComponent) -> DomNode {
+    ButtonState::render({
+        let app = state.app.clone();
+
+        Computed::from(move |_| {
+            ButtonState::active("Kosz", bind!(app, || {
+                app.redirect_to_trash();
+            }))
+        })
+    })
+}
+
+fn render_button_tasks(state: &MenuComponent) -> DomNode {
+    ButtonState::render({
+        let app = state.app.clone();
+
+        Computed::from(move |_| {
+            ButtonState::active("Zadania", bind!(app, || {
+                app.redirect_to_todo();
+            }))
+        })
+    })
+}
+
+fn render_button_history(state: &MenuComponent) -> DomNode {
+    ButtonState::render({
+        let app = state.app.clone();
+
+        Computed::from(move |context| {
+            let Some(select_content) = app.data.tab.select_content.get(context) else {
+                return ButtonState::disabled("Historia");
+            };
+
+            let Resource::Ready(ContentType::Text { .. } | ContentType::Markdown { .. }) = select_content.get_content_type(context) else {
+                return ButtonState::disabled("Historia");
+            };
+
+            let on_click = bind!(app, select_content, || {
+                app.redirect_to_history(select_content.clone());
+            });
+
+            ButtonState::active("Historia", on_click)
+        })
+    })
+}
+
 fn render_button_create_file(state: &MenuComponent) -> DomNode {
     ButtonState::render({
         let app = state.app.clone();
@@ -229,4 +344,108 @@ fn render_button_todo(state: &MenuComponent) -> DomNode {
             ButtonState::active(label, on_click)
         })
     })
-}
\ No newline at end of file
+}
+
+//Przełącznik jest widoczny tylko gdy serwer udostępnia więcej niż jedno repozytorium
+fn render_repos(state: &MenuComponent) -> DomNode {
+    let repos = state.app.data.repos.clone();
+
+    let list = Computed::from(move |context| {
+        let Resource::Ready(repos) = repos.get(context) else {
+            return Vec::new();
+        };
+
+        if repos.list.len() < 2 {
+            return Vec::new();
+        }
+
+        repos.list
+            .iter()
+            .map(|name| (name.clone(), *name == repos.current))
+            .collect::<Vec<_>>()
+    });
+
+    list.render_list(
+        |(name, _)| name.clone(),
+        |(name, current)| {
+            let href = repo_href(name);
+
+            dom! {
+                <a css={css_repo(*current)} href={href}>
+                    { name.clone() }
+                </a>
+            }
+        }
+    )
+}
+
+fn render_sync_status(state: &MenuComponent) -> DomNode {
+    let sync = state.app.data.sync.clone();
+
+    let label = Computed::from(move |context| {
+        let Resource::Ready(status) = sync.get(context) else {
+            return (String::from("Synchronizacja: ..."), String::new());
+        };
+
+        let last_success = match status.last_success {
+            0 => String::from("nigdy"),
+            timestamp => format_timestamp(timestamp),
+        };
+
+        let label = format!(
+            "Synchronizacja: {} (+{} / -{}), ostatnia udana: {}",
+            status.status,
+            status.ahead,
+            status.behind,
+            last_success,
+        );
+
+        (label, status.last_error)
+    });
+
+    label.render_value(|(label, last_error)| {
+        let css = css_sync(!last_error.is_empty());
+
+        dom! {
+            <div css={css} title={last_error}>
+                { label }
+            </div>
+        }
+    })
+}
+
+fn render_button_sync(state: &MenuComponent) -> DomNode {
+    ButtonState::render({
+        let sync = state.app.data.sync.clone();
+
+        Computed::from(move |_| {
+            let on_click = bind_spawn!(sync, async move {
+                sync.force().await;
+            });
+
+            ButtonState::active("Synchronizuj", on_click)
+        })
+    })
+}
+
+fn render_button_logout(state: &MenuComponent) -> DomNode {
+    ButtonState::render({
+        let session = state.app.data.session.clone();
+
+        Computed::from(move |context| {
+            let Resource::Ready(whoami) = session.get(context) else {
+                return ButtonState::None;
+            };
+
+            if !whoami.auth {
+                return ButtonState::None;
+            }
+
+            let on_click = bind_spawn!(session, async move {
+                session.logout().await;
+            });
+
+            ButtonState::active(format!("Wyloguj ({})", whoami.name), on_click)
+        })
+    })
+}
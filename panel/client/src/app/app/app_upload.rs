@@ -0,0 +1,347 @@
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use common::{HandlerAddFilesFile, HandlerAddFiles};
+use vertigo::{Css, Computed, Value, DomNode, DropFileEvent, RequestBody, Resource, bind, css, dom, get_driver, transaction};
+use vertigo::struct_mut::CounterMut;
+
+use crate::app::App;
+use crate::components::ButtonState;
+use crate::data::{api_url, create_blob_url, ListItem};
+
+use super::alert::format_size;
+
+//Dla nazwy zajętej w katalogu (lub przez wcześniejszy plik z tego samego upuszczenia)
+//dokładamy numer przed rozszerzeniem: "plik.txt" -> "plik (1).txt"
+fn unique_name(name: &str, is_taken: impl Fn(&str) -> bool) -> String {
+    if !is_taken(name) {
+        return name.to_string();
+    }
+
+    let (base, ext) = match name.rsplit_once('.') {
+        Some((base, ext)) if !base.is_empty() => (base, format!(".{ext}")),
+        _ => (name, String::new()),
+    };
+
+    let mut counter = 1;
+
+    loop {
+        let candidate = format!("{base} ({counter}){ext}");
+
+        if !is_taken(&candidate) {
+            return candidate;
+        }
+
+        counter += 1;
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+enum UploadStatus {
+    Waiting,
+    Sending,
+    Sent,
+    Saved,
+    Error { message: String },
+}
+
+impl UploadStatus {
+    fn label(&self) -> String {
+        match self {
+            UploadStatus::Waiting => "oczekuje".into(),
+            UploadStatus::Sending => "wysyłanie ...".into(),
+            UploadStatus::Sent => "wysłano".into(),
+            UploadStatus::Saved => "zapisano".into(),
+            UploadStatus::Error { message } => format!("błąd: {message}"),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct UploadItem {
+    id: u32,
+    name: String,               //nazwa pod którą plik zostanie zapisany
+    original: String,           //nazwa upuszczonego pliku
+    size: u64,
+    status: Value<UploadStatus>,
+}
+
+impl PartialEq for UploadItem {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+//Pliki upuszczone na listę lub podgląd katalogu. Każdy plik wysyłany jest osobno jako blob,
+//a na końcu wszystkie trafiają do katalogu jednym wywołaniem /add_files (jeden commit)
+#[derive(Clone)]
+pub struct AppUpload {
+    next_id: Rc<CounterMut>,
+    list: Value<Vec<UploadItem>>,
+    progress: Value<bool>,
+}
+
+impl PartialEq for AppUpload {
+    fn eq(&self, other: &Self) -> bool {
+        self.list.id() == other.list.id()
+    }
+}
+
+impl AppUpload {
+    pub fn new() -> AppUpload {
+        AppUpload {
+            next_id: Rc::new(CounterMut::new(1)),
+            list: Value::new(Vec::new()),
+            progress: Value::new(false),
+        }
+    }
+
+    pub fn on_drop(&self, app: &App, dir: ListItem, event: DropFileEvent) {
+        let progress = transaction(|context| self.progress.get(context));
+
+        if progress {
+            app.show_message_error("Poczekaj na zakończenie poprzedniego wysyłania", Some(5000));
+            return;
+        }
+
+        if event.items.is_empty() {
+            return;
+        }
+
+        let mut list = Vec::new();
+        let mut data = Vec::new();
+
+        transaction(|context| {
+            let mut used = HashSet::new();
+
+            for item in event.items {
+                let name = unique_name(&item.name, |name| {
+                    used.contains(name) || matches!(dir.push(name).id.get(context), Resource::Ready(_))
+                });
+
+                used.insert(name.clone());
+
+                list.push(UploadItem {
+                    id: self.next_id.get_next(),
+                    name,
+                    original: item.name,
+                    size: item.data.len() as u64,
+                    status: Value::new(UploadStatus::Waiting),
+                });
+
+                data.push(item.data);
+            }
+        });
+
+        self.progress.set(true);
+        self.list.set(list.clone());
+
+        get_driver().spawn({
+            let upload = self.clone();
+            let app = app.clone();
+
+            async move {
+                upload.send(&app, dir, list, data).await;
+                upload.progress.set(false);
+            }
+        });
+    }
+
+    async fn send(&self, app: &App, dir: ListItem, list: Vec<UploadItem>, data: Vec<Rc<Vec<u8>>>) {
+        let mut files = Vec::new();
+
+        let path = dir.to_vec_path();
+
+        for (item, data) in list.iter().zip(data) {
+            item.status.set(UploadStatus::Sending);
+
+            let mut file_path = path.clone();
+            file_path.push(item.name.clone());
+
+            let response = get_driver()
+                .request_post(create_blob_url(&file_path))
+                .body(RequestBody::Binary(data.as_ref().clone()))
+                .call()
+                .await;
+
+            match response.into_data::<String>() {
+                Ok(blob_id) => {
+                    files.push(HandlerAddFilesFile {
+                        name: item.name.clone(),
+                        blob_id,
+                    });
+                    item.status.set(UploadStatus::Sent);
+                },
+                Err(message) => {
+                    log::error!("Error /create_blob for {} => error={message}", item.original);
+                    item.status.set(UploadStatus::Error { message });
+                }
+            }
+        }
+
+        let failed = list.len() - files.len();
+
+        if files.is_empty() {
+            app.show_message_error("Nie udało się wysłać żadnego pliku", Some(10000));
+            return;
+        }
+
+        let response = get_driver()
+            .request_post(api_url("/add_files"))
+            .body_json(HandlerAddFiles {
+                path,
+                files,
+            })
+            .call()
+            .await;
+
+        let result = response.into_data::<String>().map(|_| ());
+
+        for item in list.iter() {
+            let status = transaction(|context| item.status.get(context));
+
+            if status == UploadStatus::Sent {
+                item.status.set(match &result {
+                    Ok(()) => UploadStatus::Saved,
+                    Err(message) => UploadStatus::Error { message: message.clone() },
+                });
+            }
+        }
+
+        match result {
+            Ok(()) if failed == 0 => {
+                app.show_message_info(format!("Dodano pliki: {}", list.len()), Some(5000));
+            },
+            Ok(()) => {
+                app.show_message_error(format!("Dodano pliki: {}, błędy: {failed}", list.len() - failed), Some(10000));
+            },
+            Err(message) => {
+                log::error!("Problem z dodaniem plików: {message}");
+                app.show_message_error(format!("Problem z dodaniem plików: {message}"), Some(10000));
+            }
+        }
+
+        app.data.git.root.refresh();
+    }
+
+    fn button_close(&self) -> DomNode {
+        ButtonState::render({
+            let upload = self.clone();
+
+            Computed::from(move |context| {
+                if upload.progress.get(context) {
+                    return ButtonState::process("Wysyłanie ...");
+                }
+
+                ButtonState::active("Zamknij", bind!(upload, || {
+                    upload.list.set(Vec::new());
+                }))
+            })
+        })
+    }
+
+    pub fn render(&self) -> DomNode {
+        let upload = self.clone();
+
+        self.list.render_value_option(move |list| {
+            if list.is_empty() {
+                return None;
+            }
+
+            let list = Computed::from(move |_| list.clone());
+            let view = list.render_list(|item| item.id, render_item);
+            let button_close = upload.button_close();
+
+            Some(dom! {
+                <div css={css_wrapper()}>
+                    <div css={css_header()}>
+                        "Wysyłanie plików"
+                        { button_close }
+                    </div>
+                    { view }
+                </div>
+            })
+        })
+    }
+}
+
+fn css_wrapper() -> Css {
+    css!("
+        border-top: 1px solid black;
+        max-height: 200px;
+        overflow-y: auto;
+        font-size: 14px;
+    ")
+}
+
+fn css_header() -> Css {
+    css!("
+        display: flex;
+        align-items: center;
+        justify-content: space-between;
+        padding: 5px;
+        border-bottom: 1px solid #c0c0c0;
+    ")
+}
+
+fn css_item() -> Css {
+    css!("
+        display: flex;
+        padding: 2px 5px;
+    ")
+}
+
+fn css_item_name() -> Css {
+    css!("
+        flex-grow: 1;
+        word-break: break-all;
+    ")
+}
+
+fn css_item_status(error: bool) -> Css {
+    let style = css!("
+        flex-shrink: 0;
+        padding-left: 10px;
+    ");
+
+    match error {
+        true => style.extend(css!("
+            color: red;
+        ")),
+        false => style,
+    }
+}
+
+fn render_item(item: &UploadItem) -> DomNode {
+    let name = match item.name == item.original {
+        true => format!("{} ({})", item.name, format_size(item.size)),
+        false => format!("{} -> {} ({})", item.original, item.name, format_size(item.size)),
+    };
+
+    let status = item.status.render_value(|status| {
+        let error = matches!(status, UploadStatus::Error { .. });
+        let label = status.label();
+
+        dom! {
+            <div css={css_item_status(error)}>{label}</div>
+        }
+    });
+
+    dom! {
+        <div css={css_item()}>
+            <div css={css_item_name()}>{name}</div>
+            { status }
+        </div>
+    }
+}
+
+#[test]
+fn test_unique_name() {
+    let taken = ["notatka.txt", "notatka (1).txt", "katalog", ".env"];
+    let is_taken = |name: &str| taken.contains(&name);
+
+    assert_eq!(unique_name("nowy.txt", is_taken), "nowy.txt");
+    assert_eq!(unique_name("notatka.txt", is_taken), "notatka (2).txt");
+    assert_eq!(unique_name("katalog", is_taken), "katalog (1)");
+    assert_eq!(unique_name(".env", is_taken), ".env (1)");
+}
@@ -1,10 +1,13 @@
 mod app_render;
 mod app_render_list;
 mod app_render_content;
+mod app_render_markdown;
 mod app_render_menu;
+mod app_upload;
 mod alert;
 mod app;
 
 pub use alert::AppIndexAlert;
 pub use app_render::app_index_render;
-pub use app::App;
\ No newline at end of file
+pub use app::App;
+pub use app_upload::AppUpload;
\ No newline at end of file
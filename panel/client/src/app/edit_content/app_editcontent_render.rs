@@ -1,10 +1,12 @@
-use vertigo::{Css, bind, dom, transaction, DomNode};
+use common::MergeChunk;
+use vertigo::{Css, bind, dom, dom_element, transaction, DomNode, Computed};
 use vertigo::{css};
 
 use super::AppEditcontent;
 use super::app_editcontent_state::EditContent;
+use super::conflict::{Conflict, ConflictChoice};
 use crate::app::App;
-use crate::components::button;
+use crate::components::{button, ButtonState};
 
 fn css_wrapper() -> Css {
     css!("
@@ -37,6 +39,121 @@ fn css_body() -> Css {
     ")
 }
 
+fn css_conflict() -> Css {
+    css!("
+        flex-grow: 1;
+        overflow-y: auto;
+        margin: 5px;
+        font-family: monospace;
+        font-size: 13px;
+    ")
+}
+
+fn css_conflict_lines(choice: Option<ConflictChoice>) -> Css {
+    let css = css!("
+        white-space: pre-wrap;
+        padding: 2px 5px;
+    ");
+
+    match choice {
+        Some(ConflictChoice::Current) => css.push_str("
+            background-color: #ff000030;
+        "),
+        Some(ConflictChoice::Edited) => css.push_str("
+            background-color: #00ff0030;
+        "),
+        _ => css,
+    }
+}
+
+fn css_conflict_chunk() -> Css {
+    css!("
+        border: 1px solid #a0a0a0;
+        margin: 5px 0;
+    ")
+}
+
+fn render_lines(lines: &[String], choice: Option<ConflictChoice>) -> DomNode {
+    let text = lines.join("\n");
+
+    dom! {
+        <div css={css_conflict_lines(choice)}>{text}</div>
+    }
+}
+
+fn render_choice_button(conflict: &Conflict, index: usize, label: &'static str, choice: ConflictChoice) -> DomNode {
+    let conflict = conflict.clone();
+
+    ButtonState::render(Computed::from(move |context| {
+        let current = conflict.choices.get(context).get(index).copied().flatten();
+
+        if current == Some(choice) {
+            return ButtonState::disabled(label);
+        }
+
+        let on_click = bind!(conflict, index, choice, || {
+            conflict.choose(index, choice);
+        });
+
+        ButtonState::active(label, on_click)
+    }))
+}
+
+fn render_chunk(conflict: &Conflict, index: usize, chunk: &MergeChunk) -> DomNode {
+    if !chunk.conflict {
+        return render_lines(&chunk.lines, None);
+    }
+
+    dom! {
+        <div css={css_conflict_chunk()}>
+            <div>
+                "Wersja z serwera:"
+                { render_choice_button(conflict, index, "Wybierz wersję z serwera", ConflictChoice::Current) }
+            </div>
+            { render_lines(&chunk.current, Some(ConflictChoice::Current)) }
+            <div>
+                "Twoja wersja:"
+                { render_choice_button(conflict, index, "Wybierz swoją wersję", ConflictChoice::Edited) }
+                { render_choice_button(conflict, index, "Zostaw obie", ConflictChoice::Both) }
+            </div>
+            { render_lines(&chunk.edited, Some(ConflictChoice::Edited)) }
+        </div>
+    }
+}
+
+fn render_conflict(state: &AppEditcontent, conflict: &Conflict) -> DomNode {
+    let result = dom_element! {
+        <div css={css_conflict()} />
+    };
+
+    let button_apply = ButtonState::render({
+        let state = state.clone();
+        let conflict = conflict.clone();
+
+        Computed::from(move |context| {
+            let resolved = conflict.choices.get(context).iter().zip(conflict.chunks.iter()).all(|(choice, chunk)| {
+                !chunk.conflict || choice.is_some()
+            });
+
+            if !resolved {
+                return ButtonState::disabled("Zastosuj rozwiązanie konfliktu");
+            }
+
+            ButtonState::active("Zastosuj rozwiązanie konfliktu", bind!(state, || {
+                state.on_conflict_apply();
+            }))
+        })
+    });
+
+    result.add_child(button_apply);
+
+    for (index, chunk) in conflict.chunks.iter().enumerate() {
+        result.add_child(render_chunk(conflict, index, chunk));
+    }
+
+    result.into()
+}
+
 fn render_textarea(state: &AppEditcontent) -> DomNode {
     let show_textarea = state.content_view.clone().map(|content| {
         if let Some(EditContent { ..}) = content {
@@ -89,7 +206,15 @@ fn render_textarea(state: &AppEditcontent) -> DomNode {
 
 pub fn app_editcontent_render(app: &App, state: &AppEditcontent) -> DomNode {
 
-    let view_textares = render_textarea(state);
+    let view_textares = state.conflict.render_value({
+        let state = state.clone();
+        move |conflict| {
+            match conflict {
+                Some(conflict) => render_conflict(&state, &conflict),
+                None => render_textarea(&state),
+            }
+        }
+    });
 
     let path_view = {
         let path = state.edit_item.to_string_path();
@@ -1,9 +1,10 @@
 
-use common::{HandlerSaveContentBody};
+use common::{HandlerSaveContentBody, HandlerSaveContentResponse};
 use vertigo::{Computed, Value, bind, get_driver, Context, transaction, bind_spawn, DomNode};
 
-use crate::{app::{App, response::check_request_response}, data::{ContentView, ListItem}};
+use crate::{app::{App, response::check_save_response}, data::{ContentView, ListItem, api_url}};
 use super::app_editcontent_render::app_editcontent_render;
+use super::conflict::{Conflict, resolve_chunks};
 
 #[derive(Clone)]
 pub struct EditContent {
@@ -25,6 +26,8 @@ pub struct AppEditcontent {
     pub save_enable: Computed<bool>,
 
     pub content_view: Computed<Option<EditContent>>,        //None - ładowanie
+
+    pub conflict: Value<Option<Conflict>>,                  //konflikt do rozwiązania po nieudanym zapisie
 }
 
 impl AppEditcontent {
@@ -102,6 +105,8 @@ impl AppEditcontent {
 
             save_enable,
             content_view,
+
+            conflict: Value::new(None),
         }
     }
 
@@ -167,14 +172,20 @@ impl AppEditcontent {
             };
 
             let response = get_driver()
-                .request_post("/save_content")
+                .request_post(api_url("/save_content"))
                 .body_json(body)
                 .call().await;
 
             state.action_save.set(false);
 
-            match check_request_response(response) {
-                Ok(()) => {
+            match check_save_response(response) {
+                Ok(HandlerSaveContentResponse { conflict: Some(conflict), .. }) => {
+                    log::info!("Konflikt przy zapisie");
+                    state.conflict.set(Some(Conflict::new(conflict.current_hash, conflict.chunks)));
+                    app.data.git.root.refresh();
+                    app.show_message_error("Plik został zmieniony w międzyczasie, rozwiąż konflikt", Some(5000));
+                },
+                Ok(_) => {
                     log::info!("Zapis udany");
 
                     state.edit_hash.set(None);
@@ -193,5 +204,24 @@ impl AppEditcontent {
             };
         })
     }
-}
 
+    //rozwiązany konflikt trafia do edytora, kolejny zapis odbędzie się względem aktualnej wersji z serwera
+    pub fn on_conflict_apply(&self) {
+        transaction(|context| {
+            let Some(conflict) = self.conflict.get(context) else {
+                return;
+            };
+
+            let choices = conflict.choices.get(context);
+
+            let Some(content) = resolve_chunks(conflict.chunks.as_slice(), choices.as_slice()) else {
+                log::error!("Nie wszystkie konflikty zostały rozwiązane");
+                return;
+            };
+
+            self.edit_content.set(Some(content));
+            self.edit_hash.set(Some(conflict.current_hash));
+            self.conflict.set(None);
+        });
+    }
+}
@@ -0,0 +1,93 @@
+use std::rc::Rc;
+use common::MergeChunk;
+use vertigo::Value;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConflictChoice {
+    Current,
+    Edited,
+    Both,
+}
+
+//Konflikt zwrócony przez serwer przy zapisie, użytkownik wybiera wersję dla każdego fragmentu
+#[derive(Clone, PartialEq)]
+pub struct Conflict {
+    pub current_hash: String,
+    pub chunks: Rc<Vec<MergeChunk>>,
+    pub choices: Value<Vec<Option<ConflictChoice>>>,
+}
+
+impl Conflict {
+    pub fn new(current_hash: String, chunks: Vec<MergeChunk>) -> Conflict {
+        let choices = Value::new(vec![None; chunks.len()]);
+
+        Conflict {
+            current_hash,
+            chunks: Rc::new(chunks),
+            choices,
+        }
+    }
+
+    pub fn choose(&self, index: usize, choice: ConflictChoice) {
+        self.choices.change(|choices| {
+            if let Some(item) = choices.get_mut(index) {
+                *item = Some(choice);
+            }
+        });
+    }
+}
+
+//None - nie wszystkie konflikty zostały rozwiązane
+pub fn resolve_chunks(chunks: &[MergeChunk], choices: &[Option<ConflictChoice>]) -> Option<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for (index, chunk) in chunks.iter().enumerate() {
+        if !chunk.conflict {
+            lines.extend(chunk.lines.iter().cloned());
+            continue;
+        }
+
+        match choices.get(index).copied().flatten()? {
+            ConflictChoice::Current => {
+                lines.extend(chunk.current.iter().cloned());
+            },
+            ConflictChoice::Edited => {
+                lines.extend(chunk.edited.iter().cloned());
+            },
+            ConflictChoice::Both => {
+                lines.extend(chunk.current.iter().cloned());
+                lines.extend(chunk.edited.iter().cloned());
+            }
+        }
+    }
+
+    Some(lines.join("\n"))
+}
+
+#[test]
+fn test_resolve_chunks() {
+    let chunks = vec!(
+        MergeChunk {
+            conflict: false,
+            lines: vec!("a".into()),
+            current: Vec::new(),
+            edited: Vec::new(),
+        },
+        MergeChunk {
+            conflict: true,
+            lines: Vec::new(),
+            current: vec!("B".into()),
+            edited: vec!("BB".into()),
+        },
+        MergeChunk {
+            conflict: false,
+            lines: vec!("c".into(), "".into()),
+            current: Vec::new(),
+            edited: Vec::new(),
+        },
+    );
+
+    assert_eq!(resolve_chunks(&chunks, &[None, None, None]), None);
+    assert_eq!(resolve_chunks(&chunks, &[None, Some(ConflictChoice::Edited), None]), Some("a\nBB\nc\n".to_string()));
+    assert_eq!(resolve_chunks(&chunks, &[None, Some(ConflictChoice::Both), None]), Some("a\nB\nBB\nc\n".to_string()));
+}
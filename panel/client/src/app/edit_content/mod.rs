@@ -1,4 +1,5 @@
 mod app_editcontent_state;
 mod app_editcontent_render;
+mod conflict;
 
 pub use app_editcontent_state::AppEditcontent;
\ No newline at end of file
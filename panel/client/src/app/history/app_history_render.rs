@@ -0,0 +1,232 @@
+use common::{FileHistoryItem, format_timestamp};
+use vertigo::{Css, Computed, dom, DomNode, Resource};
+use vertigo::{css, bind};
+
+use super::AppHistory;
+use super::diff_rows::{DiffSide, diff_rows_keyed};
+
+fn css_wrapper() -> Css {
+    css!("
+        display: flex;
+        flex-direction: column;
+        border: 1px solid black;
+        background-color: #e0e0e0;
+        width: 100vw;
+        height: 100vh;
+    ")
+}
+
+fn css_header() -> Css {
+    css!("
+        display: flex;
+        border-bottom: 1px solid black;
+        padding: 5px;
+    ")
+}
+
+fn css_body() -> Css {
+    css!("
+        display: flex;
+        flex-grow: 1;
+        overflow: hidden;
+    ")
+}
+
+fn css_commits() -> Css {
+    css!("
+        flex-shrink: 0;
+        width: 300px;
+        overflow-y: auto;
+        border-right: 1px solid black;
+    ")
+}
+
+fn css_commit(is_select: bool) -> Css {
+    let css = css!("
+        padding: 5px;
+        border-bottom: 1px solid #c0c0c0;
+        cursor: pointer;
+        font-size: 14px;
+
+        :hover {
+            background-color: #03fc7740;
+        }
+    ");
+
+    if is_select {
+        return css.push_str("
+            background-color: #c0c0c0;
+        ");
+    }
+
+    css
+}
+
+fn css_diff() -> Css {
+    css!("
+        flex-grow: 1;
+        overflow: auto;
+        font-family: monospace;
+        font-size: 13px;
+    ")
+}
+
+fn css_diff_row() -> Css {
+    css!("
+        display: flex;
+    ")
+}
+
+fn css_diff_side(side: &DiffSide) -> Css {
+    let css = css!("
+        width: 50%;
+        padding: 0 5px;
+        white-space: pre-wrap;
+        word-break: break-all;
+        min-height: 16px;
+    ");
+
+    match side {
+        DiffSide::Removed(_) => css.push_str("
+            background-color: #ff000040;
+        "),
+        DiffSide::Added(_) => css.push_str("
+            background-color: #00ff0040;
+        "),
+        DiffSide::Empty => css.push_str("
+            background-color: #d0d0d0;
+        "),
+        DiffSide::Same(_) => css,
+    }
+}
+
+fn render_path(state: &AppHistory) -> DomNode {
+    let path = state.item.to_string_path();
+
+    dom! {
+        <div css={css_header()}>
+            "historia => "
+            <text computed={path} />
+        </div>
+    }
+}
+
+fn render_commit(state: &AppHistory, item: &FileHistoryItem) -> DomNode {
+    let is_select = Computed::from({
+        let state = state.clone();
+        let commit = item.commit.clone();
+        move |context| {
+            match state.get_selected(context) {
+                Resource::Ready(Some(selected)) => selected == commit,
+                _ => false,
+            }
+        }
+    });
+
+    let css = is_select.map(css_commit);
+
+    let on_click = {
+        let selected = state.selected.clone();
+        let commit = item.commit.clone();
+        bind!(selected, commit, || {
+            selected.set(Some(commit.clone()));
+        })
+    };
+
+    let date = format_timestamp(item.timestamp);
+    let author = item.author.clone();
+    let message = item.message.clone();
+
+    dom! {
+        <div css={css} on_click={on_click}>
+            <div>{date} " " {author}</div>
+            <div>{message}</div>
+        </div>
+    }
+}
+
+fn render_commits(state: &AppHistory) -> DomNode {
+    let list = Computed::from({
+        let state = state.clone();
+        move |context| {
+            match state.history.get(context) {
+                Resource::Ready(list) => list.as_ref().clone(),
+                _ => Vec::new(),
+            }
+        }
+    });
+
+    let view = list.render_list(|item| item.commit.clone(), {
+        let state = state.clone();
+        move |item| render_commit(&state, item)
+    });
+
+    dom! {
+        <div css={css_commits()}>
+            {view}
+        </div>
+    }
+}
+
+fn render_side(side: &DiffSide) -> DomNode {
+    let text = match side {
+        DiffSide::Same(line) | DiffSide::Removed(line) | DiffSide::Added(line) => line.clone(),
+        DiffSide::Empty => String::from(""),
+    };
+
+    dom! {
+        <div css={css_diff_side(side)}>{text}</div>
+    }
+}
+
+fn render_diff(state: &AppHistory) -> DomNode {
+    let rows = Computed::from({
+        let state = state.clone();
+        move |context| {
+            match state.get_diff(context) {
+                Resource::Ready(rows) => diff_rows_keyed(rows),
+                _ => Vec::new(),
+            }
+        }
+    });
+
+    let view = rows.render_list(|(key, _)| key.clone(), |(_, row)| {
+        let left = render_side(&row.left);
+        let right = render_side(&row.right);
+
+        dom! {
+            <div css={css_diff_row()}>
+                {left}
+                {right}
+            </div>
+        }
+    });
+
+    dom! {
+        <div css={css_diff()}>
+            {view}
+        </div>
+    }
+}
+
+pub fn app_history_render(state: &AppHistory) -> DomNode {
+    let view_path = render_path(state);
+    let button_back = state.button_on_back();
+    let button_restore = state.button_on_restore();
+    let view_commits = render_commits(state);
+    let view_diff = render_diff(state);
+
+    dom! {
+        <div css={css_wrapper()}>
+            { view_path }
+            <div css={css_header()}>
+                { button_back }
+                { button_restore }
+            </div>
+            <div css={css_body()}>
+                { view_commits }
+                { view_diff }
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,203 @@
+use common::{HandlerFileHistoryBody, HandlerFileHistoryResponse, FileHistoryItem, HandlerDiffCommitsBody, HandlerDiffResponse, DiffLineItem, HandlerRestoreItemBody};
+use vertigo::{Computed, Value, bind, bind_spawn, transaction, get_driver, DomNode, LazyCache, RequestBuilder, AutoMap, Context, Resource};
+
+use crate::{app::{App, response::check_request_response}, components::ButtonState, data::{ListItem, api_url}};
+
+use super::app_history_render::app_history_render;
+use super::diff_rows::{diff_rows, DiffRow};
+
+fn fetch_diff(path: Vec<String>, key: &(String, String)) -> LazyCache<Vec<DiffLineItem>> {
+    let (prev_commit, next_commit) = key;
+
+    RequestBuilder::post(api_url("/diff_commits"))
+        .body_json(HandlerDiffCommitsBody {
+            path,
+            prev_commit: prev_commit.clone(),
+            next_commit: next_commit.clone(),
+        })
+        .lazy_cache(|status, body| {
+            if status == 200 {
+                Some(body.into::<HandlerDiffResponse>().map(|inner| inner.list))
+            } else {
+                None
+            }
+        })
+}
+
+#[derive(Clone)]
+pub struct AppHistory {
+    pub app: App,
+    pub item: ListItem,                             //plik którego historię przeglądamy
+
+    pub history: LazyCache<Vec<FileHistoryItem>>,
+    pub selected: Value<Option<String>>,            //wybrany commit, None - najnowszy
+    pub action_restore: Value<bool>,
+
+    diff: AutoMap<(String, String), LazyCache<Vec<DiffLineItem>>>,   //(poprzedni commit, wybrany commit)
+}
+
+impl PartialEq for AppHistory {
+    fn eq(&self, other: &Self) -> bool {
+        self.item == other.item && self.selected.id() == other.selected.id()
+    }
+}
+
+impl AppHistory {
+    pub fn new(app: &App, item: ListItem) -> AppHistory {
+        let history = RequestBuilder::post(api_url("/file_history"))
+            .body_json(HandlerFileHistoryBody {
+                path: item.to_vec_path(),
+            })
+            .lazy_cache(|status, body| {
+                if status == 200 {
+                    Some(body.into::<HandlerFileHistoryResponse>().map(|inner| inner.list))
+                } else {
+                    None
+                }
+            });
+
+        let diff = AutoMap::new({
+            let path = item.to_vec_path();
+            move |
+                _: &AutoMap<(String, String), LazyCache<Vec<DiffLineItem>>>,
+                key: &(String, String),
+            | -> LazyCache<Vec<DiffLineItem>> {
+                fetch_diff(path.clone(), key)
+            }
+        });
+
+        AppHistory {
+            app: app.clone(),
+            item,
+            history,
+            selected: Value::new(None),
+            action_restore: Value::new(false),
+            diff,
+        }
+    }
+
+    pub fn render(&self) -> DomNode {
+        app_history_render(self)
+    }
+
+    pub fn get_selected(&self, context: &Context) -> Resource<Option<String>> {
+        let list = self.history.get(context)?;
+
+        let selected = match self.selected.get(context) {
+            Some(commit) => Some(commit),
+            None => list.first().map(|item| item.commit.clone()),
+        };
+
+        Resource::Ready(selected)
+    }
+
+    //Zmiany wprowadzone przez wybrany commit - porównanie z poprzednią wersją pliku
+    pub fn get_diff(&self, context: &Context) -> Resource<Vec<DiffRow>> {
+        let list = self.history.get(context)?;
+        let selected = self.get_selected(context)?;
+
+        let position = list
+            .iter()
+            .position(|item| Some(&item.commit) == selected.as_ref());
+
+        let Some(position) = position else {
+            return Resource::Ready(Vec::new());
+        };
+
+        let next_commit = list[position].commit.clone();
+        let prev_commit = match list.get(position + 1) {
+            Some(prev) => prev.commit.clone(),
+            None => String::from(""),
+        };
+
+        let diff = self.diff.get(&(prev_commit, next_commit)).get(context)?;
+        Resource::Ready(diff_rows(diff.as_slice()))
+    }
+
+    pub fn button_on_back(&self) -> DomNode {
+        ButtonState::render({
+            let app = self.app.clone();
+
+            Computed::from(move |_| ButtonState::active("Wróć", bind!(app, || {
+                app.redirect_to_index();
+            })))
+        })
+    }
+
+    async fn on_restore(&self, commit: String) -> Result<(), String> {
+        //pusty hash - element został usunięty i będzie odtworzony
+        let prev_hash = transaction(|context| {
+            match self.item.id.get(context) {
+                Resource::Ready(id) => id,
+                _ => String::from(""),
+            }
+        });
+
+        let body = HandlerRestoreItemBody {
+            path: self.item.to_vec_path(),
+            prev_hash,
+            commit,
+        };
+
+        let response = get_driver()
+            .request_post(api_url("/restore_item"))
+            .body_json(body)
+            .call()
+            .await;
+
+        check_request_response(response)
+    }
+
+    pub fn button_on_restore(&self) -> DomNode {
+        ButtonState::render({
+            let state = self.clone();
+            let app = self.app.clone();
+
+            Computed::from(move |context| {
+                if state.action_restore.get(context) {
+                    return ButtonState::process("Przywracanie ...");
+                }
+
+                let Resource::Ready(list) = state.history.get(context) else {
+                    return ButtonState::disabled("Przywróć tę wersję");
+                };
+
+                let Resource::Ready(Some(commit)) = state.get_selected(context) else {
+                    return ButtonState::disabled("Przywróć tę wersję");
+                };
+
+                //najnowsza wersja jest wersją bieżącą
+                if list.first().map(|item| &item.commit) == Some(&commit) {
+                    return ButtonState::disabled("Przywróć tę wersję");
+                }
+
+                let action = bind_spawn!(state, app, commit, async move {
+                    let action_restore = transaction(|context| {
+                        state.action_restore.get(context)
+                    });
+
+                    if action_restore {
+                        log::error!("Trwa obecnie przywracanie");
+                        return;
+                    }
+
+                    state.action_restore.set(true);
+                    let response = state.on_restore(commit.clone()).await;
+                    state.action_restore.set(false);
+
+                    match response {
+                        Ok(()) => {
+                            log::info!("Przywrócenie udane");
+                            app.redirect_to_index_with_path(state.item.dir(), Some(state.item.name()));
+                        },
+                        Err(message) => {
+                            app.show_message_error(message, Some(10000));
+                        }
+                    };
+                });
+
+                ButtonState::active("Przywróć tę wersję", action)
+            })
+        })
+    }
+}
@@ -0,0 +1,166 @@
+use common::DiffLineItem;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiffSide {
+    Empty,
+    Same(String),
+    Removed(String),
+    Added(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffRow {
+    pub left: DiffSide,
+    pub right: DiffSide,
+}
+
+fn flush(rows: &mut Vec<DiffRow>, removed: &mut Vec<String>, added: &mut Vec<String>) {
+    let len = removed.len().max(added.len());
+    let mut removed = std::mem::take(removed).into_iter();
+    let mut added = std::mem::take(added).into_iter();
+
+    for _ in 0..len {
+        let left = match removed.next() {
+            Some(line) => DiffSide::Removed(line),
+            None => DiffSide::Empty,
+        };
+
+        let right = match added.next() {
+            Some(line) => DiffSide::Added(line),
+            None => DiffSide::Empty,
+        };
+
+        rows.push(DiffRow { left, right });
+    }
+}
+
+//Łączy kolejne bloki linii usuniętych i dodanych w pary, tak aby można je było wyświetlić obok siebie
+pub fn diff_rows(list: &[DiffLineItem]) -> Vec<DiffRow> {
+    let mut rows = Vec::new();
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+
+    for line in list {
+        match line.origin.as_str() {
+            "-" => {
+                if !added.is_empty() {
+                    flush(&mut rows, &mut removed, &mut added);
+                }
+                removed.push(line.content.clone());
+            },
+            "+" => {
+                added.push(line.content.clone());
+            },
+            _ => {
+                flush(&mut rows, &mut removed, &mut added);
+                rows.push(DiffRow {
+                    left: DiffSide::Same(line.content.clone()),
+                    right: DiffSide::Same(line.content.clone()),
+                });
+            }
+        }
+    }
+
+    flush(&mut rows, &mut removed, &mut added);
+    rows
+}
+
+fn side_text(side: &DiffSide) -> &str {
+    match side {
+        DiffSide::Same(line) | DiffSide::Removed(line) | DiffSide::Added(line) => line.as_str(),
+        DiffSide::Empty => "",
+    }
+}
+
+//Klucz wiersza dla render_list - numery linii po obu stronach oraz treść
+pub fn diff_rows_keyed(rows: Vec<DiffRow>) -> Vec<(String, DiffRow)> {
+    let mut left_line = 0;
+    let mut right_line = 0;
+    let mut result = Vec::new();
+
+    for row in rows {
+        let left = match row.left {
+            DiffSide::Empty => String::from("-"),
+            _ => {
+                left_line += 1;
+                left_line.to_string()
+            }
+        };
+
+        let right = match row.right {
+            DiffSide::Empty => String::from("-"),
+            _ => {
+                right_line += 1;
+                right_line.to_string()
+            }
+        };
+
+        let key = format!("{left}:{right}:{}:{}", side_text(&row.left), side_text(&row.right));
+        result.push((key, row));
+    }
+
+    result
+}
+
+#[test]
+fn test_diff_rows_pairs() {
+    let line = |origin: &str, content: &str| DiffLineItem {
+        origin: origin.into(),
+        content: content.into(),
+    };
+
+    let list = vec!(
+        line(" ", "a"),
+        line("-", "b"),
+        line("-", "c"),
+        line("+", "B"),
+        line(" ", "d"),
+    );
+
+    assert_eq!(diff_rows(&list), vec!(
+        DiffRow { left: DiffSide::Same("a".into()), right: DiffSide::Same("a".into()) },
+        DiffRow { left: DiffSide::Removed("b".into()), right: DiffSide::Added("B".into()) },
+        DiffRow { left: DiffSide::Removed("c".into()), right: DiffSide::Empty },
+        DiffRow { left: DiffSide::Same("d".into()), right: DiffSide::Same("d".into()) },
+    ));
+}
+
+#[test]
+fn test_diff_rows_only_added() {
+    let line = |origin: &str, content: &str| DiffLineItem {
+        origin: origin.into(),
+        content: content.into(),
+    };
+
+    let list = vec!(
+        line("+", "a"),
+        line("+", "b"),
+    );
+
+    assert_eq!(diff_rows(&list), vec!(
+        DiffRow { left: DiffSide::Empty, right: DiffSide::Added("a".into()) },
+        DiffRow { left: DiffSide::Empty, right: DiffSide::Added("b".into()) },
+    ));
+}
+
+#[test]
+fn test_diff_rows_keyed() {
+    let line = |origin: &str, content: &str| DiffLineItem {
+        origin: origin.into(),
+        content: content.into(),
+    };
+
+    let list = vec!(
+        line(" ", "a"),
+        line("-", "b"),
+        line("+", "a"),
+        line("+", "a"),
+    );
+
+    let keys = diff_rows_keyed(diff_rows(&list))
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect::<Vec<_>>();
+
+    assert_eq!(keys, vec!("1:1:a:a", "2:2:b:a", "-:3::a"));
+}
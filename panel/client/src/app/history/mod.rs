@@ -0,0 +1,5 @@
+mod app_history_state;
+mod app_history_render;
+mod diff_rows;
+
+pub use app_history_state::{AppHistory};
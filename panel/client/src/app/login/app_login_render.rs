@@ -0,0 +1,104 @@
+use vertigo::{Css, css, dom, DomNode, Computed};
+
+use super::AppLogin;
+use crate::components::ButtonState;
+
+fn css_wrapper() -> Css {
+    css!("
+        display: flex;
+        flex-direction: column;
+        align-items: center;
+        justify-content: center;
+        background-color: #e0e0e0;
+        width: 100vw;
+        height: 100vh;
+    ")
+}
+
+fn css_form() -> Css {
+    css!("
+        display: flex;
+        flex-direction: column;
+        border: 1px solid black;
+        padding: 10px;
+        width: 300px;
+    ")
+}
+
+fn css_input() -> Css {
+    css!("
+        margin-bottom: 5px;
+        line-height: 25px;
+        padding: 0 5px;
+    ")
+}
+
+fn css_error() -> Css {
+    css!("
+        color: red;
+        margin-bottom: 5px;
+    ")
+}
+
+pub fn app_login_render(state: &AppLogin) -> DomNode {
+    let on_input_login = {
+        let login = state.login.clone();
+        move |new_value: String| {
+            login.set(new_value);
+        }
+    };
+
+    let on_input_password = {
+        let password = state.password.clone();
+        move |new_value: String| {
+            password.set(new_value);
+        }
+    };
+
+    let error = state.error.to_computed().render_value_option(|error| {
+        error.map(|error| dom! {
+            <div css={css_error()}>
+                { error }
+            </div>
+        })
+    });
+
+    let button_login = ButtonState::render({
+        let state = state.clone();
+
+        Computed::from(move |context| {
+            if state.action_login.get(context) {
+                return ButtonState::process("Logowanie ...");
+            }
+
+            if state.login_enable.get(context) {
+                ButtonState::active("Zaloguj", state.bind_on_login())
+            } else {
+                ButtonState::disabled("Zaloguj")
+            }
+        })
+    });
+
+    dom! {
+        <div css={css_wrapper()}>
+            <div css={css_form()}>
+                <input
+                    css={css_input()}
+                    placeholder="login"
+                    value={state.login.to_computed()}
+                    on_input={on_input_login}
+                    autofocus=""
+                />
+                <input
+                    css={css_input()}
+                    type="password"
+                    placeholder="hasło"
+                    value={state.password.to_computed()}
+                    on_input={on_input_password}
+                />
+                { error }
+                { button_login }
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,73 @@
+use vertigo::{Computed, Value, transaction, bind_spawn, DomNode};
+
+use crate::data::Data;
+
+use super::app_login_render::app_login_render;
+
+#[derive(Clone, PartialEq)]
+pub struct AppLogin {
+    pub data: Data,
+    pub login: Value<String>,
+    pub password: Value<String>,
+    pub action_login: Value<bool>,
+    pub error: Value<Option<String>>,
+    pub login_enable: Computed<bool>,
+}
+
+impl AppLogin {
+    pub fn new(data: &Data) -> AppLogin {
+        let login = Value::new(String::from(""));
+        let password = Value::new(String::from(""));
+        let action_login = Value::new(false);
+
+        let login_enable = Computed::from({
+            let login = login.clone();
+            let password = password.clone();
+            let action_login = action_login.clone();
+
+            move |context| {
+                !action_login.get(context) && !login.get(context).is_empty() && !password.get(context).is_empty()
+            }
+        });
+
+        AppLogin {
+            data: data.clone(),
+            login,
+            password,
+            action_login,
+            error: Value::new(None),
+            login_enable,
+        }
+    }
+
+    pub fn render(&self) -> DomNode {
+        app_login_render(self)
+    }
+
+    pub fn bind_on_login(&self) -> impl Fn() {
+        let state = self.clone();
+
+        bind_spawn!(state, async move {
+            let (login, password, login_enable) = transaction(|context| (
+                state.login.get(context),
+                state.password.get(context),
+                state.login_enable.get(context),
+            ));
+
+            if !login_enable {
+                return;
+            }
+
+            state.action_login.set(true);
+            state.error.set(None);
+
+            let result = state.data.session.login(login, password).await;
+
+            state.action_login.set(false);
+
+            if let Err(message) = result {
+                state.error.set(Some(message));
+            }
+        })
+    }
+}
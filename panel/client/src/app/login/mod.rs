@@ -0,0 +1,4 @@
+mod app_login_state;
+mod app_login_render;
+
+pub use app_login_state::AppLogin;
@@ -3,6 +3,10 @@ mod new_dir;
 mod edit_content;
 mod rename_item;
 mod newcontent;
+mod history;
+mod trash;
+mod todo;
+mod login;
 mod response;
 
 pub use app::App;
@@ -4,7 +4,7 @@ use vertigo::{Computed, Value, get_driver, transaction, bind_spawn, DomNode};
 use crate::app::App;
 use crate::app::response::check_request_response;
 use crate::components::new_name::{self, NewName};
-use crate::data::ListItem;
+use crate::data::{ListItem, api_url};
 
 use super::app_newdir_render::app_newdir_render;
 
@@ -61,7 +61,7 @@ impl AppNewdir {
             };
 
             let response = get_driver()
-                .request_post("/create_dir")
+                .request_post(api_url("/create_dir"))
                 .body_json(body)
                 .call().await;
 
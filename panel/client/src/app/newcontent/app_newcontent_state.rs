@@ -5,7 +5,7 @@ use crate::app::App;
 use crate::app::newcontent::app_newcontent_render::app_newcontent_render;
 use crate::app::response::check_request_response;
 use crate::components::new_name::NewName;
-use crate::data::ListItem;
+use crate::data::{ListItem, api_url};
 
 #[derive(Clone, PartialEq)]
 pub struct AppNewcontent {
@@ -108,7 +108,7 @@ impl AppNewcontent {
             });
             
             let response = get_driver()
-                .request_post("/create_file")
+                .request_post(api_url("/create_file"))
                 .body_json(body)
                 .call()
                 .await;
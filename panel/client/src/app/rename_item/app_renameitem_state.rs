@@ -1,7 +1,7 @@
 use common::{HandlerRenameItemBody};
 use vertigo::{Computed, Value, get_driver, bind, transaction, bind_spawn, DomNode};
 
-use crate::{app::{App, response::check_request_response}, components::ButtonState, data::ListItem};
+use crate::{app::{App, response::check_request_response}, components::ButtonState, data::{ListItem, api_url}};
 
 use super::app_renameitem_render::app_renameitem_render;
 
@@ -88,7 +88,7 @@ impl AppRenameitem {
         };
 
         let response = get_driver()
-            .request_post("/rename_item")
+            .request_post(api_url("/rename_item"))
             .body_json(body)
             .call()
             .await;
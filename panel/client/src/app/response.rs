@@ -1,5 +1,5 @@
 use vertigo::{RequestResponse};
-use common::RootResponse;
+use common::{RootResponse, HandlerSaveContentResponse};
 
 pub fn check_request_response(response: RequestResponse) -> Result<(), String> {
     if response.status() == Some(200) {
@@ -13,3 +13,14 @@ pub fn check_request_response(response: RequestResponse) -> Result<(), String> {
     }
 }
 
+pub fn check_save_response(response: RequestResponse) -> Result<HandlerSaveContentResponse, String> {
+    if response.status() == Some(200) {
+        match response.into_data::<HandlerSaveContentResponse>() {
+            Ok(response) => Ok(response),
+            Err(message) => Err(format!("status 200, error decode = {message}")),
+        }
+    } else {
+        let status = response.status();
+        Err(format!("http response = {status:?}"))
+    }
+}
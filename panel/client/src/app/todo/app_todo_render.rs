@@ -0,0 +1,212 @@
+use std::rc::Rc;
+use common::TodoFileItem;
+use vertigo::{Css, Computed, dom, dom_element, DomNode, Resource, bind};
+use vertigo::css;
+
+use common::format_timestamp;
+use super::AppTodo;
+use super::app_todo_state::{TodoGroup, TodoSort};
+
+fn css_wrapper() -> Css {
+    css!("
+        display: flex;
+        flex-direction: column;
+        border: 1px solid black;
+        background-color: #e0e0e0;
+        width: 100vw;
+        height: 100vh;
+    ")
+}
+
+fn css_header() -> Css {
+    css!("
+        display: flex;
+        border-bottom: 1px solid black;
+        padding: 5px;
+    ")
+}
+
+fn css_list() -> Css {
+    css!("
+        flex-grow: 1;
+        overflow-y: auto;
+    ")
+}
+
+fn css_group() -> Css {
+    css!("
+        padding: 5px;
+        border-bottom: 1px solid black;
+        font-weight: bold;
+        background-color: #d0d0d0;
+    ")
+}
+
+fn css_item() -> Css {
+    css!("
+        display: flex;
+        align-items: center;
+        padding: 5px;
+        font-size: 14px;
+    ")
+}
+
+fn css_item_date() -> Css {
+    css!("
+        flex-shrink: 0;
+        width: 140px;
+    ")
+}
+
+fn css_item_name() -> Css {
+    css!("
+        flex-grow: 1;
+        word-break: break-all;
+        cursor: pointer;
+        color: blue;
+        text-decoration: underline;
+    ")
+}
+
+fn css_task() -> Css {
+    css!("
+        padding: 0 5px 3px 145px;
+        font-size: 13px;
+        color: #404040;
+        white-space: pre-wrap;
+    ")
+}
+
+fn css_message() -> Css {
+    css!("
+        padding: 5px;
+    ")
+}
+
+fn render_item(state: &AppTodo, item: &TodoFileItem) -> DomNode {
+    let date = match item.timestamp {
+        0 => "-".to_string(),
+        timestamp => format_timestamp(timestamp),
+    };
+
+    let name = item.path.last().cloned().unwrap_or_default();
+    let count = match item.tasks.len() {
+        0 => "".to_string(),
+        count => format!("({count})"),
+    };
+
+    let path = item.path.clone();
+    let on_click = bind!(state, path, || {
+        state.redirect_to_item(&path);
+    });
+
+    let wrapper = dom_element! {
+        <div>
+            <div css={css_item()}>
+                <div css={css_item_date()}>{date}</div>
+                <div css={css_item_name()} on_click={on_click}>{name}</div>
+                <div>{count}</div>
+            </div>
+        </div>
+    };
+
+    for task in item.tasks.iter() {
+        let content = task.content.clone();
+        wrapper.add_child(dom! {
+            <div css={css_task()}>{content}</div>
+        });
+    }
+
+    wrapper.into()
+}
+
+fn render_group(state: &AppTodo, group: &TodoGroup) -> DomNode {
+    let dir = format!("/{}", group.dir.join("/"));
+
+    let wrapper = dom_element! {
+        <div>
+            <div css={css_group()}>{dir}</div>
+        </div>
+    };
+
+    for item in group.list.iter() {
+        wrapper.add_child(render_item(state, item));
+    }
+
+    wrapper.into()
+}
+
+fn render_groups(state: &AppTodo, groups: Rc<Vec<TodoGroup>>) -> DomNode {
+    if groups.is_empty() {
+        return dom! {
+            <div css={css_message()}>"Brak otwartych zadań"</div>
+        };
+    }
+
+    let files = groups.iter().map(|group| group.list.len()).sum::<usize>();
+    let tasks = groups.iter().flat_map(|group| group.list.iter()).map(|item| item.tasks.len()).sum::<usize>();
+    let summary = format!("Pliki: {files}, otwarte zadania: {tasks}");
+
+    let wrapper = dom_element! {
+        <div>
+            <div css={css_message()}>{summary}</div>
+        </div>
+    };
+
+    for group in groups.iter() {
+        wrapper.add_child(render_group(state, group));
+    }
+
+    wrapper.into()
+}
+
+fn render_list(state: &AppTodo) -> DomNode {
+    let groups = Computed::from({
+        let state = state.clone();
+        move |context| state.get_groups(context)
+    });
+
+    groups.render_value({
+        let state = state.clone();
+        move |groups| {
+            match groups {
+                Resource::Ready(groups) => render_groups(&state, groups),
+                Resource::Loading => {
+                    dom! {
+                        <div css={css_message()}>"Wczytywanie ..."</div>
+                    }
+                },
+                Resource::Error(error) => {
+                    let message = format!("Błąd wczytywania zadań: {error}");
+
+                    dom! {
+                        <div css={css_message()}>{message}</div>
+                    }
+                },
+            }
+        }
+    })
+}
+
+pub fn app_todo_render(state: &AppTodo) -> DomNode {
+    let button_back = state.button_on_back();
+    let button_sort_time = state.button_sort(TodoSort::Time, "Sortuj wg daty zmiany");
+    let button_sort_path = state.button_sort(TodoSort::Path, "Sortuj wg ścieżki");
+    let view_list = render_list(state);
+
+    dom! {
+        <div css={css_wrapper()}>
+            <div css={css_header()}>
+                "zadania"
+            </div>
+            <div css={css_header()}>
+                { button_back }
+                { button_sort_time }
+                { button_sort_path }
+            </div>
+            <div css={css_list()}>
+                { view_list }
+            </div>
+        </div>
+    }
+}
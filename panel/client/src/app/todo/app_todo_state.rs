@@ -0,0 +1,163 @@
+use std::rc::Rc;
+use common::{HandlerTodoDashboardResponse, TodoFileItem};
+use vertigo::{Computed, Value, bind, DomNode, LazyCache, RequestBuilder, Context, Resource};
+
+use crate::{app::App, components::ButtonState, data::{api_url, RouterValue}};
+
+use super::app_todo_render::app_todo_render;
+
+fn fetch_todo() -> LazyCache<HandlerTodoDashboardResponse> {
+    RequestBuilder::get(api_url("/todo_dashboard"))
+        .lazy_cache(|status, body| {
+            if status == 200 {
+                Some(body.into::<HandlerTodoDashboardResponse>())
+            } else {
+                None
+            }
+        })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TodoSort {
+    Time,           //najpierw ostatnio zmieniane
+    Path,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TodoGroup {
+    pub dir: Vec<String>,
+    pub list: Vec<TodoFileItem>,
+}
+
+impl TodoGroup {
+    fn timestamp(&self) -> i64 {
+        self.list.iter().map(|item| item.timestamp).max().unwrap_or(0)
+    }
+}
+
+//Pliki pogrupowane według katalogu, w którym się znajdują
+pub fn group_by_dir(list: &[TodoFileItem], sort: TodoSort) -> Vec<TodoGroup> {
+    let mut list = list.to_vec();
+
+    match sort {
+        TodoSort::Time => list.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then_with(|| a.path.cmp(&b.path))),
+        TodoSort::Path => list.sort_by(|a, b| a.path.cmp(&b.path)),
+    }
+
+    let mut groups: Vec<TodoGroup> = Vec::new();
+
+    for item in list {
+        let dir = item.path[0..item.path.len().saturating_sub(1)].to_vec();
+
+        match groups.iter_mut().find(|group| group.dir == dir) {
+            Some(group) => group.list.push(item),
+            None => groups.push(TodoGroup { dir, list: vec![item] }),
+        }
+    }
+
+    match sort {
+        TodoSort::Time => groups.sort_by(|a, b| b.timestamp().cmp(&a.timestamp()).then_with(|| a.dir.cmp(&b.dir))),
+        TodoSort::Path => groups.sort_by(|a, b| a.dir.cmp(&b.dir)),
+    }
+
+    groups
+}
+
+#[derive(Clone)]
+pub struct AppTodo {
+    pub app: App,
+    list: LazyCache<HandlerTodoDashboardResponse>,
+    pub sort: Value<TodoSort>,
+}
+
+impl PartialEq for AppTodo {
+    fn eq(&self, other: &Self) -> bool {
+        self.sort.id() == other.sort.id()
+    }
+}
+
+impl AppTodo {
+    pub fn new(app: &App) -> AppTodo {
+        AppTodo {
+            app: app.clone(),
+            list: fetch_todo(),
+            sort: Value::new(TodoSort::Time),
+        }
+    }
+
+    pub fn render(&self) -> DomNode {
+        app_todo_render(self)
+    }
+
+    pub fn get_groups(&self, context: &Context) -> Resource<Rc<Vec<TodoGroup>>> {
+        let response = self.list.get(context)?;
+        let sort = self.sort.get(context);
+        Resource::Ready(Rc::new(group_by_dir(&response.list, sort)))
+    }
+
+    //Przejście do notatki - ustawienie ścieżki w routerze i powrót do głównego widoku
+    pub fn redirect_to_item(&self, path: &[String]) {
+        let Some((name, dir)) = path.split_last() else {
+            return;
+        };
+
+        self.app.data.tab.redirect_to_view(RouterValue::new(dir.to_vec(), Some(name.clone())));
+        self.app.redirect_to_index_with_root_refresh();
+    }
+
+    pub fn button_on_back(&self) -> DomNode {
+        ButtonState::render({
+            let app = self.app.clone();
+
+            Computed::from(move |_| ButtonState::active("Wróć", bind!(app, || {
+                app.redirect_to_index();
+            })))
+        })
+    }
+
+    pub fn button_sort(&self, sort: TodoSort, label: &'static str) -> DomNode {
+        ButtonState::render({
+            let state = self.clone();
+
+            Computed::from(move |context| {
+                if state.sort.get(context) == sort {
+                    return ButtonState::disabled(label);
+                }
+
+                ButtonState::active(label, bind!(state, || {
+                    state.sort.set(sort);
+                }))
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+fn item(path: &[&str], timestamp: i64) -> TodoFileItem {
+    TodoFileItem {
+        path: path.iter().map(|item| item.to_string()).collect(),
+        todo: true,
+        timestamp,
+        tasks: Vec::new(),
+    }
+}
+
+#[test]
+fn test_group_by_dir() {
+    let list = vec![
+        item(&["b", "x.todo"], 10),
+        item(&["a", "y.todo"], 20),
+        item(&["b", "z.todo"], 30),
+        item(&["root.todo"], 5),
+    ];
+
+    let groups = group_by_dir(&list, TodoSort::Time);
+    let order = groups.iter().map(|group| (group.dir.join("/"), group.list.len())).collect::<Vec<_>>();
+    assert_eq!(order, vec![("b".to_string(), 2), ("a".to_string(), 1), ("".to_string(), 1)]);
+    assert_eq!(groups[0].list[0].path, vec!["b", "z.todo"]);
+
+    let groups = group_by_dir(&list, TodoSort::Path);
+    let order = groups.iter().map(|group| group.dir.join("/")).collect::<Vec<_>>();
+    assert_eq!(order, vec!["", "a", "b"]);
+    assert_eq!(groups[2].list[0].path, vec!["b", "x.todo"]);
+}
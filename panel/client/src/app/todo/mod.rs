@@ -0,0 +1,4 @@
+mod app_todo_state;
+mod app_todo_render;
+
+pub use app_todo_state::AppTodo;
@@ -0,0 +1,148 @@
+use common::HandlerTrashItem;
+use vertigo::{Css, Computed, dom, DomNode, Resource};
+use vertigo::css;
+
+use common::format_timestamp;
+use super::AppTrash;
+
+fn css_wrapper() -> Css {
+    css!("
+        display: flex;
+        flex-direction: column;
+        border: 1px solid black;
+        background-color: #e0e0e0;
+        width: 100vw;
+        height: 100vh;
+    ")
+}
+
+fn css_header() -> Css {
+    css!("
+        display: flex;
+        border-bottom: 1px solid black;
+        padding: 5px;
+    ")
+}
+
+fn css_list() -> Css {
+    css!("
+        flex-grow: 1;
+        overflow-y: auto;
+    ")
+}
+
+fn css_item() -> Css {
+    css!("
+        display: flex;
+        align-items: center;
+        padding: 5px;
+        border-bottom: 1px solid #c0c0c0;
+        font-size: 14px;
+    ")
+}
+
+fn css_item_date() -> Css {
+    css!("
+        flex-shrink: 0;
+        width: 140px;
+    ")
+}
+
+fn css_item_path() -> Css {
+    css!("
+        flex-grow: 1;
+        word-break: break-all;
+    ")
+}
+
+fn css_message() -> Css {
+    css!("
+        padding: 5px;
+    ")
+}
+
+fn render_item(state: &AppTrash, item: &HandlerTrashItem) -> DomNode {
+    let date = format_timestamp(item.timestamp);
+
+    let path = match item.dir {
+        true => format!("{}/", item.original.join("/")),
+        false => item.original.join("/"),
+    };
+
+    let button_restore = state.button_on_restore(item);
+    let button_purge = state.button_on_purge(item);
+
+    dom! {
+        <div css={css_item()}>
+            <div css={css_item_date()}>{date}</div>
+            <div css={css_item_path()}>{path}</div>
+            { button_restore }
+            { button_purge }
+        </div>
+    }
+}
+
+fn render_list(state: &AppTrash) -> DomNode {
+    let list = Computed::from({
+        let state = state.clone();
+        move |context| state.get_list(context)
+    });
+
+    list.render_value({
+        let state = state.clone();
+        move |list| {
+            match list {
+                Resource::Ready(list) if list.is_empty() => {
+                    dom! {
+                        <div css={css_message()}>"Kosz jest pusty"</div>
+                    }
+                },
+                Resource::Ready(list) => {
+                    let list = Computed::from(move |_| list.clone());
+
+                    let view = list.render_list(|item| item.entry.clone(), {
+                        let state = state.clone();
+                        move |item| render_item(&state, item)
+                    });
+
+                    dom! {
+                        <div>
+                            { view }
+                        </div>
+                    }
+                },
+                Resource::Loading => {
+                    dom! {
+                        <div css={css_message()}>"Wczytywanie ..."</div>
+                    }
+                },
+                Resource::Error(error) => {
+                    let message = format!("Błąd wczytywania kosza: {error}");
+
+                    dom! {
+                        <div css={css_message()}>{message}</div>
+                    }
+                },
+            }
+        }
+    })
+}
+
+pub fn app_trash_render(state: &AppTrash) -> DomNode {
+    let button_back = state.button_on_back();
+    let view_list = render_list(state);
+
+    dom! {
+        <div css={css_wrapper()}>
+            <div css={css_header()}>
+                "kosz"
+            </div>
+            <div css={css_header()}>
+                { button_back }
+            </div>
+            <div css={css_list()}>
+                { view_list }
+            </div>
+        </div>
+    }
+}
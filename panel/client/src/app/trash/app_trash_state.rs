@@ -0,0 +1,130 @@
+use common::{HandlerTrashResponse, HandlerTrashItem, HandlerTrashEntryBody};
+use vertigo::{Computed, Value, bind, bind_spawn, transaction, DomNode, LazyCache, RequestBuilder, Context, Resource};
+
+use crate::{app::{App, response::check_request_response}, components::ButtonState, data::api_url};
+
+use super::app_trash_render::app_trash_render;
+
+fn fetch_trash() -> LazyCache<HandlerTrashResponse> {
+    RequestBuilder::get(api_url("/trash"))
+        .lazy_cache(|status, body| {
+            if status == 200 {
+                Some(body.into::<HandlerTrashResponse>())
+            } else {
+                None
+            }
+        })
+}
+
+#[derive(Clone)]
+pub struct AppTrash {
+    pub app: App,
+    list: Value<LazyCache<HandlerTrashResponse>>,
+    pub action: Value<bool>,
+}
+
+impl PartialEq for AppTrash {
+    fn eq(&self, other: &Self) -> bool {
+        self.list.id() == other.list.id()
+    }
+}
+
+impl AppTrash {
+    pub fn new(app: &App) -> AppTrash {
+        AppTrash {
+            app: app.clone(),
+            list: Value::new(fetch_trash()),
+            action: Value::new(false),
+        }
+    }
+
+    pub fn render(&self) -> DomNode {
+        app_trash_render(self)
+    }
+
+    pub fn get_list(&self, context: &Context) -> Resource<Vec<HandlerTrashItem>> {
+        let response = self.list.get(context).get(context)?;
+        Resource::Ready(response.list.clone())
+    }
+
+    pub fn button_on_back(&self) -> DomNode {
+        ButtonState::render({
+            let app = self.app.clone();
+
+            Computed::from(move |_| ButtonState::active("Wróć", bind!(app, || {
+                app.redirect_to_index_with_root_refresh();
+            })))
+        })
+    }
+
+    //restore - przywrócenie elementu, w przeciwnym razie trwałe usunięcie
+    async fn on_action(&self, item: HandlerTrashItem, restore: bool) {
+        let action = transaction(|context| {
+            self.action.get(context)
+        });
+
+        if action {
+            log::error!("Trwa obecnie operacja na koszu");
+            return;
+        }
+
+        let url = match restore {
+            true => "/trash_restore",
+            false => "/trash_purge",
+        };
+
+        self.action.set(true);
+
+        let response = RequestBuilder::post(api_url(url))
+            .body_json(HandlerTrashEntryBody {
+                entry: item.entry,
+                hash: item.hash,
+            })
+            .call()
+            .await;
+
+        self.action.set(false);
+
+        match check_request_response(response) {
+            Ok(()) => {
+                let message = match restore {
+                    true => format!("Przywrócono {}", item.original.join("/")),
+                    false => format!("Usunięto trwale {}", item.original.join("/")),
+                };
+
+                self.app.show_message_info(message, Some(2000));
+                self.list.set(fetch_trash());
+            },
+            Err(message) => {
+                self.app.show_message_error(message, Some(10000));
+            }
+        }
+    }
+
+    pub fn button_on_restore(&self, item: &HandlerTrashItem) -> DomNode {
+        self.button_on_action(item, true, "Przywróć")
+    }
+
+    pub fn button_on_purge(&self, item: &HandlerTrashItem) -> DomNode {
+        self.button_on_action(item, false, "Usuń trwale")
+    }
+
+    fn button_on_action(&self, item: &HandlerTrashItem, restore: bool, label: &'static str) -> DomNode {
+        ButtonState::render({
+            let state = self.clone();
+            let item = item.clone();
+
+            Computed::from(move |context| {
+                if state.action.get(context) {
+                    return ButtonState::disabled(label);
+                }
+
+                let action = bind_spawn!(state, item, async move {
+                    state.on_action(item, restore).await;
+                });
+
+                ButtonState::active(label, action)
+            })
+        })
+    }
+}
@@ -0,0 +1,4 @@
+mod app_trash_state;
+mod app_trash_render;
+
+pub use app_trash_state::AppTrash;
@@ -4,10 +4,10 @@ use vertigo::{
     css, Css,
     dom, Computed, DomNode, dom_element, component
 };
-use crate::data::{Data, ListItem, ListItemType, RouterValue};
+use crate::data::{Data, ListItem, ListItemType, RouterValue, ItemDue};
 use crate::components::icon;
 
-fn css_normal(is_select: bool, is_hover: bool, is_todo: bool) -> Css {
+fn css_normal(is_select: bool, is_hover: bool, is_todo: bool, due: Option<&ItemDue>) -> Css {
     let css = css!("
         display: flex;
         border-bottom: 1px solid #c0c0c0;
@@ -34,6 +34,18 @@ fn css_normal(is_select: bool, is_hover: bool, is_todo: bool) -> Css {
         ");
     }
 
+    if let Some(due) = due {
+        if due.overdue {
+            return css.extend(css!("
+                background: #ff000040;
+            "));
+        }
+
+        return css.extend(css!("
+            background: #ffa50060;
+        "));
+    }
+
     if is_todo {
         return css.extend(css!("
             background: #00ff0080;
@@ -43,6 +55,32 @@ fn css_normal(is_select: bool, is_hover: bool, is_todo: bool) -> Css {
     css
 }
 
+fn css_due() -> Css {
+    css!("
+        flex-shrink: 0;
+        margin-left: auto;
+        padding: 0 5px;
+        font-size: 12px;
+        color: #404040;
+    ")
+}
+
+//Najbliższy termin zadania wyświetlany po prawej stronie elementu
+fn render_due(due: Computed<Option<ItemDue>>) -> DomNode {
+    due.render_value_option(|due| {
+        let due = due?;
+
+        let label = match due.overdue {
+            true => format!("po terminie {}", due.date),
+            false => due.date,
+        };
+
+        Some(dom! {
+            <span css={css_due()}>{label}</span>
+        })
+    })
+}
+
 
 fn icon_arrow_wrapper() -> Css {
     css!("
@@ -148,7 +186,9 @@ pub fn ItemDefault(
             let is_hover = Some(item.name()) == data.tab.select_content_hover.get(context).map(|item| item.name());
             let is_select = Some(item.name()) == data.tab.select_content_current.get(context).map(|item| item.name());
 
-            css_normal(is_select, is_hover, item.is_todo())
+            let due = item.due.get(context);
+
+            css_normal(is_select, is_hover, item.is_todo(), due.as_ref())
         }
     });
 
@@ -198,6 +238,7 @@ pub fn ItemDefault(
                     <span css={label_css(item.is_todo(), item.prirority())}>
                         {name}
                     </span>
+                    {render_due(item.due.clone())}
                 </a>
             }
         }
@@ -212,6 +253,7 @@ pub fn ItemDefault(
                     <span css={label_css(item.is_todo(), item.prirority())}>
                         {name}
                     </span>
+                    {render_due(item.due.clone())}
                 </div>
             }
         }
@@ -238,7 +280,7 @@ pub fn ItemDotHtml(on_click: Rc<dyn Fn()>) {
     dom!{
         <div
             on_click={on_click}
-            css={css_normal(false, false, false)}
+            css={css_normal(false, false, false, None)}
         >
             {icon_arrow_render(false)}
             {icon::icon_dir()}
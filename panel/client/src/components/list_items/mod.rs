@@ -4,7 +4,7 @@ use vertigo::{
     bind, dom, Computed, DomNode, bind_rc
 };
 use crate::components::list_item::ItemDefault;
-use crate::data::{Data, ListItem};
+use crate::data::{Data, ListItem, image_url};
 
 fn item_default_render(data: &Data, item: &ListItem, mouse_over_enable: bool) -> DomNode {
     let tab = &data.tab;
@@ -41,6 +41,9 @@ fn item_default_render(data: &Data, item: &ListItem, mouse_over_enable: bool) ->
     }
 }
 
+//Dwa razy więcej niż szerokość w css, żeby miniatura była ostra na ekranach o dużej gęstości
+const LIST_IMAGE_WIDTH: u32 = 200;
+
 fn css_image() -> Css {
     css!("
         width: 100px;
@@ -59,7 +62,7 @@ fn item_image_render(data: &Data, item: &ListItem, ext: &String) -> DomNode {
         let id = item.id.get(context);
 
         match id {
-            Resource::Ready(id) => Some(format!("/image/{id}/{ext}")),
+            Resource::Ready(id) => Some(image_url(&id, &ext, &item.to_vec_path(), Some(LIST_IMAGE_WIDTH))),
             _ => None
         }
     }));
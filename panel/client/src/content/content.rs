@@ -1,4 +1,5 @@
 use crate::content::words::{WordIter, GroupTextIter};
+use common::parse_task_lines;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub enum ParseTextItem {
@@ -8,7 +9,11 @@ pub enum ParseTextItem {
     Link {
         url: String,
         has_open: bool,
-    }
+    },
+    WikiLink {
+        label: String,
+        path: Vec<String>,          //ścieżka liczona od katalogu głównego repozytorium
+    },
 }
 
 impl ParseTextItem {
@@ -26,9 +31,79 @@ impl ParseTextItem {
             has_open
         }
     }
+
+    #[cfg(test)]
+    pub fn wiki_str(label: &str, path: &[&str]) -> ParseTextItem {
+        ParseTextItem::WikiLink {
+            label: String::from(label),
+            path: path.iter().map(|item| item.to_string()).collect(),
+        }
+    }
+}
+
+//Zawartość "[[ścieżka/do/notatki]]" lub "[[ścieżka/do/notatki|etykieta]]"
+fn parse_wiki_link(inner: &str) -> Option<ParseTextItem> {
+    if inner.contains('\n') || inner.contains('[') || inner.contains(']') {
+        return None;
+    }
+
+    let (target, label) = match inner.split_once('|') {
+        Some((target, label)) => (target, label.trim()),
+        None => (inner, inner.trim()),
+    };
+
+    let path = target
+        .split('/')
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>();
+
+    if path.is_empty() {
+        return None;
+    }
+
+    let label = match label.is_empty() {
+        true => path.join("/"),
+        false => label.to_string(),
+    };
+
+    Some(ParseTextItem::WikiLink { label, path })
+}
+
+//Pierwszy poprawny link "[[...]]" - tekst przed nim, link i tekst po nim
+fn split_wiki_link(text: &str) -> Option<(&str, ParseTextItem, &str)> {
+    let mut from = 0;
+
+    while let Some(start) = text[from..].find("[[").map(|position| position + from) {
+        let inner_start = start + 2;
+        let end = text[inner_start..].find("]]").map(|position| position + inner_start)?;
+
+        if let Some(link) = parse_wiki_link(&text[inner_start..end]) {
+            return Some((&text[..start], link, &text[end + 2..]));
+        }
+
+        from = inner_start;
+    }
+
+    None
 }
 
 pub fn parse_text(text: &str, check_has_open: impl Fn(&String) -> bool) -> Vec<ParseTextItem> {
+    let mut result = Vec::new();
+    let mut rest = text;
+
+    while let Some((before, link, after)) = split_wiki_link(rest) {
+        result.extend(parse_urls(before, &check_has_open));
+        result.push(link);
+        rest = after;
+    }
+
+    result.extend(parse_urls(rest, &check_has_open));
+    result
+}
+
+fn parse_urls(text: &str, check_has_open: &impl Fn(&String) -> bool) -> Vec<ParseTextItem> {
     let iter = WordIter::new(text)
         .map(|item: Result<String, String>| -> Result<String, String> {
 
@@ -60,6 +135,85 @@ pub fn parse_text(text: &str, check_has_open: impl Fn(&String) -> bool) -> Vec<P
     iter.collect()
 }
 
+//Treść podzielona na zwykły tekst i linie zadań, które rysowane są jako checkboxy
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TextBlock {
+    Text {
+        items: Vec<ParseTextItem>,
+    },
+    Task {
+        line: usize,
+        prefix: String,
+        checked: bool,
+        items: Vec<ParseTextItem>,
+    },
+}
+
+pub fn parse_text_blocks(text: &str, check_has_open: impl Fn(&String) -> bool) -> Vec<TextBlock> {
+    let mut result = Vec::new();
+    let mut lines: Vec<&str> = Vec::new();
+
+    let flush = |result: &mut Vec<TextBlock>, lines: &mut Vec<&str>| {
+        if !lines.is_empty() {
+            result.push(TextBlock::Text { items: parse_text(&lines.join("\n"), &check_has_open) });
+            lines.clear();
+        }
+    };
+
+    for (index, (line, task)) in parse_task_lines(text.split('\n')).enumerate() {
+        match task {
+            Some(task) => {
+                flush(&mut result, &mut lines);
+
+                result.push(TextBlock::Task {
+                    line: index,
+                    prefix: task.prefix.to_string(),
+                    checked: task.checked,
+                    items: parse_text(task.text, &check_has_open),
+                });
+            },
+            None => {
+                lines.push(line);
+            }
+        }
+    }
+
+    flush(&mut result, &mut lines);
+    result
+}
+
+#[test]
+fn test_parse_text_blocks() {
+    let out = parse_text_blocks("zakupy\n- [ ] mleko\n[x] chleb\nkoniec\nlisty\n```\n[ ] kod", |_| false);
+
+    assert_eq!(out, vec![
+        TextBlock::Text { items: vec![ParseTextItem::text_str("zakupy")] },
+        TextBlock::Task { line: 1, prefix: "- ".into(), checked: false, items: vec![ParseTextItem::text_str("mleko")] },
+        TextBlock::Task { line: 2, prefix: "".into(), checked: true, items: vec![ParseTextItem::text_str("chleb")] },
+        TextBlock::Text { items: vec![ParseTextItem::text_str("koniec\nlisty\n```\n[ ] kod")] },
+    ]);
+}
+
+#[test]
+fn test_parse_wiki_link() {
+    let out = parse_text("zobacz [[projekty/plan.txt]] oraz [[ notatki / lista.todo | listę ]] https://css.gg", |_| false);
+
+    assert_eq!(out, vec![
+        ParseTextItem::text_str("zobacz "),
+        ParseTextItem::wiki_str("projekty/plan.txt", &["projekty", "plan.txt"]),
+        ParseTextItem::text_str(" oraz "),
+        ParseTextItem::wiki_str("listę", &["notatki", "lista.todo"]),
+        ParseTextItem::text_str(" "),
+        ParseTextItem::link_str("https://css.gg", false),
+    ]);
+
+    let out = parse_text("[[]] [[a\nb]] [[ [[ok]]", |_| false);
+    assert_eq!(out, vec![
+        ParseTextItem::text_str("[[]] [[a\nb]] [[ "),
+        ParseTextItem::wiki_str("ok", &["ok"]),
+    ]);
+}
+
 #[test]
 fn basic_parse() {
     let text = "kolekcja ikon do wykorzystania https://css.gg/play-button";
@@ -0,0 +1,446 @@
+use common::{ListMarker, parse_list_line, is_code_fence};
+use crate::content::{parse_text, ParseTextItem};
+
+//Podzbiór Markdowna używany w notatkach: nagłówki, listy (także z checkboxami), bloki kodu,
+//tabele, cytaty oraz wyróżnienia i linki w tekście
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MarkdownInline {
+    Text {
+        text: String,
+    },
+    Bold {
+        text: String,
+    },
+    Italic {
+        text: String,
+    },
+    Code {
+        text: String,
+    },
+    Link {
+        label: String,
+        url: String,
+        has_open: bool,
+    },
+    WikiLink {
+        label: String,
+        path: Vec<String>,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MarkdownListItem {
+    pub level: usize,                   //poziom zagnieżdżenia, liczony z wcięcia
+    pub marker: ListMarker,
+    pub checked: Option<bool>,          //Some - pozycja z checkboxem "[ ]" lub "[x]"
+    pub line: usize,                    //numer linii w treści, do przełączania checkboxa
+    pub inline: Vec<MarkdownInline>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MarkdownBlock {
+    Heading {
+        level: u8,
+        inline: Vec<MarkdownInline>,
+    },
+    Paragraph {
+        inline: Vec<MarkdownInline>,
+    },
+    Quote {
+        inline: Vec<MarkdownInline>,
+    },
+    List {
+        items: Vec<MarkdownListItem>,
+    },
+    Code {
+        lang: Option<String>,
+        code: String,
+    },
+    Table {
+        header: Vec<Vec<MarkdownInline>>,
+        rows: Vec<Vec<Vec<MarkdownInline>>>,
+    },
+    Rule,
+}
+
+fn find_closing(chars: &[char], from: usize, pattern: &[char]) -> Option<usize> {
+    let mut index = from;
+
+    while index + pattern.len() <= chars.len() {
+        if chars[index..index + pattern.len()] == *pattern {
+            return Some(index);
+        }
+
+        index += 1;
+    }
+
+    None
+}
+
+fn push_text(result: &mut Vec<MarkdownInline>, text: &mut String, check_has_open: &impl Fn(&String) -> bool) {
+    if text.is_empty() {
+        return;
+    }
+
+    for item in parse_text(text, check_has_open) {
+        result.push(match item {
+            ParseTextItem::Text { text } => MarkdownInline::Text { text },
+            ParseTextItem::Link { url, has_open } => MarkdownInline::Link { label: url.clone(), url, has_open },
+            ParseTextItem::WikiLink { label, path } => MarkdownInline::WikiLink { label, path },
+        });
+    }
+
+    text.clear();
+}
+
+pub fn parse_inline(line: &str, check_has_open: &impl Fn(&String) -> bool) -> Vec<MarkdownInline> {
+    let chars = line.chars().collect::<Vec<_>>();
+    let mut result = Vec::new();
+    let mut text = String::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let char = chars[index];
+        let prev_alphanumeric = index > 0 && chars[index - 1].is_alphanumeric();
+
+        if char == '`' {
+            if let Some(end) = find_closing(&chars, index + 1, &['`']) {
+                push_text(&mut result, &mut text, check_has_open);
+                result.push(MarkdownInline::Code { text: chars[index + 1..end].iter().collect() });
+                index = end + 1;
+                continue;
+            }
+        }
+
+        if char == '*' && chars.get(index + 1) == Some(&'*') {
+            if let Some(end) = find_closing(&chars, index + 2, &['*', '*']) {
+                if end > index + 2 {
+                    push_text(&mut result, &mut text, check_has_open);
+                    result.push(MarkdownInline::Bold { text: chars[index + 2..end].iter().collect() });
+                    index = end + 2;
+                    continue;
+                }
+            }
+        }
+
+        //"_" w środku słowa (np. nazwa_pliku) nie jest wyróżnieniem
+        if (char == '*' || char == '_') && !prev_alphanumeric {
+            if let Some(end) = find_closing(&chars, index + 1, &[char]) {
+                if end > index + 1 && !chars[index + 1].is_whitespace() {
+                    push_text(&mut result, &mut text, check_has_open);
+                    result.push(MarkdownInline::Italic { text: chars[index + 1..end].iter().collect() });
+                    index = end + 1;
+                    continue;
+                }
+            }
+        }
+
+        if char == '[' {
+            if let Some(middle) = find_closing(&chars, index + 1, &[']', '(']) {
+                if let Some(end) = find_closing(&chars, middle + 2, &[')']) {
+                    let label: String = chars[index + 1..middle].iter().collect();
+                    let url: String = chars[middle + 2..end].iter().collect();
+
+                    if !label.contains(']') && !url.is_empty() && is_safe_url(&url) {
+                        push_text(&mut result, &mut text, check_has_open);
+                        let has_open = check_has_open(&url);
+                        result.push(MarkdownInline::Link { label, url, has_open });
+                        index = end + 1;
+                        continue;
+                    }
+                }
+            }
+        }
+
+        text.push(char);
+        index += 1;
+    }
+
+    push_text(&mut result, &mut text, check_has_open);
+    result
+}
+
+//Dozwolone są tylko http(s), mailto i adresy względne. Inny schemat (np. javascript:) zostaje zwykłym tekstem.
+//Przeglądarka pomija białe znaki i znaki sterujące w schemacie, więc usuwamy je przed sprawdzeniem
+fn is_safe_url(url: &str) -> bool {
+    let url = url
+        .chars()
+        .filter(|char| !char.is_whitespace() && !char.is_control())
+        .collect::<String>()
+        .to_lowercase();
+
+    let scheme_end = url.find(|char| [':', '/', '?', '#'].contains(&char));
+
+    match scheme_end {
+        Some(index) if url[index..].starts_with(':') => {
+            ["http", "https", "mailto"].contains(&&url[..index])
+        },
+        _ => true,
+    }
+}
+
+fn parse_heading(line: &str) -> Option<(u8, &str)> {
+    let level = line.chars().take_while(|char| *char == '#').count();
+
+    if level == 0 || level > 6 {
+        return None;
+    }
+
+    let rest = &line[level..];
+
+    if rest.is_empty() {
+        return Some((level as u8, ""));
+    }
+
+    let rest = rest.strip_prefix(' ')?;
+    Some((level as u8, rest.trim()))
+}
+
+fn is_rule(line: &str) -> bool {
+    let line = line.replace(' ', "");
+
+    ["-", "*", "_"].iter().any(|char| {
+        line.len() >= 3 && line.chars().all(|item| item.to_string() == *char)
+    })
+}
+
+//Pozycja listy: poziom zagnieżdżenia (2 spacje), znacznik, checkbox i treść.
+//Zadanie "[ ]" bez znacznika listy też jest pozycją listy
+fn parse_list_item(line: &str) -> Option<(usize, ListMarker, Option<bool>, &str)> {
+    let list_line = parse_list_line(line);
+
+    if list_line.marker.is_none() && list_line.checked.is_none() {
+        return None;
+    }
+
+    let marker = list_line.marker.unwrap_or(ListMarker::Bullet);
+    Some((list_line.indent / 2, marker, list_line.checked, list_line.text.trim_end()))
+}
+
+fn split_row(line: &str) -> Vec<&str> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+
+    line.split('|').map(|cell| cell.trim()).collect()
+}
+
+fn is_table_separator(line: &str) -> bool {
+    let line = line.trim();
+
+    line.starts_with('|') && split_row(line).iter().all(|cell| {
+        cell.contains('-') && cell.chars().all(|char| char == '-' || char == ':')
+    })
+}
+
+pub fn parse_markdown(text: &str, check_has_open: impl Fn(&String) -> bool) -> Vec<MarkdownBlock> {
+    let lines = text.lines().collect::<Vec<_>>();
+    let mut result = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut list: Vec<MarkdownListItem> = Vec::new();
+    let mut index = 0;
+
+    let flush = |result: &mut Vec<MarkdownBlock>, paragraph: &mut Vec<&str>, list: &mut Vec<MarkdownListItem>| {
+        if !paragraph.is_empty() {
+            let inline = parse_inline(&paragraph.join(" "), &check_has_open);
+            result.push(MarkdownBlock::Paragraph { inline });
+            paragraph.clear();
+        }
+
+        if !list.is_empty() {
+            result.push(MarkdownBlock::List { items: std::mem::take(list) });
+        }
+    };
+
+    while index < lines.len() {
+        let line = lines[index];
+        let trimmed = line.trim();
+
+        if let Some(lang) = trimmed.strip_prefix("```") {
+            flush(&mut result, &mut paragraph, &mut list);
+
+            let mut code = Vec::new();
+            index += 1;
+
+            while index < lines.len() && !is_code_fence(lines[index]) {
+                code.push(lines[index]);
+                index += 1;
+            }
+
+            let lang = lang.trim();
+
+            result.push(MarkdownBlock::Code {
+                lang: if lang.is_empty() { None } else { Some(lang.to_string()) },
+                code: code.join("\n"),
+            });
+
+            index += 1;
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            flush(&mut result, &mut paragraph, &mut list);
+            index += 1;
+            continue;
+        }
+
+        if let Some((level, rest)) = parse_heading(trimmed) {
+            flush(&mut result, &mut paragraph, &mut list);
+            result.push(MarkdownBlock::Heading { level, inline: parse_inline(rest, &check_has_open) });
+            index += 1;
+            continue;
+        }
+
+        if is_rule(trimmed) {
+            flush(&mut result, &mut paragraph, &mut list);
+            result.push(MarkdownBlock::Rule);
+            index += 1;
+            continue;
+        }
+
+        if trimmed.starts_with('|') && lines.get(index + 1).map(|line| is_table_separator(line)) == Some(true) {
+            flush(&mut result, &mut paragraph, &mut list);
+
+            let header = split_row(trimmed)
+                .into_iter()
+                .map(|cell| parse_inline(cell, &check_has_open))
+                .collect();
+
+            let mut rows = Vec::new();
+            index += 2;
+
+            while index < lines.len() && lines[index].trim().starts_with('|') {
+                let row = split_row(lines[index])
+                    .into_iter()
+                    .map(|cell| parse_inline(cell, &check_has_open))
+                    .collect();
+
+                rows.push(row);
+                index += 1;
+            }
+
+            result.push(MarkdownBlock::Table { header, rows });
+            continue;
+        }
+
+        if let Some(rest) = trimmed.strip_prefix('>') {
+            flush(&mut result, &mut paragraph, &mut list);
+            result.push(MarkdownBlock::Quote { inline: parse_inline(rest.trim(), &check_has_open) });
+            index += 1;
+            continue;
+        }
+
+        if let Some((level, marker, checked, rest)) = parse_list_item(line) {
+            if !paragraph.is_empty() {
+                flush(&mut result, &mut paragraph, &mut list);
+            }
+
+            list.push(MarkdownListItem {
+                level,
+                marker,
+                checked,
+                line: index,
+                inline: parse_inline(rest, &check_has_open),
+            });
+
+            index += 1;
+            continue;
+        }
+
+        if !list.is_empty() {
+            flush(&mut result, &mut paragraph, &mut list);
+        }
+
+        paragraph.push(trimmed);
+        index += 1;
+    }
+
+    flush(&mut result, &mut paragraph, &mut list);
+    result
+}
+
+#[cfg(test)]
+fn text(text: &str) -> MarkdownInline {
+    MarkdownInline::Text { text: text.into() }
+}
+
+#[test]
+fn test_parse_inline() {
+    let out = parse_inline("zwykły **gruby** i *pochyły* oraz `kod` i nazwa_pliku", &|_| false);
+
+    assert_eq!(out, vec![
+        text("zwykły "),
+        MarkdownInline::Bold { text: "gruby".into() },
+        text(" i "),
+        MarkdownInline::Italic { text: "pochyły".into() },
+        text(" oraz "),
+        MarkdownInline::Code { text: "kod".into() },
+        text(" i nazwa_pliku"),
+    ]);
+}
+
+#[test]
+fn test_parse_inline_links() {
+    let out = parse_inline("[strona](https://example.com) i https://css.gg", &|url| url == "https://css.gg");
+
+    assert_eq!(out, vec![
+        MarkdownInline::Link { label: "strona".into(), url: "https://example.com".into(), has_open: false },
+        text(" i "),
+        MarkdownInline::Link { label: "https://css.gg".into(), url: "https://css.gg".into(), has_open: true },
+    ]);
+}
+
+#[test]
+fn test_parse_inline_unsafe_links() {
+    let out = parse_inline("[kliknij](javascript:alert(1))", &|_| false);
+    assert_eq!(out, vec![text("[kliknij](javascript:alert(1))")]);
+
+    let out = parse_inline("[x]( Java\tScript:alert(1))", &|_| false);
+    assert_eq!(out, vec![text("[x]( Java\tScript:alert(1))")]);
+
+    let out = parse_inline("[x](data:text/html,abc)", &|_| false);
+    assert_eq!(out, vec![text("[x](data:text/html,abc)")]);
+
+    assert!(is_safe_url("mailto:jan@example.com"));
+    assert!(is_safe_url("HTTPS://example.com"));
+    assert!(is_safe_url("notatki/plik.txt"));
+    assert!(is_safe_url("./a:b"));
+    assert!(is_safe_url("#sekcja"));
+}
+
+#[test]
+fn test_parse_markdown_blocks() {
+    let source = "# Tytuł\n\npierwsza linia\ndruga linia\n\n- [ ] zadanie\n  - [x] zrobione\n1. punkt\n[ ] bez znacznika\n\n```rust\nfn main() {}\n```\n---\n> cytat";
+    let out = parse_markdown(source, |_| false);
+
+    assert_eq!(out, vec![
+        MarkdownBlock::Heading { level: 1, inline: vec![text("Tytuł")] },
+        MarkdownBlock::Paragraph { inline: vec![text("pierwsza linia druga linia")] },
+        MarkdownBlock::List { items: vec![
+            MarkdownListItem { level: 0, marker: ListMarker::Bullet, checked: Some(false), line: 5, inline: vec![text("zadanie")] },
+            MarkdownListItem { level: 1, marker: ListMarker::Bullet, checked: Some(true), line: 6, inline: vec![text("zrobione")] },
+            MarkdownListItem { level: 0, marker: ListMarker::Number(1), checked: None, line: 7, inline: vec![text("punkt")] },
+            MarkdownListItem { level: 0, marker: ListMarker::Bullet, checked: Some(false), line: 8, inline: vec![text("bez znacznika")] },
+        ]},
+        MarkdownBlock::Code { lang: Some("rust".into()), code: "fn main() {}".into() },
+        MarkdownBlock::Rule,
+        MarkdownBlock::Quote { inline: vec![text("cytat")] },
+    ]);
+}
+
+#[test]
+fn test_parse_markdown_table() {
+    let source = "| nazwa | ilość |\n|---|:---:|\n| jabłka | 3 |\n| gruszki | 5 |";
+    let out = parse_markdown(source, |_| false);
+
+    assert_eq!(out, vec![
+        MarkdownBlock::Table {
+            header: vec![vec![text("nazwa")], vec![text("ilość")]],
+            rows: vec![
+                vec![vec![text("jabłka")], vec![text("3")]],
+                vec![vec![text("gruszki")], vec![text("5")]],
+            ],
+        },
+    ]);
+}
@@ -1,6 +1,11 @@
 mod content;
 mod youtube;
 mod words;
+mod markdown;
+mod task;
 
-pub use content::{parse_text, ParseTextItem};
+pub use content::{parse_text, parse_text_blocks, ParseTextItem, TextBlock};
 pub use youtube::get_thumbnail;
+pub use task::toggle_task;
+pub use markdown::{parse_markdown, MarkdownBlock, MarkdownInline, MarkdownListItem};
+pub use common::ListMarker;
@@ -0,0 +1,28 @@
+use common::parse_task_line;
+
+//Nowa treść z przełączonym zadaniem w linii o podanym numerze (liczonym od zera)
+pub fn toggle_task(content: &str, line_index: usize) -> Option<String> {
+    let mut lines = content.split('\n').map(String::from).collect::<Vec<_>>();
+
+    let line = lines.get_mut(line_index)?;
+    let task = parse_task_line(line)?;
+
+    let checkbox_at = task.prefix.len();
+    let checkbox = match task.checked {
+        true => "[ ]",
+        false => "[x]",
+    };
+
+    line.replace_range(checkbox_at..checkbox_at + 3, checkbox);
+    Some(lines.join("\n"))
+}
+
+#[test]
+fn test_toggle_task() {
+    let content = "lista\r\n- [ ] pierwsze\r\n  * [X] drugie\r\n";
+
+    assert_eq!(toggle_task(content, 1), Some("lista\r\n- [x] pierwsze\r\n  * [X] drugie\r\n".to_string()));
+    assert_eq!(toggle_task(content, 2), Some("lista\r\n- [ ] pierwsze\r\n  * [ ] drugie\r\n".to_string()));
+    assert_eq!(toggle_task(content, 0), None);
+    assert_eq!(toggle_task(content, 10), None);
+}
@@ -2,19 +2,28 @@ use std::{rc::Rc, collections::HashMap};
 use vertigo::{Resource, Context};
 mod node_dir;
 mod node_content;
+mod node_tasks;
+mod node_due;
+mod node_root;
 mod root;
 mod models;
 
 use node_dir::Dir;
 use node_content::Content;
+use node_tasks::Tasks;
+use node_due::Due;
+pub use node_due::ItemDue;
 pub use root::Root;
 
 pub use models::{
     ContentType,
+    AttachmentKind,
     TreeItem,
     ListItem,
     ListItemPath,
     ListItemType,
+    image_url,
+    create_blob_url,
 };
 
 fn get_item_from_map<'a>(current_wsk: &'a Rc<HashMap<String, TreeItem>>, path_item: &String) -> Resource<&'a TreeItem> {
@@ -30,12 +39,15 @@ fn get_item_from_map<'a>(current_wsk: &'a Rc<HashMap<String, TreeItem>>, path_it
     Resource::Ready(wsk_child)
 }
 
-fn move_pointer(context: &Context, state_data: &Git, list: Rc<HashMap<String, TreeItem>>, path_item: &String) -> Resource<Rc<HashMap<String, TreeItem>>> {
+fn move_pointer(context: &Context, state_data: &Git, list: Rc<HashMap<String, TreeItem>>, path: &[String]) -> Resource<Rc<HashMap<String, TreeItem>>> {
+    let Some(path_item) = path.last() else {
+        return Resource::Ready(list);
+    };
 
     let child = get_item_from_map(&list, path_item)?;
 
     if child.dir {
-        let child_list = state_data.dir.get_list(context, &child.id)?;
+        let child_list = state_data.dir.get_list(context, &child.id, path)?;
 
         return Resource::Ready(child_list);
     }
@@ -53,6 +65,8 @@ pub struct ContentView {
 pub struct Git {
     dir: Dir,
     content: Content,
+    tasks: Tasks,
+    due: Due,
     pub root: Root,
 }
 
@@ -66,11 +80,15 @@ impl Git {
     pub fn new() -> Git {
         let dir = Dir::new();
         let content = Content::new();
+        let tasks = Tasks::new();
+        let due = Due::new();
         let root = Root::new();
 
         Git {
             dir,
             content,
+            tasks,
+            due,
             root,
         }
     }
@@ -78,10 +96,10 @@ impl Git {
     pub fn dir_list(&self, context: &Context, path: &[String]) -> Resource<Rc<HashMap<String, TreeItem>>> {
         let root_wsk = self.root.get_current_root(context)?;
 
-        let mut result = self.dir.get_list(context, &root_wsk)?;
+        let mut result = self.dir.get_list(context, &root_wsk, &[])?;
 
-        for path_item in path {
-            result = move_pointer(context, self, result, path_item)?;
+        for index in 1..=path.len() {
+            result = move_pointer(context, self, result, &path[0..index])?;
         }
 
         Resource::Ready(result)
@@ -112,12 +130,28 @@ impl Git {
         self.get_item(context, path.as_slice(), &last)
     }
 
-    pub fn get_list(&self, context: &Context, id: &String) -> Resource<Rc<HashMap<String, TreeItem>>> {
-        self.dir.get_list(context, id)
+    pub fn get_list(&self, context: &Context, id: &String, path: &[String]) -> Resource<Rc<HashMap<String, TreeItem>>> {
+        self.dir.get_list(context, id, path)
+    }
+
+    pub fn open_tasks(&self, context: &Context, path: &[String]) -> u32 {
+        let Resource::Ready(root) = self.root.get_current_root(context) else {
+            return 0;
+        };
+
+        self.tasks.get_open(context, &root, path)
+    }
+
+    pub fn due(&self, context: &Context, path: &[String]) -> Option<ItemDue> {
+        let Resource::Ready(root) = self.root.get_current_root(context) else {
+            return None;
+        };
+
+        self.due.get(context, &root, path)
     }
 
-    pub fn get_content_string(&self, context: &Context, id: &String) -> Resource<Rc<String>> {
-        self.content.get(context, id)
+    pub fn get_content_string(&self, context: &Context, id: &String, path: &[String]) -> Resource<Rc<String>> {
+        self.content.get(context, id, path)
     }
 }
 
@@ -2,10 +2,12 @@ use std::{rc::Rc};
 use std::cmp::Ordering;
 
 use vertigo::{Resource, Context, Computed, bind, AutoMap};
+use common::is_todo_name;
 
 use crate::data::tabs_hash::RouterValue;
+use crate::data::repo::api_url;
 
-use super::{Git, ContentView};
+use super::{Git, ContentView, ItemDue};
 
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct TreeItem {
@@ -36,8 +38,83 @@ fn extract() {
     assert_eq!(get_ext(&name2), Some("txt".to_string()));
 }
 
+//width - miniatura przygotowana przez serwer, bez tego oryginalny plik
+//path - ścieżka pliku, serwer sprawdza uprawnienia i czy id pochodzi z tej ścieżki
+pub fn image_url(id: &str, ext: &str, path: &[String], width: Option<u32>) -> String {
+    let path = encode_url_path(path);
+
+    match width {
+        Some(width) => api_url(&format!("/image/{id}/{ext}?path={path}&w={width}")),
+        None => api_url(&format!("/image/{id}/{ext}?path={path}")),
+    }
+}
+
+//path - docelowa ścieżka wysyłanego pliku, serwer sprawdza do niej uprawnienia zapisu
+pub fn create_blob_url(path: &[String]) -> String {
+    api_url(&format!("/create_blob?path={}", encode_url_path(path)))
+}
+
+//name - nazwa pod jaką przeglądarka zapisze plik, path - jak w image_url
+fn download_url(id: &str, name: &str, path: &[String]) -> String {
+    api_url(&format!("/download/{id}/{}?path={}", encode_url_segment(name), encode_url_path(path)))
+}
+
+//Ścieżka w parametrze url w postaci "a/b/c"
+fn encode_url_path(path: &[String]) -> String {
+    path
+        .iter()
+        .map(|item| encode_url_segment(item))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+//Segment ścieżki w adresie url (np. nazwa pliku w /download)
+fn encode_url_segment(value: &str) -> String {
+    let mut result = String::new();
+
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            result.push(byte as char);
+        } else {
+            result.push_str(&format!("%{byte:02X}"));
+        }
+    }
+
+    result
+}
+
+#[test]
+fn test_encode_url_segment() {
+    assert_eq!(encode_url_segment("plik.pdf"), "plik.pdf");
+    assert_eq!(encode_url_segment("a b/ż.zip"), "a%20b%2F%C5%BC.zip");
+}
+
 ///////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AttachmentKind {
+    Pdf,
+    Audio,
+    Video,
+    Other,
+}
+
+impl AttachmentKind {
+    fn from_ext(ext: &str) -> Option<AttachmentKind> {
+        match ext {
+            "pdf" => Some(AttachmentKind::Pdf),
+            "mp3" | "wav" | "ogg" | "m4a" | "flac" => Some(AttachmentKind::Audio),
+            "mp4" | "webm" | "mov" => Some(AttachmentKind::Video),
+            "zip" | "gz" | "tar" | "7z" | "rar" | "doc" | "docx" | "xls" | "xlsx" | "odt" | "ods" | "gif" | "svg" | "bin" | "exe" => {
+                Some(AttachmentKind::Other)
+            },
+            _ => None,
+        }
+    }
+}
+
+const PREVIEW_WIDTH: u32 = 1024;
+
 #[derive(Clone, PartialEq)]
 pub enum ContentType {
     Dir {
@@ -46,9 +123,18 @@ pub enum ContentType {
     Text {
         content: Rc<String>,
     },
+    Markdown {
+        content: Rc<String>,
+    },
     Image {
-        url: Rc<String>,
-    }
+        url: Rc<String>,            //miniatura do podglądu
+        original: Rc<String>,
+    },
+    Attachment {
+        url: Rc<String>,            //adres do pobrania pliku
+        name: String,
+        kind: AttachmentKind,
+    },
 }
 
 #[derive(Clone, PartialEq)]
@@ -236,13 +322,19 @@ impl ListItem {
             let full_path = full_path.clone();
             let is_dir = is_dir.clone();
             let list = list.clone();
+            let git = git.clone();
 
             move |context: &Context| -> u32 {
                 let is_dir = is_dir.get(context);
 
                 match is_dir {
                     ListItemType::File => {
-                        if is_todo_name(&full_path.name()) {
+                        //otwarte zadania "[ ]" w treści, a plik *.todo bez nich liczy się jako jeden element
+                        let open_tasks = git.open_tasks(context, full_path.as_slice());
+
+                        if open_tasks > 0 {
+                            open_tasks
+                        } else if is_todo_name(&full_path.name()) {
                             1
                         } else {
                             0
@@ -267,6 +359,31 @@ impl ListItem {
             }
         });
 
+        let due = Computed::from({
+            let full_path = full_path.clone();
+            let is_dir = is_dir.clone();
+            let list = list.clone();
+            let git = git.clone();
+
+            move |context: &Context| -> Option<ItemDue> {
+                match is_dir.get(context) {
+                    ListItemType::File => git.due(context, full_path.as_slice()),
+                    ListItemType::Dir => {
+                        let mut due = None;
+
+                        if let Resource::Ready(list) = list.get(context) {
+                            for item in list {
+                                due = ItemDue::earlier(due, item.due.get(context));
+                            }
+                        }
+
+                        due
+                    },
+                    ListItemType::Unknown => None,
+                }
+            }
+        });
+
         let redirect_view = Computed::from({
             let full_path = full_path.clone();
             let is_dir = is_dir.clone();
@@ -296,6 +413,7 @@ impl ListItem {
             id,
             list,
             count_todo,
+            due,
             todo_only,
             redirect_view,
         }
@@ -349,9 +467,13 @@ impl ListItem {
 
         enum FileType {
             Txt,
+            Markdown,
             Image {
                 ext: String,
             },
+            Attachment {
+                kind: AttachmentKind,
+            },
             Unknown,
         }
 
@@ -360,14 +482,18 @@ impl ListItem {
                 match ext.as_str() {
                     "txt" => FileType::Txt,
                     "todo" => FileType::Txt,
+                    "md" => FileType::Markdown,
                     "webp" => FileType::Image { ext: "webp".into() },
                     "jpg" => FileType::Image { ext: "jpg".into() },
                     "jpeg" => FileType::Image { ext: "jpeg".into() },
                     "png" => FileType::Image { ext: "png".into() },
                     "key" => FileType::Txt,
-                    _ => {
-                        log::warn!("Nierozpoznany typ pliku: {ext}");
-                        FileType::Unknown
+                    other => match AttachmentKind::from_ext(other) {
+                        Some(kind) => FileType::Attachment { kind },
+                        None => {
+                            log::warn!("Nierozpoznany typ pliku: {ext}");
+                            FileType::Unknown
+                        }
                     }
                 }
             },
@@ -378,17 +504,31 @@ impl ListItem {
 
         let content = match file_type {
             FileType::Txt => {
-                let content = self.git.get_content_string(context, &id)?;
+                let content = self.git.get_content_string(context, &id, &self.to_vec_path())?;
                 ContentType::Text { content }
             },
+            FileType::Markdown => {
+                let content = self.git.get_content_string(context, &id, &self.to_vec_path())?;
+                ContentType::Markdown { content }
+            },
             FileType::Image { ext } => {
-                let id = &id;
-                let url = format!("/image/{id}/{ext}");
-                ContentType::Image { url: Rc::new(url) }
+                ContentType::Image {
+                    url: Rc::new(image_url(&id, &ext, &self.to_vec_path(), Some(PREVIEW_WIDTH))),
+                    original: Rc::new(image_url(&id, &ext, &self.to_vec_path(), None)),
+                }
             }
+            FileType::Attachment { kind } => {
+                self.attachment(&id, kind)
+            },
             FileType::Unknown => {
-                let content = self.git.get_content_string(context, &id)?;
-                ContentType::Text { content }
+                //plik, który nie jest poprawnym tekstem utf8, pokazujemy jako załącznik do pobrania
+                match self.git.get_content_string(context, &id, &self.to_vec_path()) {
+                    Resource::Ready(content) => ContentType::Text { content },
+                    Resource::Loading => {
+                        return Resource::Loading;
+                    },
+                    Resource::Error(_) => self.attachment(&id, AttachmentKind::Other),
+                }
             }
         };
 
@@ -396,10 +536,21 @@ impl ListItem {
     }
 
 
+    fn attachment(&self, id: &String, kind: AttachmentKind) -> ContentType {
+        let name = self.name();
+        let url = download_url(id, &name, &self.to_vec_path());
+
+        ContentType::Attachment {
+            url: Rc::new(url),
+            name,
+            kind,
+        }
+    }
+
     pub fn get_content(&self, context: &Context) -> Option<ContentView> {
         let content_type = self.get_content_type(context);
 
-        if let Resource::Ready(ContentType::Text { content }) = content_type {
+        if let Resource::Ready(ContentType::Text { content } | ContentType::Markdown { content }) = content_type {
             let Resource::Ready(id) = self.id.get(context) else {
                 return None;
             };
@@ -530,19 +681,3 @@ fn remove_prefix(name: &String) -> String {
 
     out
 }
-
-fn is_todo_name(name: &str) -> bool {
-    let aa = name.chars().rev().take(5).collect::<String>();
-    aa == "odot."
-}
-
-#[test]
-fn test_is_todo_name() {
-    assert_eq!(is_todo_name("dsada.todo"), true);
-    assert_eq!(is_todo_name("dsadasd.todo"), true);
-    assert_eq!(is_todo_name("dsadatodo"), false);
-    assert_eq!(is_todo_name("as"), false);
-    assert_eq!(is_todo_name("todo"), false);
-    assert_eq!(is_todo_name("o"), false);
-    assert_eq!(is_todo_name(""), false);
-}
\ No newline at end of file
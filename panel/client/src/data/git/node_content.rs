@@ -1,6 +1,7 @@
 use std::rc::Rc;
 use common::{ HandlerFetchNodeBody, HandlerFetchNodeResponse };
 use vertigo::{ Resource, AutoMap, LazyCache, Context, RequestBuilder };
+use crate::data::repo::api_url;
 
 #[derive(Clone, Debug)]
 pub struct NodeContent {
@@ -8,10 +9,13 @@ pub struct NodeContent {
 }
 
 impl NodeContent {
-    pub fn new(_: &AutoMap<String, NodeContent>, hash: &String) -> NodeContent {
-        let response = RequestBuilder::post("/fetch_node")
+    pub fn new(_: &AutoMap<(String, Vec<String>), NodeContent>, key: &(String, Vec<String>)) -> NodeContent {
+        let (hash, path) = key;
+
+        let response = RequestBuilder::post(api_url("/fetch_node"))
             .body_json(HandlerFetchNodeBody {
                 hash: hash.clone(),
+                path: path.clone(),
             })
             .lazy_cache(|status, body| {
                 if status == 200 {
@@ -36,7 +40,7 @@ impl NodeContent {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Content {
-    data: AutoMap<String, NodeContent>,
+    data: AutoMap<(String, Vec<String>), NodeContent>,
 }
 
 impl Content {
@@ -48,7 +52,7 @@ impl Content {
         }
     }
 
-    pub fn get(&self, context: &Context, id: &String) -> Resource<Rc<String>> {
-        self.data.get(id).get(context)
+    pub fn get(&self, context: &Context, id: &String, path: &[String]) -> Resource<Rc<String>> {
+        self.data.get(&(id.clone(), path.to_vec())).get(context)
     }
 }
@@ -7,6 +7,7 @@ use vertigo::{
 };
 
 use super::models::{TreeItem};
+use crate::data::repo::api_url;
 
 fn convert(list: Rc<HandlerFetchDirResponse>) -> Rc<HashMap<String, TreeItem>> {
     let mut out: HashMap<String, TreeItem> = HashMap::new();
@@ -35,10 +36,13 @@ impl PartialEq for NodeDir {
 }
 
 impl NodeDir {
-    pub fn new(_: &AutoMap<String, NodeDir>, id: &String) -> NodeDir {
-        let response = RequestBuilder::post("/fetch_tree_item")
+    pub fn new(_: &AutoMap<(String, Vec<String>), NodeDir>, key: &(String, Vec<String>)) -> NodeDir {
+        let (id, path) = key;
+
+        let response = RequestBuilder::post(api_url("/fetch_tree_item"))
             .body_json(HandlerFetchDirBody {
                 id: id.to_string(),
+                path: path.clone(),
             })
             .lazy_cache(|status, body| {
                 if status == 200 {
@@ -72,7 +76,8 @@ impl NodeDir {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Dir {
-    data: AutoMap<String, NodeDir>,
+    //ścieżka jest częścią klucza, bo serwer filtruje listę według uprawnień do ścieżki
+    data: AutoMap<(String, Vec<String>), NodeDir>,
 }
 
 impl Dir {
@@ -84,7 +89,7 @@ impl Dir {
         }
     }
 
-    pub fn get_list(&self, context: &Context, id: &String) -> Resource<Rc<HashMap<String, TreeItem>>> {
-        self.data.get(id).get_list(context)
+    pub fn get_list(&self, context: &Context, id: &String, path: &[String]) -> Resource<Rc<HashMap<String, TreeItem>>> {
+        self.data.get(&(id.clone(), path.to_vec())).get_list(context)
     }
 }
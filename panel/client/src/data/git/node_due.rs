@@ -0,0 +1,97 @@
+use std::{collections::HashMap, rc::Rc};
+use common::HandlerDueResponse;
+use vertigo::{
+    Resource,
+    Computed,
+    Context, RequestBuilder,
+};
+
+use crate::data::repo::api_url;
+use super::node_root::{RootFetch, RootResource};
+
+//Najbliższy termin zadania w pliku lub katalogu
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ItemDue {
+    pub date: String,           //YYYY-MM-DD
+    pub overdue: bool,
+}
+
+impl ItemDue {
+    //wcześniejszy z dwóch terminów, przeterminowane są zawsze wcześniejsze niż nadchodzące
+    pub fn earlier(first: Option<ItemDue>, second: Option<ItemDue>) -> Option<ItemDue> {
+        match (first, second) {
+            (Some(first), Some(second)) if second.date < first.date => Some(second),
+            (Some(first), _) => Some(first),
+            (None, second) => second,
+        }
+    }
+}
+
+fn convert(response: Rc<HandlerDueResponse>) -> Rc<HashMap<Vec<String>, ItemDue>> {
+    let mut out: HashMap<Vec<String>, ItemDue> = HashMap::new();
+
+    for item in response.list.iter() {
+        let due = ItemDue {
+            date: item.due.clone(),
+            overdue: item.overdue,
+        };
+
+        let current = out.remove(&item.path);
+
+        if let Some(due) = ItemDue::earlier(current, Some(due)) {
+            out.insert(item.path.clone(), due);
+        }
+    }
+
+    Rc::new(out)
+}
+
+fn fetch() -> RootResource<HashMap<Vec<String>, ItemDue>> {
+    let response = RequestBuilder::get(api_url("/due"))
+        .lazy_cache(|status, body| {
+            if status == 200 {
+                Some(body.into::<HandlerDueResponse>())
+            } else {
+                None
+            }
+        });
+
+    Computed::from(move |context| {
+        let resource = response.get(context);
+        resource.map(convert)
+    })
+}
+
+//Przeterminowane i nadchodzące zadania "@YYYY-MM-DD", wyliczane przez serwer dla całego drzewa.
+//Pobierane na nowo po każdej zmianie roota, tak jak Tasks
+#[derive(Clone, Debug, PartialEq)]
+pub struct Due {
+    data: RootFetch<HashMap<Vec<String>, ItemDue>>,
+}
+
+impl Due {
+    pub fn new() -> Due {
+        Due {
+            data: RootFetch::new(fetch),
+        }
+    }
+
+    pub fn get(&self, context: &Context, root: &String, path: &[String]) -> Option<ItemDue> {
+        let Resource::Ready(list) = self.data.get(context, root) else {
+            return None;
+        };
+
+        list.get(path).cloned()
+    }
+}
+
+#[test]
+fn test_item_due_earlier() {
+    let overdue = ItemDue { date: "2026-10-01".into(), overdue: true };
+    let upcoming = ItemDue { date: "2026-10-20".into(), overdue: false };
+
+    assert_eq!(ItemDue::earlier(Some(upcoming.clone()), Some(overdue.clone())), Some(overdue.clone()));
+    assert_eq!(ItemDue::earlier(Some(overdue.clone()), Some(upcoming.clone())), Some(overdue.clone()));
+    assert_eq!(ItemDue::earlier(None, Some(upcoming.clone())), Some(upcoming));
+    assert_eq!(ItemDue::earlier(None, None), None);
+}
@@ -0,0 +1,60 @@
+use std::{cell::RefCell, rc::Rc};
+use vertigo::{Resource, Computed, Context};
+
+pub type RootResource<V> = Computed<Resource<Rc<V>>>;
+type CurrentRoot<V> = Option<(String, RootResource<V>)>;
+
+//Odpowiedź serwera liczona dla całego drzewa (/tasks, /due), pobierana na nowo po każdej zmianie roota.
+//Trzymany jest tylko wpis dla ostatniego roota, poprzedni jest zwalniany razem z odpowiedzią
+pub struct RootFetch<V: PartialEq + 'static> {
+    create: Rc<dyn Fn() -> RootResource<V>>,
+    current: Rc<RefCell<CurrentRoot<V>>>,
+}
+
+impl<V: PartialEq + 'static> Clone for RootFetch<V> {
+    fn clone(&self) -> Self {
+        RootFetch {
+            create: self.create.clone(),
+            current: self.current.clone(),
+        }
+    }
+}
+
+impl<V: PartialEq + 'static> PartialEq for RootFetch<V> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.current, &other.current)
+    }
+}
+
+impl<V: PartialEq + 'static> std::fmt::Debug for RootFetch<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let root = self.current.borrow().as_ref().map(|(root, _)| root.clone());
+        f.debug_struct("RootFetch").field("root", &root).finish()
+    }
+}
+
+impl<V: PartialEq + 'static> RootFetch<V> {
+    pub fn new(create: impl Fn() -> RootResource<V> + 'static) -> RootFetch<V> {
+        RootFetch {
+            create: Rc::new(create),
+            current: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    pub fn get(&self, context: &Context, root: &String) -> Resource<Rc<V>> {
+        let resource = {
+            let mut current = self.current.borrow_mut();
+
+            match current.as_ref() {
+                Some((current_root, resource)) if current_root == root => resource.clone(),
+                _ => {
+                    let resource = (self.create)();
+                    *current = Some((root.clone(), resource.clone()));
+                    resource
+                }
+            }
+        };
+
+        resource.get(context)
+    }
+}
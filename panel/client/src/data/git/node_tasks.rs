@@ -0,0 +1,59 @@
+use std::{collections::HashMap, rc::Rc};
+use common::HandlerTasksResponse;
+use vertigo::{
+    Resource,
+    Computed,
+    Context, RequestBuilder,
+};
+
+use crate::data::repo::api_url;
+use super::node_root::{RootFetch, RootResource};
+
+fn convert(response: Rc<HandlerTasksResponse>) -> Rc<HashMap<Vec<String>, u32>> {
+    let mut out = HashMap::new();
+
+    for item in response.list.iter() {
+        out.insert(item.path.clone(), item.open);
+    }
+
+    Rc::new(out)
+}
+
+fn fetch() -> RootResource<HashMap<Vec<String>, u32>> {
+    let response = RequestBuilder::get(api_url("/tasks"))
+        .lazy_cache(|status, body| {
+            if status == 200 {
+                Some(body.into::<HandlerTasksResponse>())
+            } else {
+                None
+            }
+        });
+
+    Computed::from(move |context| {
+        let resource = response.get(context);
+        resource.map(convert)
+    })
+}
+
+//Ilość otwartych zadań "[ ]" w plikach, liczona przez serwer dla całego drzewa.
+//Po każdej zmianie roota lista pobierana jest na nowo
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tasks {
+    data: RootFetch<HashMap<Vec<String>, u32>>,
+}
+
+impl Tasks {
+    pub fn new() -> Tasks {
+        Tasks {
+            data: RootFetch::new(fetch),
+        }
+    }
+
+    pub fn get_open(&self, context: &Context, root: &String, path: &[String]) -> u32 {
+        let Resource::Ready(list) = self.data.get(context, root) else {
+            return 0;
+        };
+
+        list.get(path).copied().unwrap_or(0)
+    }
+}
@@ -1,9 +1,12 @@
+use std::rc::Rc;
 use common::RootResponse;
 use vertigo::{
     Resource,
     Value,
     LazyCache, Context, RequestBuilder,
+    get_driver, transaction, DropResource, WebsocketMessage,
 };
+use crate::data::repo::{api_url, location_get};
 
 #[derive(Clone, PartialEq)]
 pub struct RootNode {
@@ -12,7 +15,7 @@ pub struct RootNode {
 
 impl RootNode {
     fn new() -> RootNode {
-        let root = RequestBuilder::get("/fetch_root")
+        let root = RequestBuilder::get(api_url("/fetch_root"))
             .lazy_cache(|status, body| {
                 if status == 200 {
                     Some(body.into::<RootResponse>())
@@ -32,10 +35,26 @@ impl RootNode {
     }
 }
 
-#[derive(Clone, PartialEq)]
+fn websocket_url() -> String {
+    let protocol = match location_get("protocol").as_str() {
+        "https:" => "wss:",
+        _ => "ws:",
+    };
+
+    format!("{protocol}//{}{}", location_get("host"), api_url("/root_ws"))
+}
+
+#[derive(Clone)]
 pub struct Root {
     pub current: Value<RootNode>,
     //list: Value<VecDeque<RootNode>>,      //todo zaimplementowach historie, zeby zniwelowac ilosc migań
+    _subscription: Rc<DropResource>,
+}
+
+impl PartialEq for Root {
+    fn eq(&self, other: &Self) -> bool {
+        self.current.id() == other.current.id()
+    }
 }
 
 impl Default for Root {
@@ -48,9 +67,36 @@ impl Root {
     pub fn new() -> Root {
         let current = RootNode::new();
         let current = Value::new(current);
+
+        //serwer wysyła nowy root za każdym razem gdy zmieni się head gałęzi (również po synchronizacji)
+        let subscription = get_driver().websocket(websocket_url(), {
+            let current = current.clone();
+
+            move |message| {
+                match message {
+                    WebsocketMessage::Message(new_root) => {
+                        let current_root = transaction(|context| {
+                            current.get(context).get(context)
+                        });
+
+                        if let Resource::Ready(current_root) = current_root {
+                            if current_root != new_root {
+                                log::info!("New root from server {new_root}");
+                                current.set(RootNode::new());
+                            }
+                        }
+                    },
+                    WebsocketMessage::Connection(_) => {},
+                    WebsocketMessage::Close => {
+                        log::warn!("Root subscription closed");
+                    },
+                }
+            }
+        });
        
         Root {
             current,
+            _subscription: Rc::new(subscription),
         }
     }
 
@@ -1,14 +1,18 @@
-use self::{git::Git, tabs::TabPath};
+use self::{git::Git, tabs::TabPath, sync_status::SyncStatus, repo::Repos, session::Session};
 
 mod git;
 mod tabs_hash;
 mod tabs;
 mod open_links;
+mod sync_status;
+mod repo;
+mod session;
 
-pub use git::{ContentType, ContentView};
-pub use git::{ListItem, ListItemPath, ListItemType};
+pub use git::{ContentType, ContentView, AttachmentKind, image_url, create_blob_url};
+pub use git::{ListItem, ListItemPath, ListItemType, ItemDue};
 pub use open_links::OpenLinks;
 pub use tabs_hash::{Router, RouterValue};
+pub use repo::{api_url, repo_href};
 use vertigo::{AutoMap, Value};
 
 #[derive(Clone, PartialEq)]
@@ -57,6 +61,9 @@ pub struct Data {
     pub git: Git,
     pub tab: TabPath,
     pub items: AutoMapListItem,
+    pub sync: SyncStatus,
+    pub repos: Repos,
+    pub session: Session,
 }
 
 //TODO - zastanowić sie nad zamianą Rc<Vec<String>> na lzejszą strukturę
@@ -69,10 +76,17 @@ impl Data {
 
         let tab = TabPath::new(&items);
 
+        let sync = SyncStatus::new();
+        let repos = Repos::new();
+        let session = Session::new();
+
         Data {
             git,
             tab,
-            items
+            items,
+            sync,
+            repos,
+            session,
         }
     }
 }
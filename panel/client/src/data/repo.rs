@@ -0,0 +1,100 @@
+use std::cell::RefCell;
+use common::HandlerReposResponse;
+use vertigo::{
+    Resource,
+    LazyCache, Context, RequestBuilder,
+    get_driver, JsValue,
+};
+
+pub fn location_get(name: &'static str) -> String {
+    let value = get_driver()
+        .dom_access()
+        .root("window")
+        .get("location")
+        .get(name)
+        .fetch();
+
+    match value {
+        JsValue::String(value) => value,
+        _ => String::from(""),
+    }
+}
+
+//Aplikacja otwarta pod adresem "/repo/<nazwa>/" pracuje na wskazanym repozytorium
+fn repo_from_pathname(pathname: &str) -> Option<String> {
+    let rest = pathname.strip_prefix("/repo/")?;
+    let name = rest.split('/').next()?;
+
+    if name.is_empty() {
+        return None;
+    }
+
+    Some(name.to_string())
+}
+
+thread_local! {
+    static API_PREFIX: RefCell<Option<String>> = RefCell::new(None);
+}
+
+fn api_prefix() -> String {
+    API_PREFIX.with(|prefix| {
+        prefix
+            .borrow_mut()
+            .get_or_insert_with(|| {
+                match repo_from_pathname(&location_get("pathname")) {
+                    Some(name) => format!("/repo/{name}"),
+                    None => String::from(""),
+                }
+            })
+            .clone()
+    })
+}
+
+pub fn api_url(path: &str) -> String {
+    format!("{}{path}", api_prefix())
+}
+
+pub fn repo_href(name: &str) -> String {
+    format!("/repo/{name}/")
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Repos {
+    list: LazyCache<HandlerReposResponse>,
+}
+
+impl Default for Repos {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Repos {
+    pub fn new() -> Repos {
+        let list = RequestBuilder::get(api_url("/repos"))
+            .lazy_cache(|status, body| {
+                if status == 200 {
+                    Some(body.into::<HandlerReposResponse>())
+                } else {
+                    None
+                }
+            });
+
+        Repos {
+            list,
+        }
+    }
+
+    pub fn get(&self, context: &Context) -> Resource<HandlerReposResponse> {
+        self.list.get(context)
+    }
+}
+
+#[test]
+fn test_repo_from_pathname() {
+    assert_eq!(repo_from_pathname("/"), None);
+    assert_eq!(repo_from_pathname("/repo/"), None);
+    assert_eq!(repo_from_pathname("/repo/team"), Some(String::from("team")));
+    assert_eq!(repo_from_pathname("/repo/team/"), Some(String::from("team")));
+    assert_eq!(repo_from_pathname("/other/team/"), None);
+}
@@ -0,0 +1,89 @@
+use common::{HandlerLoginBody, HandlerWhoamiResponse};
+use vertigo::{
+    Resource,
+    LazyCache, Context, RequestBuilder,
+    get_driver,
+};
+
+fn page_reload() {
+    get_driver()
+        .dom_access()
+        .root("window")
+        .get("location")
+        .call("reload", Vec::new())
+        .exec();
+}
+
+//Sesja jest wspólna dla wszystkich repozytoriów, dlatego adresy są bez prefiksu repozytorium
+#[derive(Clone, PartialEq)]
+pub struct Session {
+    whoami: LazyCache<HandlerWhoamiResponse>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Session {
+    pub fn new() -> Session {
+        let whoami = RequestBuilder::get("/whoami")
+            .lazy_cache(|status, body| {
+                if status == 200 {
+                    Some(body.into::<HandlerWhoamiResponse>())
+                } else {
+                    None
+                }
+            });
+
+        Session {
+            whoami,
+        }
+    }
+
+    pub fn get(&self, context: &Context) -> Resource<HandlerWhoamiResponse> {
+        self.whoami.get(context)
+    }
+
+    pub fn is_login_required(&self, context: &Context) -> bool {
+        match self.get(context) {
+            Resource::Ready(whoami) => whoami.auth && whoami.login.is_empty(),
+            _ => false,
+        }
+    }
+
+    //Po zalogowaniu przeładowujemy stronę, żeby wszystkie dane zostały pobrane z nową sesją
+    pub async fn login(&self, login: String, password: String) -> Result<(), String> {
+        let response = get_driver()
+            .request_post("/login")
+            .body_json(HandlerLoginBody {
+                login,
+                password,
+            })
+            .call()
+            .await;
+
+        match response.status() {
+            Some(200) => {
+                page_reload();
+                Ok(())
+            },
+            Some(401) => Err(String::from("Nieprawidłowy login lub hasło")),
+            status => Err(format!("http response = {status:?}")),
+        }
+    }
+
+    pub async fn logout(&self) {
+        let response = get_driver()
+            .request_post("/logout")
+            .call()
+            .await;
+
+        if response.status() != Some(200) {
+            log::error!("Problem z wylogowaniem");
+        }
+
+        page_reload();
+    }
+}
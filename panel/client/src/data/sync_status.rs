@@ -0,0 +1,83 @@
+use std::rc::Rc;
+use common::HandlerSyncStatusResponse;
+use vertigo::{
+    Resource,
+    Value,
+    LazyCache, Context, RequestBuilder,
+    get_driver, DropResource,
+};
+use super::repo::api_url;
+
+//Co ile odpytywać serwer o stan synchronizacji (ms)
+const SYNC_STATUS_INTERVAL: u32 = 10_000;
+
+fn fetch_sync_status() -> LazyCache<HandlerSyncStatusResponse> {
+    RequestBuilder::get(api_url("/sync_status"))
+        .lazy_cache(|status, body| {
+            if status == 200 {
+                Some(body.into::<HandlerSyncStatusResponse>())
+            } else {
+                None
+            }
+        })
+}
+
+#[derive(Clone)]
+pub struct SyncStatus {
+    current: Value<LazyCache<HandlerSyncStatusResponse>>,
+    _interval: Rc<DropResource>,
+}
+
+impl PartialEq for SyncStatus {
+    fn eq(&self, other: &Self) -> bool {
+        self.current.id() == other.current.id()
+    }
+}
+
+impl Default for SyncStatus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SyncStatus {
+    pub fn new() -> SyncStatus {
+        let current = Value::new(fetch_sync_status());
+
+        let interval = get_driver().set_interval(SYNC_STATUS_INTERVAL, {
+            let current = current.clone();
+            move || {
+                current.set(fetch_sync_status());
+            }
+        });
+
+        SyncStatus {
+            current,
+            _interval: Rc::new(interval),
+        }
+    }
+
+    pub fn get(&self, context: &Context) -> Resource<HandlerSyncStatusResponse> {
+        let current = self.current.get(context);
+        current.get(context)
+    }
+
+    pub fn refresh(&self) {
+        self.current.set(fetch_sync_status());
+    }
+
+    //Synchronizacja wykonuje się na serwerze w tle, stan odświeżamy z opóźnieniem
+    pub async fn force(&self) {
+        let response = get_driver()
+            .request_post(api_url("/sync_force"))
+            .call()
+            .await;
+
+        if response.status() != Some(200) {
+            log::error!("Problem z wymuszeniem synchronizacji");
+        }
+
+        get_driver().sleep(2000).await;
+        self.refresh();
+    }
+}
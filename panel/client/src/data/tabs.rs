@@ -4,7 +4,7 @@ use vertigo::{Resource, Computed, Context, transaction, bind, bind_rc};
 use super::{
     git::ListItem,
     open_links::OpenLinks,
-    ContentType, tabs_hash::Router, ListItemType, AutoMapListItem, ListItemPath
+    ContentType, tabs_hash::{Router, RouterValue}, ListItemType, AutoMapListItem, ListItemPath
 };
 
 #[derive(Clone, PartialEq)]
@@ -194,6 +194,10 @@ impl TabPath {
         self.router.set(dir, item);
     }
 
+    pub fn redirect_to_view(&self, view: RouterValue) {
+        self.router.redirect_to(view);
+    }
+
     pub fn set_path(&self, path: ListItem) {
         self.router.set(path, None);
     }
@@ -1,5 +1,10 @@
 #![allow(clippy::new_without_default)]
 
+mod tasks;
+mod time;
+pub use tasks::{is_todo_name, ListMarker, ListLine, parse_list_line, TaskLine, parse_task_line, is_code_fence, parse_task_lines};
+pub use time::{date_from_seconds, format_timestamp};
+
 pub type TimestampType = u128;
 
 #[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
@@ -15,6 +20,7 @@ pub struct RootResponse {
 #[derive(Debug, PartialEq, Eq)]
 pub struct HandlerFetchDirBody {
     pub id: String,
+    pub path: Vec<String>,
 }
 
 #[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
@@ -51,6 +57,7 @@ impl HandlerFetchDirResponse {
 #[derive(Debug, PartialEq, Eq)]
 pub struct HandlerFetchNodeBody {
     pub hash: String,
+    pub path: Vec<String>,
 }
 
 
@@ -71,6 +78,32 @@ pub struct HandlerSaveContentBody {
     pub new_content: String,
 }
 
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MergeChunk {
+    pub conflict: bool,
+    pub lines: Vec<String>,         //conflict == false
+    pub current: Vec<String>,       //conflict == true - wersja z repozytorium
+    pub edited: Vec<String>,        //conflict == true - wersja użytkownika
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct SaveContentConflict {
+    pub current_hash: String,       //hash aktualnej zawartości pliku, z którą wystąpił konflikt
+    pub chunks: Vec<MergeChunk>,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerSaveContentResponse {
+    pub root: String,
+    pub conflict: Option<SaveContentConflict>,      //Some - zmiany nie zostały zapisane
+}
+
 
 #[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
@@ -110,6 +143,8 @@ pub struct HandlerRenameItemBody {
 pub struct HandlerDeleteItemBody {
     pub path: Vec<String>,
     pub hash: String,
+    #[cfg_attr(feature = "api", serde(default))]
+    pub recursive: bool,
 }
 
 #[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize, poem_openapi::Object))]
@@ -121,6 +156,90 @@ pub struct HandlerMoveItemBody {
     pub new_path: Vec<String>,
 }
 
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "api", serde(tag = "operation", rename_all = "lowercase"))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub enum HandlerBatchOperation {
+    Create(HandlerBatchCreate),
+    Mkdir(HandlerBatchMkdir),
+    Save(HandlerBatchSave),
+    Rename(HandlerBatchRename),
+    Move(HandlerBatchMove),
+    Delete(HandlerBatchDelete),
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerBatchCreate {
+    pub path: Vec<String>,
+    pub name: String,
+    pub content: String,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerBatchMkdir {
+    pub path: Vec<String>,
+    pub name: String,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerBatchSave {
+    pub path: Vec<String>,
+    pub hash: String,
+    pub content: String,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerBatchRename {
+    pub path: Vec<String>,
+    pub name: String,
+    pub hash: String,
+    pub new_name: String,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerBatchMove {
+    pub path: Vec<String>,
+    pub hash: String,
+    pub new_path: Vec<String>,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerBatchDelete {
+    pub path: Vec<String>,
+    pub hash: String,
+    #[cfg_attr(feature = "api", serde(default))]
+    pub recursive: bool,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerBatchBody {
+    pub operations: Vec<HandlerBatchOperation>,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerCopyItemBody {
+    pub path: Vec<String>,
+    pub hash: String,
+    pub new_path: Vec<String>,
+}
+
 
 #[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
 #[derive(Debug, PartialEq, Eq)]
@@ -134,4 +253,242 @@ pub struct HandlerAddFiles {
 pub struct HandlerAddFilesFile {
     pub name: String,
     pub blob_id: String,
-}
\ No newline at end of file
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerFileHistoryBody {
+    pub path: Vec<String>,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FileHistoryItem {
+    pub commit: String,
+    pub author: String,
+    pub timestamp: i64,
+    pub message: String,
+    pub id: String,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerFileHistoryResponse {
+    pub list: Vec<FileHistoryItem>,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerDiffBody {
+    pub path: Vec<String>,
+    pub prev_id: String,        //pusty string - porównanie z pustą zawartością
+    pub next_id: String,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerDiffCommitsBody {
+    pub path: Vec<String>,
+    pub prev_commit: String,    //pusty string - porównanie z pustą zawartością
+    pub next_commit: String,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DiffLineItem {
+    pub origin: String,         //"+", "-" lub " "
+    pub content: String,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerDiffResponse {
+    pub list: Vec<DiffLineItem>,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerRestoreItemBody {
+    pub path: Vec<String>,
+    pub prev_hash: String,          //hash bieżącej wersji elementu, pusty string - element nie istnieje
+    pub commit: String,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerSearchBody {
+    pub phrase: String,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SearchLineItem {
+    pub number: u32,
+    pub content: String,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SearchResultItem {
+    pub path: Vec<String>,
+    pub lines: Vec<SearchLineItem>,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerSearchResponse {
+    pub list: Vec<SearchResultItem>,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TaskFileItem {
+    pub path: Vec<String>,
+    pub open: u32,              //ilość niezakończonych zadań "[ ]" w pliku
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerTasksResponse {
+    pub list: Vec<TaskFileItem>,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TodoLineItem {
+    pub line: u32,              //numer linii liczony od zera
+    pub content: String,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TodoFileItem {
+    pub path: Vec<String>,
+    pub todo: bool,             //plik z rozszerzeniem .todo
+    pub timestamp: i64,         //czas commita z ostatnią zmianą pliku, 0 - brak w historii
+    pub tasks: Vec<TodoLineItem>,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerTodoDashboardResponse {
+    pub list: Vec<TodoFileItem>,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DueItem {
+    pub path: Vec<String>,
+    pub line: Option<u32>,      //numer linii zadania liczony od zera, None - termin w nazwie pliku .todo
+    pub content: String,
+    pub due: String,            //YYYY-MM-DD
+    pub overdue: bool,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerDueResponse {
+    pub today: String,
+    pub list: Vec<DueItem>,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HandlerSyncStatusResponse {
+    pub status: String,
+    pub last_attempt: i64,
+    pub last_success: i64,
+    pub ahead: u32,
+    pub behind: u32,
+    pub last_error: String,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HandlerReposResponse {
+    pub current: String,
+    pub list: Vec<String>,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerLoginBody {
+    pub login: String,
+    pub password: String,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HandlerWhoamiResponse {
+    pub auth: bool,
+    pub login: String,
+    pub name: String,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerDeleteManifestBody {
+    pub path: Vec<String>,
+    pub hash: String,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HandlerDeleteManifestResponse {
+    pub hash: String,
+    pub files: u32,
+    pub dirs: u32,
+    pub size: u64,
+    pub todo: u32,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HandlerTrashItem {
+    pub entry: String,
+    pub original: Vec<String>,
+    pub hash: String,
+    pub dir: bool,
+    pub timestamp: i64,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HandlerTrashResponse {
+    pub list: Vec<HandlerTrashItem>,
+}
+
+#[cfg_attr(feature = "api", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "client", derive(vertigo::AutoJsJson))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct HandlerTrashEntryBody {
+    pub entry: String,
+    pub hash: String,
+}
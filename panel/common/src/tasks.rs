@@ -0,0 +1,148 @@
+//Plik z listą zadań (*.todo) i linie zadań w treści notatek - wspólne dla serwera i klienta
+pub fn is_todo_name(name: &str) -> bool {
+    name.ends_with(".todo")
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ListMarker {
+    Bullet,
+    Number(u32),
+}
+
+//Linia z opcjonalnym znacznikiem listy ("-", "*", "+", "1.") i opcjonalnym checkboxem "[ ]" lub "[x]"
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ListLine<'a> {
+    pub indent: usize,              //liczba spacji i tabulatorów na początku linii
+    pub prefix: &'a str,            //wcięcie i znacznik listy
+    pub marker: Option<ListMarker>,
+    pub checked: Option<bool>,
+    pub text: &'a str,
+}
+
+fn parse_marker(rest: &str) -> Option<(ListMarker, usize)> {
+    if rest.starts_with("- ") || rest.starts_with("* ") || rest.starts_with("+ ") {
+        return Some((ListMarker::Bullet, 2));
+    }
+
+    let digits = rest.chars().take_while(|char| char.is_ascii_digit()).count();
+
+    if digits == 0 || !rest[digits..].starts_with(". ") {
+        return None;
+    }
+
+    let number = rest[..digits].parse::<u32>().ok()?;
+    Some((ListMarker::Number(number), digits + 2))
+}
+
+pub fn parse_list_line(line: &str) -> ListLine<'_> {
+    let indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+
+    let (marker, prefix_len) = match parse_marker(&line[indent..]) {
+        Some((marker, len)) => (Some(marker), indent + len),
+        None => (None, indent),
+    };
+
+    let (prefix, rest) = line.split_at(prefix_len);
+
+    let (checked, text) = if let Some(text) = rest.strip_prefix("[ ] ") {
+        (Some(false), text)
+    } else if let Some(text) = rest.strip_prefix("[x] ").or_else(|| rest.strip_prefix("[X] ")) {
+        (Some(true), text)
+    } else {
+        (None, rest)
+    };
+
+    ListLine {
+        indent,
+        prefix,
+        marker,
+        checked,
+        text,
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TaskLine<'a> {
+    pub prefix: &'a str,            //wcięcie i znacznik listy
+    pub checked: bool,
+    pub text: &'a str,
+}
+
+pub fn parse_task_line(line: &str) -> Option<TaskLine<'_>> {
+    let list_line = parse_list_line(line);
+
+    Some(TaskLine {
+        prefix: list_line.prefix,
+        checked: list_line.checked?,
+        text: list_line.text.trim_end(),
+    })
+}
+
+//Linia otwierająca lub zamykająca blok kodu
+pub fn is_code_fence(line: &str) -> bool {
+    line.trim().starts_with("```")
+}
+
+//Zadanie dla każdej linii treści. Linie w blokach kodu (```) nie są zadaniami,
+//tak samo liczy je serwer (/tasks, /todo, /due) i pokazuje klient
+pub fn parse_task_lines<'a>(lines: impl IntoIterator<Item = &'a str>) -> impl Iterator<Item = (&'a str, Option<TaskLine<'a>>)> {
+    let mut in_code = false;
+
+    lines.into_iter().map(move |line| {
+        if is_code_fence(line) {
+            in_code = !in_code;
+            return (line, None);
+        }
+
+        match in_code {
+            true => (line, None),
+            false => (line, parse_task_line(line)),
+        }
+    })
+}
+
+#[test]
+fn test_is_todo_name() {
+    assert!(is_todo_name("dsada.todo"));
+    assert!(is_todo_name("dsadasd.todo"));
+    assert!(!is_todo_name("dsadatodo"));
+    assert!(!is_todo_name("as"));
+    assert!(!is_todo_name("todo"));
+    assert!(!is_todo_name("o"));
+    assert!(!is_todo_name(""));
+}
+
+#[test]
+fn test_parse_task_line() {
+    assert_eq!(parse_task_line("[ ] kupić mleko"), Some(TaskLine { prefix: "", checked: false, text: "kupić mleko" }));
+    assert_eq!(parse_task_line("  - [x] zrobione\r"), Some(TaskLine { prefix: "  - ", checked: true, text: "zrobione" }));
+    assert_eq!(parse_task_line("* [X] zrobione"), Some(TaskLine { prefix: "* ", checked: true, text: "zrobione" }));
+    assert_eq!(parse_task_line("12. [ ] punkt"), Some(TaskLine { prefix: "12. ", checked: false, text: "punkt" }));
+    assert_eq!(parse_task_line("- zwykły punkt"), None);
+    assert_eq!(parse_task_line("[ ]"), None);
+    assert_eq!(parse_task_line("tekst [ ] w środku"), None);
+}
+
+#[test]
+fn test_parse_list_line() {
+    let line = parse_list_line("\t- [ ] zadanie");
+    assert_eq!((line.indent, line.prefix, line.marker, line.checked, line.text), (1, "\t- ", Some(ListMarker::Bullet), Some(false), "zadanie"));
+
+    let line = parse_list_line("3. punkt");
+    assert_eq!((line.indent, line.marker, line.checked, line.text), (0, Some(ListMarker::Number(3)), None, "punkt"));
+
+    let line = parse_list_line("  zwykły tekst");
+    assert_eq!((line.indent, line.marker, line.checked, line.text), (2, None, None, "zwykły tekst"));
+}
+
+#[test]
+fn test_parse_task_lines() {
+    let content = "- [ ] przed\n```\n- [ ] w kodzie\n```\n[x] po";
+
+    let tasks = parse_task_lines(content.split('\n'))
+        .enumerate()
+        .filter_map(|(index, (_, task))| Some((index, task?.checked)))
+        .collect::<Vec<_>>();
+
+    assert_eq!(tasks, vec![(0, false), (4, true)]);
+}
@@ -0,0 +1,47 @@
+fn pad(value: i64) -> String {
+    format!("{value:02}")
+}
+
+//Rok, miesiąc i dzień dla liczby dni od 1970-01-01
+//http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+//Data w formacie YYYY-MM-DD, w tym samym formacie co terminy "@2026-11-01" w zadaniach.
+//Strefa czasowa jest po stronie wywołującego - timestamp w sekundach z doliczonym przesunięciem
+pub fn date_from_seconds(timestamp: i64) -> String {
+    let (year, month, day) = civil_from_days(timestamp.div_euclid(86400));
+    format!("{year}-{}-{}", pad(month), pad(day))
+}
+
+//Format daty "YYYY-MM-DD HH:MM" (UTC), timestamp w sekundach
+pub fn format_timestamp(timestamp: i64) -> String {
+    let seconds = timestamp.rem_euclid(86400);
+    format!("{} {}:{}", date_from_seconds(timestamp), pad(seconds / 3600), pad(seconds % 3600 / 60))
+}
+
+#[test]
+fn test_date_from_seconds() {
+    assert_eq!(date_from_seconds(0), "1970-01-01");
+    assert_eq!(date_from_seconds(951782400), "2000-02-29");
+    assert_eq!(date_from_seconds(1793491200 + 86399), "2026-11-01");
+    assert_eq!(date_from_seconds(-1), "1969-12-31");
+}
+
+#[test]
+fn test_format_timestamp() {
+    assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+    assert_eq!(format_timestamp(951782400), "2000-02-29 00:00");
+    assert_eq!(format_timestamp(1700000000), "2023-11-14 22:13");
+}
@@ -0,0 +1,227 @@
+use std::sync::Arc;
+use serde::Deserialize;
+use crate::auth::{CurrentUser, UserEntry};
+use crate::utils::ErrorProcess;
+
+const ACL_EVERYONE: &str = "*";
+const ACL_ROLE_PREFIX: char = '@';
+
+//Reguła dla prefiksu ścieżki, np. "private/jan". Wpisy w read/write:
+//"*" - wszyscy, "@rola" - użytkownicy z daną rolą, pozostałe - login użytkownika
+#[derive(Deserialize, Debug, Clone)]
+pub struct AclRule {
+    pub path: String,
+    #[serde(default)]
+    pub read: Vec<String>,
+    #[serde(default)]
+    pub write: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Access {
+    None,
+    Read,
+    Write,
+}
+
+fn split_path(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn matches(principals: &[String], user: Option<&UserEntry>) -> bool {
+    principals.iter().any(|principal| {
+        if principal == ACL_EVERYONE {
+            return true;
+        }
+
+        let Some(user) = user else {
+            return false;
+        };
+
+        match principal.strip_prefix(ACL_ROLE_PREFIX) {
+            Some(role) => user.roles.iter().any(|item| item == role),
+            None => *principal == user.login,
+        }
+    })
+}
+
+//Decyduje reguła z najdłuższym pasującym prefiksem, brak reguł oznacza pełny dostęp
+#[derive(Clone)]
+pub struct Acl {
+    rules: Arc<Vec<(Vec<String>, AclRule)>>,
+}
+
+impl Acl {
+    pub fn new(rules: Vec<AclRule>) -> Acl {
+        let rules = rules
+            .into_iter()
+            .map(|rule| (split_path(&rule.path), rule))
+            .collect();
+
+        Acl {
+            rules: Arc::new(rules),
+        }
+    }
+
+    pub fn load(acl_file: Option<String>) -> Result<Acl, String> {
+        let Some(acl_file) = acl_file else {
+            return Ok(Acl::new(Vec::new()));
+        };
+
+        let data = std::fs::read_to_string(&acl_file)
+            .map_err(|err| format!("Problem with read acl file {acl_file} {err}"))?;
+
+        let rules = serde_json::from_str::<Vec<AclRule>>(&data)
+            .map_err(|err| format!("Problem with parse acl file {acl_file} {err}"))?;
+
+        Ok(Acl::new(rules))
+    }
+
+    pub fn access(&self, user: Option<&UserEntry>, path: &[String]) -> Access {
+        let rule = self.rules
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix))
+            .max_by_key(|(prefix, _)| prefix.len());
+
+        let Some((_, rule)) = rule else {
+            return Access::Write;
+        };
+
+        if matches(&rule.write, user) {
+            Access::Write
+        } else if matches(&rule.read, user) {
+            Access::Read
+        } else {
+            Access::None
+        }
+    }
+
+    pub fn can_read(&self, user: &CurrentUser, path: &[String]) -> bool {
+        self.access(user.user.as_ref(), path) >= Access::Read
+    }
+
+    pub fn check_read(&self, user: &CurrentUser, path: &[String]) -> Result<(), ErrorProcess> {
+        if self.can_read(user, path) {
+            return Ok(());
+        }
+
+        ErrorProcess::forbidden_result(format!("No read access to {}", path.join("/")))
+    }
+
+    pub fn check_write(&self, user: &CurrentUser, path: &[String]) -> Result<(), ErrorProcess> {
+        if self.access(user.user.as_ref(), path) == Access::Write {
+            return Ok(());
+        }
+
+        ErrorProcess::forbidden_result(format!("No write access to {}", path.join("/")))
+    }
+
+    //Kopiowany katalog może zawierać ścieżki z bardziej restrykcyjnymi regułami
+    pub fn check_read_subtree(&self, user: &CurrentUser, path: &[String]) -> Result<(), ErrorProcess> {
+        self.check_read(user, path)?;
+
+        for (prefix, _) in self.rules.iter() {
+            if prefix.len() > path.len() && prefix.starts_with(path) {
+                self.check_read(user, prefix)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    //Dla operacji na katalogu (usunięcie, przeniesienie) sprawdzamy też reguły zagnieżdżone w tej ścieżce
+    pub fn check_write_subtree(&self, user: &CurrentUser, path: &[String]) -> Result<(), ErrorProcess> {
+        self.check_write(user, path)?;
+
+        for (prefix, _) in self.rules.iter() {
+            if prefix.len() > path.len() && prefix.starts_with(path) {
+                self.check_write(user, prefix)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn user(login: &str, roles: &[&str]) -> UserEntry {
+    UserEntry {
+        login: login.into(),
+        name: login.into(),
+        email: format!("{login}@example.com"),
+        password: String::new(),
+        roles: roles.iter().map(|role| role.to_string()).collect(),
+    }
+}
+
+#[cfg(test)]
+fn rule(path: &str, read: &[&str], write: &[&str]) -> AclRule {
+    AclRule {
+        path: path.into(),
+        read: read.iter().map(|item| item.to_string()).collect(),
+        write: write.iter().map(|item| item.to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_acl_without_rules() {
+    let acl = Acl::new(Vec::new());
+    assert_eq!(acl.access(None, &split_path("any/path")), Access::Write);
+}
+
+#[test]
+fn test_acl_longest_prefix() {
+    let acl = Acl::new(vec![
+        rule("", &["*"], &["*"]),
+        rule("private", &[], &[]),
+        rule("private/jan", &["jan"], &["jan"]),
+        rule("team", &["*"], &["@team"]),
+    ]);
+
+    let jan = user("jan", &[]);
+    let ola = user("ola", &["team"]);
+
+    assert_eq!(acl.access(Some(&jan), &split_path("notes/a.txt")), Access::Write);
+    assert_eq!(acl.access(Some(&jan), &split_path("private")), Access::None);
+    assert_eq!(acl.access(Some(&jan), &split_path("private/jan/a.txt")), Access::Write);
+    assert_eq!(acl.access(Some(&ola), &split_path("private/jan/a.txt")), Access::None);
+    assert_eq!(acl.access(Some(&jan), &split_path("team/a.txt")), Access::Read);
+    assert_eq!(acl.access(Some(&ola), &split_path("team/a.txt")), Access::Write);
+    assert_eq!(acl.access(None, &split_path("team/a.txt")), Access::Read);
+
+    //prefiks dopasowywany po całych segmentach ścieżki
+    assert_eq!(acl.access(Some(&ola), &split_path("private_other")), Access::Write);
+}
+
+#[test]
+fn test_acl_subtree() {
+    let acl = Acl::new(vec![
+        rule("", &["*"], &["*"]),
+        rule("team/jan", &["*"], &["jan"]),
+    ]);
+
+    let jan = CurrentUser { user: Some(user("jan", &[])) };
+    let ola = CurrentUser { user: Some(user("ola", &[])) };
+
+    assert!(acl.check_write_subtree(&jan, &split_path("team")).is_ok());
+    assert!(acl.check_write_subtree(&ola, &split_path("team")).is_err());
+    assert!(acl.check_write_subtree(&ola, &split_path("other")).is_ok());
+}
+
+#[test]
+fn test_acl_read_subtree() {
+    let acl = Acl::new(vec![
+        rule("", &["*"], &["*"]),
+        rule("team/jan", &["jan"], &["jan"]),
+    ]);
+
+    let jan = CurrentUser { user: Some(user("jan", &[])) };
+    let ola = CurrentUser { user: Some(user("ola", &[])) };
+
+    assert!(acl.check_read_subtree(&jan, &split_path("team")).is_ok());
+    assert!(acl.check_read_subtree(&ola, &split_path("team")).is_err());
+    assert!(acl.check_read_subtree(&ola, &split_path("team/other")).is_ok());
+}
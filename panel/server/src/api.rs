@@ -8,22 +8,46 @@ use super::models::{
     HandlerFetchNodeBody,
     HandlerFetchNodeResponse,
     HandlerRenameItemBody,
-    HandlerSaveContentBody, HandlerMoveItemBody, HandlerAddFiles,
+    HandlerSaveContentBody, HandlerSaveContentResponse, HandlerMoveItemBody, HandlerCopyItemBody, HandlerBatchBody, HandlerBatchOperation, HandlerAddFiles,
+    HandlerFileHistoryBody, HandlerFileHistoryResponse,
+    HandlerDiffBody, HandlerDiffCommitsBody, HandlerDiffResponse,
+    HandlerRestoreItemBody,
+    HandlerSearchBody, HandlerSearchResponse,
+    HandlerTasksResponse,
+    HandlerTodoDashboardResponse,
+    HandlerDueResponse,
+    HandlerSyncStatusResponse,
+    HandlerReposResponse,
+    HandlerDeleteManifestBody, HandlerDeleteManifestResponse,
+    HandlerTrashResponse, HandlerTrashEntryBody,
 };
 use poem_openapi::{
     OpenApi,
-    param::Path,
+    param::{Path, Query},
     payload::{Html, Binary}
 };
+use poem::web::Data;
 use super::static_response::StaticResponse;
+use super::content_type;
 use super::utils::{
     ApiResponseHttp,
+    ErrorProcess,
+    now_seconds,
+    local_offset_seconds,
 };
+use common::date_from_seconds;
 
+use std::sync::Arc;
 use serde::{Deserialize};
 use poem_openapi::payload::Json;
-use super::git::{self, Git, GitBlob};
+use super::git::{self, Git, GitBlob, PathFilter};
+use super::sync::SyncHandle;
+use super::auth::CurrentUser;
+use super::acl::Acl;
+use super::thumbnail::{self, Thumbnails};
 
+const DUE_DAYS_DEFAULT: u32 = 7;
+const DUE_DAYS_MAX: u32 = 365;
 
 #[derive(Deserialize)]
 struct IndexJson {
@@ -32,16 +56,106 @@ struct IndexJson {
 }
 
 
+fn child_path(path: &[String], name: &str) -> Vec<String> {
+    let mut path = path.to_vec();
+    path.push(name.to_string());
+    path
+}
+
+//Ścieżka przekazana w adresie url jako "a/b/c"
+fn split_query_path(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+//Uprawnienia sprawdzane są dla ścieżek. Endpointy odczytu (również /image i /download)
+//przyjmują ścieżkę razem z hashem, a serwer sprawdza, czy hash odpowiada elementowi
+//spod tej ścieżki (Git::get_from_path)
 #[derive(Clone)]
 pub struct Api {
     git: Git,
+    sync: SyncHandle,
+    acl: Acl,
+    thumbnails: Thumbnails,
+    repo: String,
+    repos: Arc<Vec<String>>,
 }
 
 #[OpenApi]
 impl Api {
-    pub fn new(git: Git) -> Api {
+    pub fn new(git: Git, sync: SyncHandle, acl: Acl, thumbnails: Thumbnails, repo: String, repos: Arc<Vec<String>>) -> Api {
         Api {
-            git
+            git,
+            sync,
+            acl,
+            thumbnails,
+            repo,
+            repos,
+        }
+    }
+
+    fn is_trash(&self, path: &[String]) -> bool {
+        match self.git.trash_dir() {
+            Some(trash_dir) => path.first() == Some(trash_dir),
+            None => false,
+        }
+    }
+
+    //Zawartość kosza jest dostępna tylko przez /trash, /trash_restore i /trash_purge. Ścieżki w koszu
+    //podlegają regułom domyślnym, a nie regułom pierwotnej ścieżki, więc żaden inny endpoint ich nie obsługuje
+    fn check_not_trash(&self, path: &[String]) -> Result<(), ErrorProcess> {
+        if self.is_trash(path) {
+            return ErrorProcess::forbidden_result(format!("Trash entries are available only through /trash {}", path.join("/")));
+        }
+
+        Ok(())
+    }
+
+    fn check_read(&self, user: &CurrentUser, path: &[String]) -> Result<(), ErrorProcess> {
+        self.check_not_trash(path)?;
+        self.acl.check_read(user, path)
+    }
+
+    fn check_read_subtree(&self, user: &CurrentUser, path: &[String]) -> Result<(), ErrorProcess> {
+        self.check_not_trash(path)?;
+        self.acl.check_read_subtree(user, path)
+    }
+
+    fn check_write(&self, user: &CurrentUser, path: &[String]) -> Result<(), ErrorProcess> {
+        self.check_not_trash(path)?;
+        self.acl.check_write(user, path)
+    }
+
+    fn check_write_subtree(&self, user: &CurrentUser, path: &[String]) -> Result<(), ErrorProcess> {
+        self.check_not_trash(path)?;
+        self.acl.check_write_subtree(user, path)
+    }
+
+    //Uprawnienia sprawdzane są dla każdej operacji przed wykonaniem całej paczki
+    fn check_batch_operation(&self, user: &CurrentUser, operation: &HandlerBatchOperation) -> Result<(), ErrorProcess> {
+        match operation {
+            HandlerBatchOperation::Create(operation) => {
+                self.check_write(user, &child_path(&operation.path, &operation.name))
+            },
+            HandlerBatchOperation::Mkdir(operation) => {
+                self.check_write(user, &child_path(&operation.path, &operation.name))
+            },
+            HandlerBatchOperation::Save(operation) => {
+                self.check_write(user, &operation.path)
+            },
+            HandlerBatchOperation::Rename(operation) => {
+                self.check_write_subtree(user, &child_path(&operation.path, &operation.name))?;
+                self.check_write_subtree(user, &child_path(&operation.path, &operation.new_name))
+            },
+            HandlerBatchOperation::Move(operation) => {
+                self.check_write_subtree(user, &operation.path)?;
+                self.check_write_subtree(user, &operation.new_path)
+            },
+            HandlerBatchOperation::Delete(operation) => {
+                self.check_write_subtree(user, &operation.path)
+            },
         }
     }
 
@@ -75,10 +189,12 @@ impl Api {
     }
 
     #[oai(method = "post", path = "/fetch_tree_item")]
-    async fn handler_fetch_dir(&self, json: Json<HandlerFetchDirBody>) -> ApiResponseHttp<HandlerFetchDirResponse> {
+    async fn handler_fetch_dir(&self, json: Json<HandlerFetchDirBody>, user: Data<&CurrentUser>) -> ApiResponseHttp<HandlerFetchDirResponse> {
         let Json(body_request) = json;
+        let Data(user) = user;
+        self.check_read(user, &body_request.path)?;
 
-        let root = self.git.get_from_id(&body_request.id).await;
+        let root = self.git.get_from_path(&body_request.path, &body_request.id).await;
 
         let root = match root {
             Ok(root) => root,
@@ -90,7 +206,11 @@ impl Api {
         if let Some(git::GitBlob::Tree { list }) = root {
             let mut response: HandlerFetchDirResponse = HandlerFetchDirResponse::new();
             for item in list {
-                response.add(item);
+                let path = child_path(&body_request.path, &item.name);
+
+                if self.acl.can_read(user, &path) && !self.is_trash(&path) {
+                    response.add(item);
+                }
             }
 
             return ApiResponseHttp::ok(response);
@@ -100,12 +220,11 @@ impl Api {
     }
 
     #[oai(method = "post", path = "/fetch_node")]
-    async fn handler_fetch_node(&self, json: Json<HandlerFetchNodeBody>) -> ApiResponseHttp<HandlerFetchNodeResponse> {
+    async fn handler_fetch_node(&self, json: Json<HandlerFetchNodeBody>, user: Data<&CurrentUser>) -> ApiResponseHttp<HandlerFetchNodeResponse> {
         let Json(body_request) = json;
+        self.check_read(&user, &body_request.path)?;
 
-        let hash_id = body_request.hash;
-
-        let data = self.git.get_from_id(&hash_id).await;
+        let data = self.git.get_from_path(&body_request.path, &body_request.hash).await;
 
         let data = match data {
             Ok(data) => data,
@@ -131,27 +250,31 @@ impl Api {
             };
         }
 
-        ApiResponseHttp::not_found(format!("missing content {hash_id}"))
+        ApiResponseHttp::not_found(format!("missing content {}", body_request.hash))
     }
 
     #[oai(method = "post", path = "/save_content")]
-    async fn handler_save_content(&self, json: Json<HandlerSaveContentBody>) -> ApiResponseHttp<RootResponse> {
+    async fn handler_save_content(&self, json: Json<HandlerSaveContentBody>, user: Data<&CurrentUser>) -> ApiResponseHttp<HandlerSaveContentResponse> {
         let Json(body_request) = json;
+        self.check_write(&user, &body_request.path)?;
+
         let result = self.git.save_content(
+            user.author(),
             body_request.path,
             body_request.prev_hash,
             body_request.new_content
         ).await?;
 
-        ApiResponseHttp::ok(RootResponse {
-            root: result
-        })
+        ApiResponseHttp::ok(result)
     }
 
     #[oai(method = "post", path = "/create_file")]
-    async fn handler_create_file(&self, json: Json<HandlerCreateFileBody>) -> ApiResponseHttp<RootResponse> {
+    async fn handler_create_file(&self, json: Json<HandlerCreateFileBody>, user: Data<&CurrentUser>) -> ApiResponseHttp<RootResponse> {
         let Json(body_request) = json;
+        self.check_write(&user, &child_path(&body_request.path, &body_request.new_name))?;
+
         let result = self.git.create_file(
+            user.author(),
             body_request.path,
             body_request.new_name,
             body_request.new_content
@@ -163,9 +286,12 @@ impl Api {
     } 
 
     #[oai(method = "post", path = "/create_dir")]
-    async fn handler_create_dir(&self, json: Json<HandlerCreateDirBody>) -> ApiResponseHttp<RootResponse> {
+    async fn handler_create_dir(&self, json: Json<HandlerCreateDirBody>, user: Data<&CurrentUser>) -> ApiResponseHttp<RootResponse> {
         let Json(body_request) = json;
+        self.check_write(&user, &child_path(&body_request.path, &body_request.dir))?;
+
         let result = self.git.create_dir(
+            user.author(),
             body_request.path,
             body_request.dir
         ).await?;
@@ -176,9 +302,13 @@ impl Api {
     }
 
     #[oai(method = "post", path = "/rename_item")]
-    async fn handler_rename_item(&self, json: Json<HandlerRenameItemBody>) -> ApiResponseHttp<RootResponse> {
+    async fn handler_rename_item(&self, json: Json<HandlerRenameItemBody>, user: Data<&CurrentUser>) -> ApiResponseHttp<RootResponse> {
         let Json(body_request) = json;
+        self.check_write_subtree(&user, &child_path(&body_request.path, &body_request.prev_name))?;
+        self.check_write_subtree(&user, &child_path(&body_request.path, &body_request.new_name))?;
+
         let result = self.git.rename_item(
+            user.author(),
             body_request.path,
             body_request.prev_name,
             body_request.prev_hash,
@@ -191,11 +321,43 @@ impl Api {
     }
 
     #[oai(method = "post", path = "/delete_item")]
-    async fn handler_delete_item(&self, json: Json<HandlerDeleteItemBody>) -> ApiResponseHttp<RootResponse> {
+    async fn handler_delete_item(&self, json: Json<HandlerDeleteItemBody>, user: Data<&CurrentUser>) -> ApiResponseHttp<RootResponse> {
         let Json(body_request) = json;
+        self.check_write_subtree(&user, &body_request.path)?;
+
         let result = self.git.delete_item(
+            user.author(),
             body_request.path,
             body_request.hash,
+            body_request.recursive,
+        ).await?;
+
+        ApiResponseHttp::ok(RootResponse {
+            root: result
+        })
+    }
+
+    #[oai(method = "get", path = "/trash")]
+    async fn handler_trash(&self, user: Data<&CurrentUser>) -> ApiResponseHttp<HandlerTrashResponse> {
+        let mut list = self.git.trash_list().await?;
+        list.retain(|item| self.acl.can_read(&user, &item.original));
+
+        ApiResponseHttp::ok(HandlerTrashResponse {
+            list
+        })
+    }
+
+    #[oai(method = "post", path = "/trash_restore")]
+    async fn handler_trash_restore(&self, json: Json<HandlerTrashEntryBody>, user: Data<&CurrentUser>) -> ApiResponseHttp<RootResponse> {
+        let Json(body_request) = json;
+        //uprawnienia z pierwotnej ścieżki elementu
+        let item = self.git.trash_entry(&body_request.entry).await?;
+        self.acl.check_write_subtree(&user, &item.original)?;
+
+        let result = self.git.trash_restore(
+            user.author(),
+            body_request.entry,
+            body_request.hash,
         ).await?;
 
         ApiResponseHttp::ok(RootResponse {
@@ -203,11 +365,46 @@ impl Api {
         })
     }
 
+    #[oai(method = "post", path = "/trash_purge")]
+    async fn handler_trash_purge(&self, json: Json<HandlerTrashEntryBody>, user: Data<&CurrentUser>) -> ApiResponseHttp<RootResponse> {
+        let Json(body_request) = json;
+        //uprawnienia z pierwotnej ścieżki elementu
+        let item = self.git.trash_entry(&body_request.entry).await?;
+        self.acl.check_write_subtree(&user, &item.original)?;
+
+        let result = self.git.trash_purge(
+            user.author(),
+            body_request.entry,
+            body_request.hash,
+        ).await?;
+
+        ApiResponseHttp::ok(RootResponse {
+            root: result
+        })
+    }
+
+    #[oai(method = "post", path = "/delete_manifest")]
+    async fn handler_delete_manifest(&self, json: Json<HandlerDeleteManifestBody>, user: Data<&CurrentUser>) -> ApiResponseHttp<HandlerDeleteManifestResponse> {
+        let Json(body_request) = json;
+        //liczniki i rozmiar opisują też zagnieżdżone elementy
+        self.check_read_subtree(&user, &body_request.path)?;
+
+        let manifest = self.git.delete_manifest(
+            body_request.path,
+            body_request.hash,
+        ).await?;
+
+        ApiResponseHttp::ok(manifest)
+    }
+
     #[oai(method = "post", path = "/move_item")]
-    async fn handler_move_item(&self, json: Json<HandlerMoveItemBody>) -> ApiResponseHttp<RootResponse> {
+    async fn handler_move_item(&self, json: Json<HandlerMoveItemBody>, user: Data<&CurrentUser>) -> ApiResponseHttp<RootResponse> {
         let Json(body_request) = json;
-        
+        self.check_write_subtree(&user, &body_request.path)?;
+        self.check_write_subtree(&user, &body_request.new_path)?;
+
         let result = self.git.move_item(
+            user.author(),
             body_request.path,
             body_request.hash,
             body_request.new_path,
@@ -218,18 +415,201 @@ impl Api {
         })
     }
 
+    #[oai(method = "post", path = "/copy_item")]
+    async fn handler_copy_item(&self, json: Json<HandlerCopyItemBody>, user: Data<&CurrentUser>) -> ApiResponseHttp<RootResponse> {
+        let Json(body_request) = json;
+        self.check_read_subtree(&user, &body_request.path)?;
+        self.check_write_subtree(&user, &body_request.new_path)?;
+
+        let result = self.git.copy_item(
+            user.author(),
+            body_request.path,
+            body_request.hash,
+            body_request.new_path,
+        ).await?;
+
+        ApiResponseHttp::ok(RootResponse {
+            root: result
+        })
+    }
+
+    #[oai(method = "post", path = "/batch")]
+    async fn handler_batch(&self, json: Json<HandlerBatchBody>, user: Data<&CurrentUser>) -> ApiResponseHttp<RootResponse> {
+        let Json(body_request) = json;
+
+        for operation in body_request.operations.iter() {
+            self.check_batch_operation(&user, operation)?;
+        }
+
+        let result = self.git.batch(
+            user.author(),
+            body_request.operations,
+        ).await?;
+
+        ApiResponseHttp::ok(RootResponse {
+            root: result
+        })
+    }
+
+    #[oai(method = "post", path = "/restore_item")]
+    async fn handler_restore_item(&self, json: Json<HandlerRestoreItemBody>, user: Data<&CurrentUser>) -> ApiResponseHttp<RootResponse> {
+        let Json(body_request) = json;
+        self.check_write_subtree(&user, &body_request.path)?;
+
+        let result = self.git.restore_item(
+            user.author(),
+            body_request.path,
+            body_request.prev_hash,
+            body_request.commit,
+        ).await?;
+
+        ApiResponseHttp::ok(RootResponse {
+            root: result
+        })
+    }
+
+    #[oai(method = "post", path = "/file_history")]
+    async fn handler_file_history(&self, json: Json<HandlerFileHistoryBody>, user: Data<&CurrentUser>) -> ApiResponseHttp<HandlerFileHistoryResponse> {
+        let Json(body_request) = json;
+        self.check_read(&user, &body_request.path)?;
+
+        //w katalogu z ukrytymi podkatalogami pomijane są commity, które je zmieniają
+        let visible = |path: &[String]| self.acl.can_read(&user, path);
+
+        let visible: Option<PathFilter> = match self.check_read_subtree(&user, &body_request.path) {
+            Ok(()) => None,
+            Err(_) => Some(&visible),
+        };
+
+        let list = self.git.file_history(body_request.path, visible).await?;
+
+        ApiResponseHttp::ok(HandlerFileHistoryResponse {
+            list
+        })
+    }
+
+    #[oai(method = "post", path = "/search")]
+    async fn handler_search(&self, json: Json<HandlerSearchBody>, user: Data<&CurrentUser>) -> ApiResponseHttp<HandlerSearchResponse> {
+        let Json(body_request) = json;
+        let mut list = self.git.search(body_request.phrase).await?;
+        list.retain(|item| self.acl.can_read(&user, &item.path) && !self.is_trash(&item.path));
+
+        ApiResponseHttp::ok(HandlerSearchResponse {
+            list
+        })
+    }
+
+    //Pliki z niezakończonymi zadaniami "[ ]" - licznik todo po stronie klienta
+    #[oai(method = "get", path = "/tasks")]
+    async fn handler_tasks(&self, user: Data<&CurrentUser>) -> ApiResponseHttp<HandlerTasksResponse> {
+        let mut list = self.git.tasks().await?;
+        list.retain(|item| self.acl.can_read(&user, &item.path) && !self.is_trash(&item.path));
+
+        ApiResponseHttp::ok(HandlerTasksResponse {
+            list
+        })
+    }
+
+    //Wszystkie pliki .todo i otwarte zadania w repozytorium - widok zbiorczy zadań
+    #[oai(method = "get", path = "/todo_dashboard")]
+    async fn handler_todo_dashboard(&self, user: Data<&CurrentUser>) -> ApiResponseHttp<HandlerTodoDashboardResponse> {
+        let mut list = self.git.todo_dashboard().await?;
+        list.retain(|item| self.acl.can_read(&user, &item.path) && !self.is_trash(&item.path));
+
+        ApiResponseHttp::ok(HandlerTodoDashboardResponse {
+            list
+        })
+    }
+
+    //Zadania z terminem "@YYYY-MM-DD" - przeterminowane oraz przypadające w ciągu najbliższych dni (domyślnie 7)
+    #[oai(method = "get", path = "/due")]
+    async fn handler_due(&self, user: Data<&CurrentUser>, days: Query<Option<u32>>) -> ApiResponseHttp<HandlerDueResponse> {
+        let days = days.0.unwrap_or(DUE_DAYS_DEFAULT).min(DUE_DAYS_MAX);
+
+        //"dzisiaj" według lokalnej strefy czasowej serwera, a nie UTC
+        let now = now_seconds() + local_offset_seconds();
+        let today = date_from_seconds(now);
+        let limit = date_from_seconds(now + i64::from(days) * 86400);
+
+        let mut list = self.git.due(today.clone(), limit).await?;
+        list.retain(|item| self.acl.can_read(&user, &item.path) && !self.is_trash(&item.path));
+
+        ApiResponseHttp::ok(HandlerDueResponse {
+            today,
+            list
+        })
+    }
+
+    #[oai(method = "post", path = "/diff")]
+    async fn handler_diff(&self, json: Json<HandlerDiffBody>, user: Data<&CurrentUser>) -> ApiResponseHttp<HandlerDiffResponse> {
+        let Json(body_request) = json;
+        self.check_read(&user, &body_request.path)?;
+        let list = self.git.diff(body_request.path, body_request.prev_id, body_request.next_id).await?;
+
+        ApiResponseHttp::ok(HandlerDiffResponse {
+            list
+        })
+    }
+
+    //Zmiany pliku pomiędzy dwoma commitami
+    #[oai(method = "post", path = "/diff_commits")]
+    async fn handler_diff_commits(&self, json: Json<HandlerDiffCommitsBody>, user: Data<&CurrentUser>) -> ApiResponseHttp<HandlerDiffResponse> {
+        let Json(body_request) = json;
+        self.check_read(&user, &body_request.path)?;
+        let list = self.git.diff_commits(body_request.path, body_request.prev_commit, body_request.next_commit).await?;
+
+        ApiResponseHttp::ok(HandlerDiffResponse {
+            list
+        })
+    }
+
+    #[oai(method = "get", path = "/repos")]
+    async fn handler_repos(&self) -> ApiResponseHttp<HandlerReposResponse> {
+        ApiResponseHttp::ok(HandlerReposResponse {
+            current: self.repo.clone(),
+            list: self.repos.as_ref().clone(),
+        })
+    }
+
+    #[oai(method = "get", path = "/sync_status")]
+    async fn handler_sync_status(&self) -> ApiResponseHttp<HandlerSyncStatusResponse> {
+        let state = self.sync.get();
+
+        ApiResponseHttp::ok(HandlerSyncStatusResponse {
+            status: state.status.label().into(),
+            last_attempt: state.last_attempt,
+            last_success: state.last_success,
+            ahead: state.ahead as u32,
+            behind: state.behind as u32,
+            last_error: state.last_error.unwrap_or_default(),
+        })
+    }
+
+    #[oai(method = "post", path = "/sync_force")]
+    async fn handler_sync_force(&self) -> ApiResponseHttp<String> {
+        self.sync.force();
+        ApiResponseHttp::ok("ok".into())
+    }
+
+    //path - docelowa ścieżka pliku w postaci "a/b/c.png", tak jak w /add_files
     #[oai(method = "post", path = "/create_blob")]
-    async fn handler_create_blob(&self, data: Binary<Vec<u8>>) -> ApiResponseHttp<String> {
+    async fn handler_create_blob(&self, data: Binary<Vec<u8>>, path: Query<String>, user: Data<&CurrentUser>) -> ApiResponseHttp<String> {
         let Binary(data) = data;
+        self.check_write(&user, &split_query_path(&path.0))?;
 
         let result = self.git.create_blob(data).await?;
         ApiResponseHttp::ok(result)
     }
 
     #[oai(method = "post", path = "/add_files")]
-    async fn handler_add_files(&self, data: Json<HandlerAddFiles>) -> ApiResponseHttp<String> {
+    async fn handler_add_files(&self, data: Json<HandlerAddFiles>, user: Data<&CurrentUser>) -> ApiResponseHttp<String> {
         let Json(data) = data;
-        let root = self.git.add_files(data.path, data.files).await?;
+
+        for file in data.files.iter() {
+            self.check_write(&user, &child_path(&data.path, &file.name))?;
+        }
+
+        let root = self.git.add_files(user.author(), data.path, data.files).await?;
 
         ApiResponseHttp::ok(root)
     }
@@ -238,22 +618,45 @@ impl Api {
     //https://docs.rs/poem-openapi/1.3.29/poem_openapi/response/enum.StaticFileResponse.html
     //https://github.com/poem-web/poem/blob/master/poem-openapi/src/docs/response_content.md
 
+    //w - szerokość miniatury, bez tego parametru zwracany jest oryginalny plik
+    //path - ścieżka pliku w postaci "a/b/c.png", hash musi odpowiadać plikowi spod tej ścieżki
     #[oai(method = "get", path = "/image/:id/:meta")]
-    async fn handler_get_image(&self, id: Path<String>, meta: Path<String>) -> StaticResponse {
+    async fn handler_get_image(
+        &self,
+        id: Path<String>,
+        meta: Path<String>,
+        w: Query<Option<u32>>,
+        path: Query<String>,
+        user: Data<&CurrentUser>,
+    ) -> StaticResponse {
         let Path(id) = id;
         let Path(meta) = meta;
+        let Query(width) = w;
+        let path = split_query_path(&path.0);
+
+        let check = match self.check_read(&user, &path) {
+            Ok(()) => self.git.check_path(&path, &id).await,
+            Err(err) => Err(err),
+        };
+
+        if let Err(err) = check {
+            return StaticResponse::from_error_process(err);
+        }
+
+        let width = width.map(thumbnail::normalize_width);
+
+        if let Some(width) = width {
+            if let Some(thumbnail) = self.thumbnails.cached(&id, width).await {
+                return StaticResponse::binary(format!("Content-Type: {}", thumbnail.content_type), thumbnail.data);
+            }
+        }
 
         let data = self.git.get_from_id(&id).await;
 
         let data = match data {
             Ok(content) => content,
             Err(err) => {
-                let message = match err.to_string() {
-                    (false, message) => format!("User error: {message}"),
-                    (true, message) => format!("Internal error: {message}"),
-                };
-
-                return StaticResponse::internal_server(message);
+                return StaticResponse::from_error_process(err);
             }
         };
 
@@ -279,9 +682,54 @@ impl Api {
             _ => None
         };
 
-        match header {
-            Some(header) => StaticResponse::binary(header, content),
-            None => StaticResponse::not_found()
+        let Some(header) = header else {
+            return StaticResponse::not_found();
+        };
+
+        let Some(width) = width else {
+            return StaticResponse::binary(header, content);
+        };
+
+        //Obrazek, którego nie da się zdekodować, zwracamy w oryginale
+        match self.thumbnails.create(&id, width, content.clone()).await {
+            Ok(thumbnail) => StaticResponse::binary(format!("Content-Type: {}", thumbnail.content_type), thumbnail.data),
+            Err(err) => {
+                log::warn!("Problem with thumbnail id={id} width={width} {err:?}");
+                StaticResponse::binary(header, content)
+            }
         }
     }
+
+    //Dowolny plik (załącznik), name służy do rozpoznania typu i jako nazwa przy zapisie
+    //path - ścieżka pliku w postaci "a/b/c.pdf", hash musi odpowiadać plikowi spod tej ścieżki
+    #[oai(method = "get", path = "/download/:id/:name")]
+    async fn handler_download(
+        &self,
+        id: Path<String>,
+        name: Path<String>,
+        path: Query<String>,
+        user: Data<&CurrentUser>,
+    ) -> StaticResponse {
+        let Path(id) = id;
+        let Path(name) = name;
+        let path = split_query_path(&path.0);
+
+        let data = match self.check_read(&user, &path) {
+            Ok(()) => self.git.get_from_path(&path, &id).await,
+            Err(err) => Err(err),
+        };
+
+        let data = match data {
+            Ok(data) => data,
+            Err(err) => {
+                return StaticResponse::from_error_process(err);
+            }
+        };
+
+        let Some(GitBlob::Blob { content }) = data else {
+            return StaticResponse::not_found();
+        };
+
+        StaticResponse::download(content_type::download(&name, &content), content)
+    }
 }
@@ -0,0 +1,373 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use argon2::{
+    Argon2,
+    PasswordHash,
+    PasswordHasher,
+    PasswordVerifier,
+    password_hash::SaltString,
+};
+use poem::{
+    handler,
+    async_trait,
+    Endpoint,
+    IntoResponse,
+    Middleware,
+    Request,
+    Response,
+    http::{StatusCode, Method, header},
+    web::{Data, Json},
+};
+use rand::{RngCore, rngs::OsRng};
+use serde::Deserialize;
+use crate::git::CommitAuthor;
+use crate::models::{HandlerLoginBody, HandlerWhoamiResponse};
+use crate::utils::now_seconds;
+
+const SESSION_COOKIE: &str = "notatki_session";
+
+//Wpis w pliku użytkowników (tablica json), password - hash argon2 w formacie PHC
+#[derive(Deserialize, Debug, Clone)]
+pub struct UserEntry {
+    pub login: String,
+    pub name: String,
+    pub email: String,
+    #[serde(default)]
+    pub password: String,       //pusty - użytkownik tylko przez nagłówek proxy
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CurrentUser {
+    pub user: Option<UserEntry>,
+}
+
+impl CurrentUser {
+    pub fn author(&self) -> Option<CommitAuthor> {
+        self.user.as_ref().map(|user| CommitAuthor {
+            name: user.name.clone(),
+            email: user.email.clone(),
+        })
+    }
+}
+
+pub fn hash_password(password: &str) -> Result<String, String> {
+    let salt = SaltString::generate(&mut OsRng);
+
+    match Argon2::default().hash_password(password.as_bytes(), &salt) {
+        Ok(hash) => Ok(hash.to_string()),
+        Err(err) => Err(format!("Problem with hash password {err}")),
+    }
+}
+
+fn verify_password(password: &str, hash: &str) -> bool {
+    let Ok(hash) = PasswordHash::new(hash) else {
+        return false;
+    };
+
+    Argon2::default().verify_password(password.as_bytes(), &hash).is_ok()
+}
+
+fn new_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn session_token(req: &Request) -> Option<String> {
+    let cookies = req.headers().get(header::COOKIE)?.to_str().ok()?;
+
+    for cookie in cookies.split(';') {
+        if let Some((name, value)) = cookie.trim().split_once('=') {
+            if name == SESSION_COOKIE {
+                return Some(value.to_string());
+            }
+        }
+    }
+
+    None
+}
+
+struct Session {
+    login: String,
+    expires: i64,               //timestamp w sekundach
+}
+
+//Sesje trzymane w pamięci - restart serwera wymaga ponownego logowania
+#[derive(Clone)]
+pub struct Auth {
+    users: Arc<HashMap<String, UserEntry>>,
+    password_login: bool,
+    proxy_header: Option<String>,
+    session_ttl: i64,           //czas życia sesji w sekundach
+    cookie_secure: bool,
+    sessions: Arc<Mutex<HashMap<String, Session>>>,
+}
+
+impl Auth {
+    //users_file == None i proxy_header == None - logowanie wyłączone
+    //proxy_header - nazwa nagłówka z loginem ustawianego przez zaufane reverse proxy
+    //cookie_secure == false - tylko dla serwera dostępnego po zwykłym http
+    //proxy_header wymaga users_file - użytkownicy z nagłówka muszą mieć wpis z imieniem i emailem do commitów
+    pub fn load(users_file: Option<String>, proxy_header: Option<String>, session_ttl: i64, cookie_secure: bool) -> Result<Auth, String> {
+        if proxy_header.is_some() && users_file.is_none() {
+            return Err(String::from("AUTH_PROXY_HEADER requires USERS_FILE with the proxy users"));
+        }
+
+        let mut users = HashMap::new();
+
+        if let Some(users_file) = &users_file {
+            let data = std::fs::read_to_string(users_file)
+                .map_err(|err| format!("Problem with read users file {users_file} {err}"))?;
+
+            let list = serde_json::from_str::<Vec<UserEntry>>(&data)
+                .map_err(|err| format!("Problem with parse users file {users_file} {err}"))?;
+
+            if list.is_empty() {
+                return Err(format!("Users file {users_file} is empty"));
+            }
+
+            for user in list {
+                if users.contains_key(&user.login) {
+                    return Err(format!("Duplicated login {}", user.login));
+                }
+
+                users.insert(user.login.clone(), user);
+            }
+        }
+
+        Ok(Auth {
+            users: Arc::new(users),
+            password_login: users_file.is_some(),
+            proxy_header,
+            session_ttl,
+            cookie_secure,
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.password_login || self.proxy_header.is_some()
+    }
+
+    fn find_user(&self, login: &str) -> Option<UserEntry> {
+        self.users.get(login).cloned()
+    }
+
+    //Login z nagłówka proxy musi mieć wpis w pliku użytkowników, inaczej żądanie traktujemy jak niezalogowane
+    fn proxy_user(&self, req: &Request) -> Option<UserEntry> {
+        let header = self.proxy_header.as_ref()?;
+        let login = req.headers().get(header.as_str())?.to_str().ok()?.trim();
+
+        if login.is_empty() {
+            return None;
+        }
+
+        let user = self.find_user(login);
+
+        if user.is_none() {
+            log::warn!("Proxy user {login:?} is missing in users file");
+        }
+
+        user
+    }
+
+    fn request_user(&self, req: &Request) -> Option<UserEntry> {
+        if let Some(user) = self.proxy_user(req) {
+            return Some(user);
+        }
+
+        let token = session_token(req)?;
+        self.session_user(&token, now_seconds())
+    }
+
+    fn session_user(&self, token: &str, now: i64) -> Option<UserEntry> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let session = sessions.get(token)?;
+
+        if session.expires <= now {
+            sessions.remove(token);
+            return None;
+        }
+
+        let login = session.login.clone();
+        drop(sessions);
+        self.find_user(&login)
+    }
+
+    fn cookie(&self, value: &str, max_age: i64) -> String {
+        let secure = if self.cookie_secure { "; Secure" } else { "" };
+        format!("{SESSION_COOKIE}={value}; Path=/; HttpOnly; SameSite=Strict; Max-Age={max_age}{secure}")
+    }
+
+    fn login(&self, login: &str, password: &str) -> Option<String> {
+        if !self.password_login {
+            return None;
+        }
+
+        let user = self.find_user(login)?;
+
+        if user.password.is_empty() || !verify_password(password, &user.password) {
+            return None;
+        }
+
+        let token = new_token();
+        let now = now_seconds();
+
+        let mut sessions = self.sessions.lock().unwrap();
+        sessions.retain(|_, session| session.expires > now);
+        sessions.insert(token.clone(), Session {
+            login: user.login,
+            expires: now + self.session_ttl,
+        });
+
+        Some(token)
+    }
+
+    fn logout(&self, token: &str) {
+        self.sessions.lock().unwrap().remove(token);
+    }
+}
+
+//Strona startowa: "/" oraz "/repo/<nazwa>/" dla każdego repozytorium
+fn is_index(path: &str) -> bool {
+    if path == "/" {
+        return true;
+    }
+
+    let Some(name) = path.strip_prefix("/repo/") else {
+        return false;
+    };
+
+    let name = name.strip_suffix('/').unwrap_or(name);
+    !name.is_empty() && !name.contains('/')
+}
+
+//Bez sesji dostępna jest tylko strona startowa, pliki klienta oraz logowanie
+fn is_public(method: &Method, path: &str) -> bool {
+    if path == "/login" || path == "/logout" || path == "/whoami" || path.starts_with("/build/") {
+        return true;
+    }
+
+    *method == Method::GET && is_index(path)
+}
+
+pub struct AuthMiddleware {
+    auth: Auth,
+}
+
+impl AuthMiddleware {
+    pub fn new(auth: Auth) -> AuthMiddleware {
+        AuthMiddleware {
+            auth,
+        }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for AuthMiddleware {
+    type Output = AuthEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        AuthEndpoint {
+            ep,
+            auth: self.auth.clone(),
+        }
+    }
+}
+
+pub struct AuthEndpoint<E> {
+    ep: E,
+    auth: Auth,
+}
+
+#[async_trait]
+impl<E: Endpoint> Endpoint for AuthEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, mut req: Request) -> poem::Result<Self::Output> {
+        let user = self.auth.request_user(&req);
+
+        if self.auth.enabled() && user.is_none() && !is_public(req.method(), req.uri().path()) {
+            return Ok(Response::builder()
+                .status(StatusCode::UNAUTHORIZED)
+                .content_type("application/json; charset=utf-8")
+                .body("\"Unauthorized\""));
+        }
+
+        req.extensions_mut().insert(CurrentUser { user });
+
+        let response = self.ep.call(req).await?;
+        Ok(response.into_response())
+    }
+}
+
+#[handler]
+pub fn handler_login(body: Json<HandlerLoginBody>, auth: Data<&Auth>) -> Response {
+    let Json(body) = body;
+
+    let Some(token) = auth.login(&body.login, &body.password) else {
+        log::warn!("Failed login attempt for {:?}", body.login);
+
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .content_type("application/json; charset=utf-8")
+            .body("\"Incorrect login or password\"");
+    };
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::SET_COOKIE, auth.cookie(&token, auth.session_ttl))
+        .content_type("application/json; charset=utf-8")
+        .body("\"ok\"")
+}
+
+#[handler]
+pub fn handler_logout(req: &Request, auth: Data<&Auth>) -> Response {
+    if let Some(token) = session_token(req) {
+        auth.logout(&token);
+    }
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::SET_COOKIE, auth.cookie("", 0))
+        .content_type("application/json; charset=utf-8")
+        .body("\"ok\"")
+}
+
+#[handler]
+pub fn handler_whoami(user: Data<&CurrentUser>, auth: Data<&Auth>) -> Json<HandlerWhoamiResponse> {
+    let (login, name) = match &user.user {
+        Some(user) => (user.login.clone(), user.name.clone()),
+        None => (String::from(""), String::from("")),
+    };
+
+    Json(HandlerWhoamiResponse {
+        auth: auth.enabled(),
+        login,
+        name,
+    })
+}
+
+#[test]
+fn test_is_public() {
+    assert!(is_public(&Method::GET, "/"));
+    assert!(is_public(&Method::GET, "/repo/notatki/"));
+    assert!(is_public(&Method::GET, "/repo/notatki"));
+    assert!(is_public(&Method::GET, "/build/index.json"));
+    assert!(is_public(&Method::POST, "/login"));
+
+    assert!(!is_public(&Method::POST, "/"));
+    assert!(!is_public(&Method::GET, "/repo/"));
+    assert!(!is_public(&Method::GET, "/repo/notatki/fetch_root"));
+    assert!(!is_public(&Method::GET, "/repo/notatki/tasks/"));
+    assert!(!is_public(&Method::GET, "/tasks/"));
+    assert!(!is_public(&Method::GET, "/swagger/"));
+}
+
+#[test]
+fn test_proxy_requires_users_file() {
+    assert!(Auth::load(None, Some(String::from("X-Remote-User")), 3600, true).is_err());
+    assert!(Auth::load(None, None, 3600, true).is_ok());
+}
@@ -0,0 +1,163 @@
+//Typ zawartości dla pobieranych plików - najpierw po rozszerzeniu nazwy, potem po sygnaturze na początku pliku
+
+const OCTET_STREAM: &str = "application/octet-stream";
+
+fn from_ext(name: &str) -> Option<&'static str> {
+    let (_, ext) = name.rsplit_once('.')?;
+
+    let content_type = match ext.to_lowercase().as_str() {
+        "txt" | "todo" | "key" => "text/plain; charset=utf-8",
+        "md" => "text/markdown; charset=utf-8",
+        "csv" => "text/csv; charset=utf-8",
+        "html" | "htm" => "text/html; charset=utf-8",
+        "json" => "application/json",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        "tar" => "application/x-tar",
+        "7z" => "application/x-7z-compressed",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xls" => "application/vnd.ms-excel",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "odt" => "application/vnd.oasis.opendocument.text",
+        "ods" => "application/vnd.oasis.opendocument.spreadsheet",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "ogg" => "audio/ogg",
+        "m4a" => "audio/mp4",
+        "flac" => "audio/flac",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "mov" => "video/quicktime",
+        _ => {
+            return None;
+        }
+    };
+
+    Some(content_type)
+}
+
+fn from_signature(content: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF8", "image/gif"),
+        (b"ID3", "audio/mpeg"),
+        (b"OggS", "audio/ogg"),
+        (b"fLaC", "audio/flac"),
+        (b"\x1f\x8b", "application/gzip"),
+    ];
+
+    SIGNATURES
+        .iter()
+        .find(|(signature, _)| content.starts_with(signature))
+        .map(|(_, content_type)| *content_type)
+}
+
+pub fn detect(name: &str, content: &[u8]) -> &'static str {
+    if let Some(content_type) = from_ext(name) {
+        return content_type;
+    }
+
+    if let Some(content_type) = from_signature(content) {
+        return content_type;
+    }
+
+    if std::str::from_utf8(content).is_ok() {
+        return "text/plain; charset=utf-8";
+    }
+
+    OCTET_STREAM
+}
+
+//Typy, w których przeglądarka może wykonać skrypt
+fn is_active(content_type: &str) -> bool {
+    content_type.starts_with("text/html") || content_type.starts_with("image/svg+xml")
+}
+
+//Nagłówki dla /download
+pub struct Download {
+    pub content_type: &'static str,
+    pub disposition: String,
+    pub sandbox: bool,          //content-security-policy: sandbox, tylko dla typów aktywnych
+}
+
+//Aktywne typy (html, svg) są tylko do pobrania i dostają sandbox. Pozostałe otwierane są w przeglądarce
+//bez sandboxa - wbudowana przeglądarka pdf nie wyświetla dokumentu z sandboxem w iframe podglądu
+pub fn download(name: &str, content: &[u8]) -> Download {
+    let content_type = detect(name, content);
+
+    if is_active(content_type) {
+        return Download {
+            content_type: OCTET_STREAM,
+            disposition: content_disposition(name, "attachment"),
+            sandbox: true,
+        };
+    }
+
+    Download {
+        content_type,
+        disposition: content_disposition(name, "inline"),
+        sandbox: false,
+    }
+}
+
+//Nazwa w formacie RFC 5987, żeby przeglądarka zachowała polskie znaki
+fn content_disposition(name: &str, disposition: &str) -> String {
+    let mut encoded = String::new();
+
+    for byte in name.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+
+    let fallback: String = name
+        .chars()
+        .map(|char| if char.is_ascii_alphanumeric() || ".-_ ".contains(char) { char } else { '_' })
+        .collect();
+
+    format!("{disposition}; filename=\"{fallback}\"; filename*=UTF-8''{encoded}")
+}
+
+#[test]
+fn test_detect() {
+    assert_eq!(detect("raport.PDF", b""), "application/pdf");
+    assert_eq!(detect("plik", b"%PDF-1.4"), "application/pdf");
+    assert_eq!(detect("plik.bin", b"PK\x03\x04rest"), "application/zip");
+    assert_eq!(detect("notatka", "zażółć".as_bytes()), "text/plain; charset=utf-8");
+    assert_eq!(detect("dane", &[0, 159, 146, 150]), OCTET_STREAM);
+}
+
+#[test]
+fn test_content_disposition() {
+    assert_eq!(
+        content_disposition("żółw 1.pdf", "inline"),
+        "inline; filename=\"___w 1.pdf\"; filename*=UTF-8''%C5%BC%C3%B3%C5%82w%201.pdf"
+    );
+}
+
+#[test]
+fn test_download_active() {
+    let pdf = download("raport.pdf", b"");
+    assert_eq!(pdf.content_type, "application/pdf");
+    assert_eq!(pdf.disposition, "inline; filename=\"raport.pdf\"; filename*=UTF-8''raport.pdf");
+    assert!(!pdf.sandbox);
+
+    let html = download("strona.HTML", b"<script></script>");
+    assert_eq!(html.content_type, OCTET_STREAM);
+    assert!(html.sandbox);
+
+    assert_eq!(download("strona.htm", b"").disposition, "attachment; filename=\"strona.htm\"; filename*=UTF-8''strona.htm");
+    assert_eq!(download("rysunek.svg", b"<svg></svg>").content_type, OCTET_STREAM);
+}
@@ -0,0 +1,439 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use git2::{Delta, FileMode, ObjectType, Oid, Repository, Sort, Tree, TreeWalkMode, TreeWalkResult};
+use common::parse_task_lines;
+use crate::models::SearchLineItem;
+use crate::utils::ErrorProcess;
+
+const SEARCH_MAX_LINES: usize = 3;
+
+fn convert_path(path: Option<&Path>) -> Option<Vec<String>> {
+    let path = path?.to_str()?;
+
+    let path = path
+        .split('/')
+        .filter(|item| !item.is_empty())
+        .map(|item| item.to_string())
+        .collect::<Vec<String>>();
+
+    Some(path)
+}
+
+fn read_lines(repo: &Repository, id: Oid) -> Result<Option<Vec<String>>, ErrorProcess> {
+    let blob = repo.find_blob(id)?;
+
+    let lines = match std::str::from_utf8(blob.content()) {
+        Ok(content) => Some(content.lines().map(|line| line.to_string()).collect()),
+        Err(_) => None,
+    };
+
+    Ok(lines)
+}
+
+//Indeks zawartości plików dla aktualnego drzewa (root).
+//Po zmianie roota (commit lokalny lub synchronizacja) porównywane jest stare i nowe drzewo
+//i czytane są tylko bloby, które się zmieniły.
+pub struct ContentIndex {
+    root: Option<Oid>,
+    files: BTreeMap<Vec<String>, Oid>,
+    blobs: HashMap<Oid, Option<Vec<String>>>,       //None - blob nie jest tekstem utf8
+    last_modified: LastModified,
+}
+
+impl Default for ContentIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContentIndex {
+    pub fn new() -> ContentIndex {
+        ContentIndex {
+            root: None,
+            files: BTreeMap::new(),
+            blobs: HashMap::new(),
+            last_modified: LastModified::new(),
+        }
+    }
+
+    pub fn update(&mut self, repo: &Repository, new_root: Oid) -> Result<(), ErrorProcess> {
+        if self.root == Some(new_root) {
+            return Ok(());
+        }
+
+        let old_tree = match self.root {
+            Some(old_root) => repo.find_tree(old_root).ok(),
+            None => None,
+        };
+
+        match old_tree {
+            Some(old_tree) => {
+                let new_tree = repo.find_tree(new_root)?;
+                let diff = repo.diff_tree_to_tree(Some(&old_tree), Some(&new_tree), None)?;
+
+                for delta in diff.deltas() {
+                    if let Some(old_path) = convert_path(delta.old_file().path()) {
+                        self.files.remove(&old_path);
+                    }
+
+                    if delta.status() == Delta::Deleted {
+                        continue;
+                    }
+
+                    let new_file = delta.new_file();
+                    let is_blob = new_file.mode() == FileMode::Blob || new_file.mode() == FileMode::BlobExecutable;
+
+                    if let (true, Some(new_path)) = (is_blob, convert_path(new_file.path())) {
+                        self.files.insert(new_path, new_file.id());
+                    }
+                }
+            },
+            None => {
+                self.rebuild(repo, new_root)?;
+            }
+        }
+
+        for id in self.files.values() {
+            if !self.blobs.contains_key(id) {
+                self.blobs.insert(*id, read_lines(repo, *id)?);
+            }
+        }
+
+        let ids = self.files.values().copied().collect::<HashSet<Oid>>();
+        self.blobs.retain(|id, _| ids.contains(id));
+
+        self.root = Some(new_root);
+        Ok(())
+    }
+
+    fn rebuild(&mut self, repo: &Repository, new_root: Oid) -> Result<(), ErrorProcess> {
+        let tree = repo.find_tree(new_root)?;
+        let mut files = BTreeMap::new();
+
+        tree.walk(TreeWalkMode::PreOrder, |dir, entry| {
+            if entry.kind() == Some(ObjectType::Blob) {
+                if let Some(mut path) = convert_path(Some(Path::new(dir))) {
+                    if let Some(name) = entry.name() {
+                        path.push(name.to_string());
+                        files.insert(path, entry.id());
+                    }
+                }
+            }
+
+            TreeWalkResult::Ok
+        })?;
+
+        self.files = files;
+        Ok(())
+    }
+
+    //Czas commita z ostatnią zmianą każdego z plików, 0 - brak w historii
+    pub fn last_modified(&mut self, repo: &Repository, head: Oid, paths: &[Vec<String>]) -> Result<Vec<i64>, ErrorProcess> {
+        self.last_modified.get(repo, head, paths)
+    }
+
+    //pliki tekstowe z aktualnego drzewa, posortowane po ścieżce
+    pub fn text_files(&self) -> impl Iterator<Item = (&Vec<String>, &Vec<String>)> + '_ {
+        self.files.iter().filter_map(|(path, id)| {
+            let lines = self.blobs.get(id)?.as_ref()?;
+            Some((path, lines))
+        })
+    }
+}
+
+//Niezakończone zadania (numer linii, treść linii), z pominięciem bloków kodu
+pub fn open_tasks(lines: &[String]) -> impl Iterator<Item = (usize, &str)> {
+    parse_task_lines(lines.iter().map(String::as_str))
+        .enumerate()
+        .filter(|(_, (_, task))| task.map(|task| !task.checked).unwrap_or(false))
+        .map(|(number, (line, _))| (number, line))
+}
+
+fn find_path_id(tree: &Tree, path: &[String]) -> Option<Oid> {
+    let path = path.join("/");
+    tree.get_path(Path::new(&path)).ok().map(|entry| entry.id())
+}
+
+//Przejście po historii od head (bez commitów osiągalnych z hide). Dla każdej ścieżki szukany jest
+//najnowszy commit, który ją zmienił względem pierwszego rodzica, on_change dostaje indeks ścieżki i czas
+fn walk_changes(
+    repo: &Repository,
+    head: Oid,
+    hide: Option<Oid>,
+    paths: &[&Vec<String>],
+    mut on_change: impl FnMut(usize, i64),
+) -> Result<(), ErrorProcess> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+    revwalk.push(head)?;
+
+    if let Some(hide) = hide {
+        revwalk.hide(hide)?;
+    }
+
+    let mut pending = (0..paths.len()).collect::<Vec<usize>>();
+
+    for commit_id in revwalk {
+        if pending.is_empty() {
+            break;
+        }
+
+        let commit = repo.find_commit(commit_id?)?;
+        let tree = commit.tree()?;
+        let parent_tree = match commit.parents().next() {
+            Some(parent) => Some(parent.tree()?),
+            None => None,
+        };
+
+        pending.retain(|position| {
+            let id = find_path_id(&tree, paths[*position]);
+            let prev_id = parent_tree.as_ref().and_then(|parent_tree| find_path_id(parent_tree, paths[*position]));
+
+            if id.is_some() && id != prev_id {
+                on_change(*position, commit.time().seconds());
+                return false;
+            }
+
+            true
+        });
+    }
+
+    Ok(())
+}
+
+//Czasy ostatniej zmiany plików dla commita head. Po przesunięciu head do przodu przeglądane są
+//tylko nowe commity, całą historię przechodzimy jedynie dla ścieżek, których jeszcze nie ma w pamięci
+struct LastModified {
+    head: Option<Oid>,
+    times: HashMap<Vec<String>, i64>,
+}
+
+impl LastModified {
+    fn new() -> LastModified {
+        LastModified {
+            head: None,
+            times: HashMap::new(),
+        }
+    }
+
+    fn get(&mut self, repo: &Repository, head: Oid, paths: &[Vec<String>]) -> Result<Vec<i64>, ErrorProcess> {
+        if let Some(old_head) = self.head {
+            if old_head != head {
+                match repo.graph_descendant_of(head, old_head)? {
+                    true => {
+                        let cached = self.times.keys().cloned().collect::<Vec<_>>();
+                        let cached = cached.iter().collect::<Vec<_>>();
+                        walk_changes(repo, head, Some(old_head), &cached, |position, time| {
+                            self.times.insert(cached[position].clone(), time);
+                        })?;
+                    },
+                    false => {
+                        //historia została przepisana
+                        self.times.clear();
+                    }
+                }
+            }
+        }
+
+        self.head = Some(head);
+
+        let missing = paths
+            .iter()
+            .filter(|path| !self.times.contains_key(*path))
+            .collect::<Vec<_>>();
+
+        if !missing.is_empty() {
+            let mut found = vec![0; missing.len()];
+            walk_changes(repo, head, None, &missing, |position, time| {
+                found[position] = time;
+            })?;
+
+            for (path, time) in missing.into_iter().zip(found) {
+                self.times.insert(path.clone(), time);
+            }
+        }
+
+        //w pamięci zostają tylko ścieżki z ostatniego zapytania
+        let requested = paths.iter().collect::<HashSet<_>>();
+        self.times.retain(|path, _| requested.contains(path));
+
+        Ok(paths.iter().map(|path| self.times.get(path).copied().unwrap_or(0)).collect())
+    }
+}
+
+pub fn count_open_tasks(lines: &[String]) -> u32 {
+    open_tasks(lines).count() as u32
+}
+
+//Termin w postaci "@2026-11-01" w treści zadania lub w nazwie pliku .todo, zwracany jest pierwszy poprawny
+pub fn parse_due_date(text: &str) -> Option<String> {
+    for (position, _) in text.match_indices('@') {
+        let Some(date) = text.get(position + 1..position + 11) else {
+            continue;
+        };
+
+        let bytes = date.as_bytes();
+
+        let is_valid = bytes.iter().enumerate().all(|(index, char)| match index {
+            4 | 7 => *char == b'-',
+            _ => char.is_ascii_digit(),
+        });
+
+        if !is_valid {
+            continue;
+        }
+
+        let month = date[5..7].parse::<u32>().unwrap_or(0);
+        let day = date[8..10].parse::<u32>().unwrap_or(0);
+
+        if (1..=12).contains(&month) && (1..=31).contains(&day) {
+            return Some(date.to_string());
+        }
+    }
+
+    None
+}
+
+pub fn split_phrase(phrase: &str) -> Vec<String> {
+    phrase
+        .to_lowercase()
+        .split_whitespace()
+        .map(|item| item.to_string())
+        .filter(|item| item.chars().count() >= 2)
+        .collect::<Vec<String>>()
+}
+
+//plik pasuje, jeśli zawiera wszystkie fragmenty frazy, zwracane są pierwsze linie z trafieniami
+pub fn search_lines(lines: &[String], phrase: &[String]) -> Option<Vec<SearchLineItem>> {
+    let mut found = vec![false; phrase.len()];
+    let mut result = Vec::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        let line_lower = line.to_lowercase();
+        let mut line_match = false;
+
+        for (chunk_index, chunk) in phrase.iter().enumerate() {
+            if line_lower.contains(chunk.as_str()) {
+                found[chunk_index] = true;
+                line_match = true;
+            }
+        }
+
+        if line_match && result.len() < SEARCH_MAX_LINES {
+            result.push(SearchLineItem {
+                number: index as u32 + 1,
+                content: line.clone(),
+            });
+        }
+    }
+
+    if found.iter().all(|item| *item) {
+        Some(result)
+    } else {
+        None
+    }
+}
+
+#[test]
+fn test_open_tasks() {
+    let lines = ["[ ] kupić mleko", "  - [x] zrobione", "```", "- [ ] przykład w kodzie", "```", "12. [ ] punkt", "tekst [ ] w środku"]
+        .map(String::from);
+
+    assert_eq!(open_tasks(&lines).collect::<Vec<_>>(), vec![(0, "[ ] kupić mleko"), (5, "12. [ ] punkt")]);
+    assert_eq!(count_open_tasks(&lines), 2);
+}
+
+#[test]
+fn test_parse_due_date() {
+    assert_eq!(parse_due_date("[ ] raport @2026-11-01"), Some("2026-11-01".to_string()));
+    assert_eq!(parse_due_date("spotkanie@2026-01-05.todo"), Some("2026-01-05".to_string()));
+    assert_eq!(parse_due_date("mail user@example.com @2026-13-01 @2026-02-03"), Some("2026-02-03".to_string()));
+    assert_eq!(parse_due_date("@2026-11-1"), None);
+    assert_eq!(parse_due_date("bez terminu"), None);
+}
+
+#[cfg(test)]
+fn test_index_files(index: &ContentIndex) -> Vec<(String, Vec<String>)> {
+    index
+        .text_files()
+        .map(|(path, lines)| (path.join("/"), lines.clone()))
+        .collect()
+}
+
+#[test]
+fn test_update_incremental() {
+    use super::test_utils::{test_repo, test_change, test_change_bytes, test_root};
+
+    let repo = test_repo("content-index");
+    let mut index = ContentIndex::new();
+
+    //porównanie indeksu aktualizowanego przyrostowo z indeksem zbudowanym od zera
+    let check = |index: &mut ContentIndex, expected: Vec<(&str, Vec<&str>)>| {
+        index.update(&repo, test_root(&repo)).unwrap();
+
+        let mut fresh = ContentIndex::new();
+        fresh.update(&repo, test_root(&repo)).unwrap();
+
+        let expected = expected
+            .into_iter()
+            .map(|(path, lines)| (path.to_string(), lines.into_iter().map(String::from).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(test_index_files(index), expected);
+        assert_eq!(test_index_files(&fresh), expected);
+        assert_eq!(index.blobs.len(), fresh.blobs.len());
+    };
+
+    test_change(&repo, "add", &[("a.txt", Some("a1")), ("dir/b.txt", Some("b1"))]);
+    check(&mut index, vec!(("a.txt", vec!("a1")), ("dir/b.txt", vec!("b1"))));
+
+    test_change(&repo, "add nested", &[("dir/sub/c.txt", Some("c1\nc2"))]);
+    check(&mut index, vec!(("a.txt", vec!("a1")), ("dir/b.txt", vec!("b1")), ("dir/sub/c.txt", vec!("c1", "c2"))));
+
+    test_change(&repo, "modify", &[("a.txt", Some("a2"))]);
+    check(&mut index, vec!(("a.txt", vec!("a2")), ("dir/b.txt", vec!("b1")), ("dir/sub/c.txt", vec!("c1", "c2"))));
+
+    test_change(&repo, "delete", &[("dir/b.txt", None)]);
+    check(&mut index, vec!(("a.txt", vec!("a2")), ("dir/sub/c.txt", vec!("c1", "c2"))));
+
+    test_change(&repo, "rename", &[("dir/sub/c.txt", None), ("d.txt", Some("c1\nc2"))]);
+    check(&mut index, vec!(("a.txt", vec!("a2")), ("d.txt", vec!("c1", "c2"))));
+
+    //plik binarny jest w drzewie, ale nie trafia do plików tekstowych
+    test_change_bytes(&repo, "binary", &[("e.bin", Some(&[0xff, 0xfe, 0x00]))]);
+    check(&mut index, vec!(("a.txt", vec!("a2")), ("d.txt", vec!("c1", "c2"))));
+    assert_eq!(index.files.len(), 3);
+
+    test_change(&repo, "binary to text", &[("e.bin", Some("e1"))]);
+    check(&mut index, vec!(("a.txt", vec!("a2")), ("d.txt", vec!("c1", "c2")), ("e.bin", vec!("e1"))));
+
+    //usunięcie całego katalogu
+    test_change(&repo, "add dir", &[("x/y/z.txt", Some("z1"))]);
+    test_change(&repo, "delete dir", &[("x/y/z.txt", None)]);
+    check(&mut index, vec!(("a.txt", vec!("a2")), ("d.txt", vec!("c1", "c2")), ("e.bin", vec!("e1"))));
+}
+
+#[test]
+fn test_last_modified() {
+    use super::test_utils::{test_repo, test_commit_tree_at};
+
+    let repo = test_repo("last-modified");
+    let path = |path: &str| path.split('/').map(String::from).collect::<Vec<_>>();
+    let paths = [path("a.txt"), path("dir/b.txt")];
+
+    let first = test_commit_tree_at(&repo, "first", &[], &[("a.txt", Some("1")), ("dir/b.txt", Some("1"))], 1000);
+    let mut index = ContentIndex::new();
+    assert_eq!(index.last_modified(&repo, first, &paths).unwrap(), vec![1000, 1000]);
+
+    //przyrostowo - tylko nowe commity
+    let second = test_commit_tree_at(&repo, "second", &[first], &[("dir/b.txt", Some("2"))], 2000);
+    let third = test_commit_tree_at(&repo, "third", &[second], &[("c.txt", Some("1"))], 3000);
+
+    assert_eq!(index.last_modified(&repo, third, &paths).unwrap(), vec![1000, 2000]);
+    assert_eq!(ContentIndex::new().last_modified(&repo, third, &paths).unwrap(), vec![1000, 2000]);
+
+    //head, który nie jest następcą poprzedniego (np. po przepisaniu historii)
+    let other = test_commit_tree_at(&repo, "other", &[first], &[("a.txt", Some("3"))], 4000);
+    assert_eq!(index.last_modified(&repo, other, &paths).unwrap(), vec![4000, 1000]);
+    assert_eq!(index.last_modified(&repo, other, &[path("missing.txt")]).unwrap(), vec![0]);
+}
@@ -1,27 +1,49 @@
 use std::sync::Arc;
-use git2::{Repository, Oid};
-use crate::{utils::ErrorProcess, models::HandlerAddFilesFile};
+use git2::{Repository, Oid, BranchType};
+use crate::{utils::ErrorProcess, models::{HandlerAddFilesFile, FileHistoryItem, DiffLineItem, SearchResultItem, TaskFileItem, TodoFileItem, DueItem, HandlerSaveContentResponse, SaveContentConflict, HandlerDeleteManifestResponse, HandlerBatchOperation, HandlerTrashItem}};
 use tokio::sync::{Mutex, Notify};
-use super::git_session::{GitSession, GitId};
-use crate::git::GitBlob;
-
-fn split_last(path: &[String]) -> Result<(&[String], &String), ErrorProcess> {    
-    if let Some((last, begin)) = path.split_last() {
-        Ok((begin, last))
-    } else {
-        ErrorProcess::user_result("missing last element to split")
+use tokio::task;
+use super::git_session::{GitSession, GitId, PathFilter};
+use super::content_index::ContentIndex;
+use super::merge::{merge3, MergeResult};
+use super::git_sync::{self, SyncStatus, SyncResult};
+use super::git_operations::{self, split_last};
+use super::trash;
+use crate::git::{GitBlob, CommitAuthor};
+
+fn blob_to_string(blob: Option<GitBlob>, hash: &String) -> Result<String, ErrorProcess> {
+    match blob {
+        Some(GitBlob::Blob { content }) => match String::from_utf8(content) {
+            Ok(content) => Ok(content),
+            Err(_) => ErrorProcess::user_result(format!("content is not correctly encoded in utf8 {hash}")),
+        },
+        _ => ErrorProcess::user_result(format!("missing content {hash}")),
     }
 }
 
+async fn session_check_path<'repo>(session: GitSession<'repo>, path: &[String], id: &String) -> Result<GitSession<'repo>, ErrorProcess> {
+    let (session, current) = session.find_path(path).await?;
+
+    let Some(current) = current else {
+        return ErrorProcess::user_result(format!("Element not found {}", path.join("/")));
+    };
+
+    session.should_eq(&current, id)?;
+    Ok(session)
+}
+
 #[derive(Clone)]
 pub struct Git {
     notify: Arc<Notify>,
+    path: String,
     branch_name: String,
+    trash_dir: Option<String>,      //None - usuwanie bez kosza
     repo: Arc<Mutex<Repository>>,
+    content_index: Arc<Mutex<ContentIndex>>,
 }
 
 impl Git {
-    pub fn new(notify: Arc<Notify>, path: String, branch_name: String) -> Result<Git, ErrorProcess> {
+    pub fn new(notify: Arc<Notify>, path: String, branch_name: String, trash_dir: Option<String>) -> Result<Git, ErrorProcess> {
         let repository = match Repository::open(&path) {
             Ok(repo) => repo,
             Err(e) => {
@@ -29,19 +51,77 @@ impl Git {
             },
         };
 
+        if let Err(e) = repository.find_branch(&branch_name, BranchType::Local) {
+            return ErrorProcess::server_result(format!("Missing branch {branch_name} in repo: {path} {e}"));
+        }
+
         Ok(Git {
             notify,
+            path,
             branch_name,
-            repo: Arc::new(Mutex::new(repository))
+            trash_dir,
+            repo: Arc::new(Mutex::new(repository)),
+            content_index: Arc::new(Mutex::new(ContentIndex::new())),
         })
     }
 
+    pub fn trash_dir(&self) -> Option<&String> {
+        self.trash_dir.as_ref()
+    }
+
     async fn session<'repo>(&'repo self) -> Result<GitSession<'repo>, ErrorProcess> {
         let mutex_guard = self.repo.lock().await;
 
         GitSession::new(self.notify.clone(), mutex_guard, self.branch_name.as_str())
     }
 
+    //Operacje sieciowe (fetch, push) działają na osobnej instancji repozytorium,
+    //blokada jest trzymana tylko na czas przesuwania lokalnej gałęzi
+    pub async fn sync(&self, remote_name: &str) -> SyncResult {
+        let branch = self.branch_name.as_str();
+
+        let repo = match Repository::open(&self.path) {
+            Ok(repo) => repo,
+            Err(err) => {
+                return SyncResult {
+                    status: SyncStatus::Offline,
+                    ahead: 0,
+                    behind: 0,
+                    error: Some(format!("Problem with open repo {} {err}", self.path)),
+                };
+            }
+        };
+
+        if let Err(err) = task::block_in_place(|| git_sync::fetch(&repo, remote_name, branch)) {
+            return SyncResult::new(&repo, remote_name, branch, SyncStatus::Offline, Some(err));
+        }
+
+        let current = {
+            let repo = self.repo.lock().await;
+            task::block_in_place(|| git_sync::integrate(&repo, remote_name, branch))
+        };
+
+        let current = match current {
+            Ok(current) => current,
+            Err(err) => {
+                return SyncResult::new(&repo, remote_name, branch, SyncStatus::Error, Some(err));
+            }
+        };
+
+        if current != SyncStatus::Ahead {
+            return SyncResult::new(&repo, remote_name, branch, current, None);
+        }
+
+        if let Err(err) = task::block_in_place(|| git_sync::push(&repo, remote_name, branch)) {
+            return SyncResult::new(&repo, remote_name, branch, SyncStatus::Ahead, Some(err));
+        }
+
+        match git_sync::status(&repo, remote_name, branch) {
+            Ok(current) => SyncResult::new(&repo, remote_name, branch, current, None),
+            Err(err) => SyncResult::new(&repo, remote_name, branch, SyncStatus::Error, Some(err)),
+        }
+    }
+
     pub async fn main_commit(
         &self,
     ) -> Result<String, ErrorProcess> {
@@ -51,10 +131,11 @@ impl Git {
 
     pub async fn save_content(
         &self,
+        author: Option<CommitAuthor>,
         mut path: Vec<String>,
         prev_hash: String,
         new_content: String
-    ) -> Result<String, ErrorProcess> {
+    ) -> Result<HandlerSaveContentResponse, ErrorProcess> {
         let session = self.session().await?;
         let message = format!("save {}", path.join("/"));
 
@@ -67,17 +148,45 @@ impl Git {
             }
         };
 
-        let (session, prev_content_id) = session.extract_child(&path, &file_name).await?;
-
-        if prev_content_id.id.to_string() != prev_hash {
-            return ErrorProcess::user_result(format!("item not found to be modified = {file_name}, hash mismatch"));
-        }
+        let (session, current_content_id) = session.extract_child(&path, &file_name).await?;
+        let current_hash = current_content_id.id.to_string();
+
+        //plik zmienił się w trakcie edycji - próba scalenia trójstronnego
+        let (session, new_content) = if current_hash != prev_hash {
+            let (session, base) = session.get_from_id(&prev_hash).await?;
+            let base = blob_to_string(base, &prev_hash)?;
+
+            let (session, current) = session.get_from_id(&current_hash).await?;
+            let current = blob_to_string(current, &current_hash)?;
+
+            match merge3(&base, &current, &new_content) {
+                MergeResult::Clean(new_content) => (session, new_content),
+                MergeResult::Conflict(chunks) => {
+                    session.end();
+
+                    return Ok(HandlerSaveContentResponse {
+                        root: String::from(""),
+                        conflict: Some(SaveContentConflict {
+                            current_hash,
+                            chunks,
+                        }),
+                    });
+                }
+            }
+        } else {
+            (session, new_content)
+        };
 
         let (session, new_content_id) = session.create_blob(new_content).await?;
 
         let session = session.insert_child(&path, &file_name, new_content_id).await?;
 
-        session.commit(message).await
+        let root = session.commit(author, message).await?;
+
+        Ok(HandlerSaveContentResponse {
+            root,
+            conflict: None,
+        })
     }
 
     pub async fn create_blob(&self, data: Vec<u8>) -> Result<String, ErrorProcess> {
@@ -94,84 +203,207 @@ impl Git {
         Ok(result)
     }
 
-    pub async fn create_file(
-        &self,
-        path: Vec<String>,      //wskazuje na katalog w którym utworzymy nową treść
-        new_name: String,
-        new_content: String,
-    ) -> Result<String, ErrorProcess> {
+    //Element pod ścieżką w aktualnym drzewie. Id od klienta musi się zgadzać z tym ze ścieżki,
+    //dzięki temu uprawnienia sprawdzone dla ścieżki dotyczą zwracanych danych
+    pub async fn get_from_path(&self, path: &[String], id: &String) -> Result<Option<GitBlob>, ErrorProcess> {
+        let session = self.session().await?;
+        let session = session_check_path(session, path, id).await?;
+        let (session, result) = session.get_from_id(id).await?;
+        session.end();
+
+        Ok(result)
+    }
+
+    pub async fn check_path(&self, path: &[String], id: &String) -> Result<(), ErrorProcess> {
+        let session = self.session().await?;
+        let session = session_check_path(session, path, id).await?;
+        session.end();
+
+        Ok(())
+    }
+
+    //visible == None - użytkownik widzi wszystkie ścieżki pod path
+    pub async fn file_history(&self, path: Vec<String>, visible: Option<PathFilter<'_>>) -> Result<Vec<FileHistoryItem>, ErrorProcess> {
+        let session = self.session().await?;
+        let (session, list) = session.file_history(&path, visible).await?;
+        session.end();
+
+        Ok(list)
+    }
+
+    pub async fn search(&self, phrase: String) -> Result<Vec<SearchResultItem>, ErrorProcess> {
+        let mut content_index = self.content_index.lock().await;
+
         let session = self.session().await?;
+        let (session, list) = session.search(&mut content_index, &phrase).await?;
+        session.end();
+
+        Ok(list)
+    }
+
+    pub async fn tasks(&self) -> Result<Vec<TaskFileItem>, ErrorProcess> {
+        let mut content_index = self.content_index.lock().await;
 
-        let (session, new_content_id) = session.create_file_content(&new_content).await?;
+        let session = self.session().await?;
+        let (session, list) = session.tasks(&mut content_index).await?;
+        session.end();
 
-        let (session, old_child) = session.remove_child(&path, &new_name).await?;
+        Ok(list)
+    }
+
+    pub async fn todo_dashboard(&self) -> Result<Vec<TodoFileItem>, ErrorProcess> {
+        let mut content_index = self.content_index.lock().await;
+
+        let session = self.session().await?;
+        let (session, list) = session.todo_dashboard(&mut content_index).await?;
+        session.end();
+
+        Ok(list)
+    }
 
-        if old_child.is_some() {
-            return Err(ErrorProcess::user(format!("File exists in this location: {}", &new_name)));
+    pub async fn due(&self, today: String, limit: String) -> Result<Vec<DueItem>, ErrorProcess> {
+        let mut content_index = self.content_index.lock().await;
+
+        let session = self.session().await?;
+        let (session, list) = session.due(&mut content_index, &today, &limit).await?;
+        session.end();
+
+        Ok(list)
+    }
+
+    //Porównywane bloby muszą być wersjami pliku spod ścieżki
+    pub async fn diff(&self, path: Vec<String>, prev_id: String, next_id: String) -> Result<Vec<DiffLineItem>, ErrorProcess> {
+        let session = self.session().await?;
+        let (session, history) = session.file_history(&path, None).await?;
+
+        for id in [&prev_id, &next_id] {
+            if !id.is_empty() && !history.iter().any(|item| item.id == *id) {
+                return ErrorProcess::user_result(format!("Blob {id} is not a version of {}", path.join("/")));
+            }
         }
 
-        let session = session.insert_child(&path, &new_name, new_content_id).await?;
+        let (session, list) = session.diff(&prev_id, &next_id).await?;
+        session.end();
 
-        let message = format!("create file {}/{}", path.join("/"), new_name);
-        session.commit(message).await
+        Ok(list)
+    }
+
+    pub async fn diff_commits(&self, path: Vec<String>, prev_commit: String, next_commit: String) -> Result<Vec<DiffLineItem>, ErrorProcess> {
+        let session = self.session().await?;
+        let (session, list) = session.diff_commits(&path, &prev_commit, &next_commit).await?;
+        session.end();
+
+        Ok(list)
+    }
+
+    pub async fn create_file(
+        &self,
+        author: Option<CommitAuthor>,
+        path: Vec<String>,      //wskazuje na katalog w którym utworzymy nową treść
+        new_name: String,
+        new_content: String,
+    ) -> Result<String, ErrorProcess> {
+        let session = self.session().await?;
+        let (session, message) = git_operations::create_file(session, &path, &new_name, &new_content).await?;
+        session.commit(author, message).await
     }
 
     pub async fn create_dir(
         &self,
+        author: Option<CommitAuthor>,
         path: Vec<String>,
         dir: String,
     ) -> Result<String, ErrorProcess> {
         let session = self.session().await?;
-
-        let (session, empty_dir) = session.create_empty_dir().await?;
-
-        let session = session.insert_child(&path, &dir, empty_dir).await?;
-
-        let message = format!("create dir {}/{}", path.join("/"), dir);
-        session.commit(message).await
+        let (session, message) = git_operations::create_dir(session, &path, &dir).await?;
+        session.commit(author, message).await
     }
 
 
     pub async fn rename_item(
         &self,
+        author: Option<CommitAuthor>,
         path: Vec<String>,          //wskazuje na katalog
         prev_name: String,          //mona od razu utworzyc potrzebne podktalogi
         prev_hash: String,
         new_name: String,
     ) -> Result<String, ErrorProcess> {
         let session = self.session().await?;
-
-        let (session, child) = session.extract_child(&path, &prev_name).await?;
-        session.should_eq(&child, &prev_hash)?;
-        let session = session.insert_child(&path, &new_name, child).await?;
-
-        let message = format!("rename {} {prev_name} -> {new_name}", path.join("/"));
-        session.commit(message).await
+        let (session, message) = git_operations::rename_item(session, &path, &prev_name, &prev_hash, &new_name).await?;
+        session.commit(author, message).await
     }
 
     pub async fn move_item(
         &self,
+        author: Option<CommitAuthor>,
+        path: Vec<String>,          //dir lub file
+        hash: String,
+        new_path: Vec<String>,
+    ) -> Result<String, ErrorProcess> {
+        let session = self.session().await?;
+        let (session, message) = git_operations::move_item(session, &path, &hash, &new_path).await?;
+        session.commit(author, message).await
+    }
+
+    //kopia wskazuje na te same obiekty tree/blob co oryginał
+    pub async fn copy_item(
+        &self,
+        author: Option<CommitAuthor>,
         path: Vec<String>,          //dir lub file
         hash: String,
         new_path: Vec<String>,
     ) -> Result<String, ErrorProcess> {
-        let (path_base, path_last) = split_last(&path)?;
         let (new_path_base, new_path_last) = split_last(&new_path)?;
 
         let session = self.session().await?;
-        let (session, child) = session.extract_child(path_base, path_last).await?;
+        let (session, child) = session.find_path(&path).await?;
+
+        let Some(child) = child else {
+            return ErrorProcess::user_result(format!("No file exists in the location: {}", path.join("/")));
+        };
 
         session.should_eq(&child, &hash)?;
 
         let session = session.insert_child(new_path_base, new_path_last, child).await?;
-        
-        let message = format!("move item from={} to={}", path.join("/"), new_path.join("/"));
-        let new_root_id = session.commit(message).await?;
-        Ok(new_root_id)
+
+        let message = format!("copy item from={} to={}", path.join("/"), new_path.join("/"));
+        session.commit(author, message).await
+    }
+
+    pub async fn restore_item(
+        &self,
+        author: Option<CommitAuthor>,
+        path: Vec<String>,          //dir lub file
+        prev_hash: String,          //bieżąca wersja elementu, pusty string - element obecnie nie istnieje
+        commit_id: String,          //commit z którego odtwarzamy element
+    ) -> Result<String, ErrorProcess> {
+        let (path_base, path_last) = split_last(&path)?;
+
+        let session = self.session().await?;
+        let (session, current) = session.find_path(&path).await?;
+
+        match current {
+            Some(current) => session.should_eq(&current, &prev_hash)?,
+            None => {
+                if !prev_hash.is_empty() {
+                    return ErrorProcess::user_result(format!("element does not exist - {}", path.join("/")));
+                }
+            }
+        }
+
+        let (session, child) = session.find_in_commit(&commit_id, &path).await?;
+
+        //jeśli element obecnie istnieje, to zostanie zastąpiony wersją z commita
+        let (session, _) = session.remove_child(path_base, path_last).await?;
+        let session = session.insert_child(path_base, path_last, child).await?;
+
+        let message = format!("restore {} from {commit_id}", path.join("/"));
+        session.commit(author, message).await
     }
 
     pub async fn add_files(
         &self,
+        author: Option<CommitAuthor>,
         path: Vec<String>,
         files: Vec<HandlerAddFilesFile>,
     ) -> Result<String, ErrorProcess> {
@@ -191,40 +423,257 @@ impl Git {
         }
 
         let message = format!("Add files to path={}, files={}", path.join("/"), files_name.join(","));
-        session.commit(message).await
+        session.commit(author, message).await
     }
 
-    pub async fn delete_item(
+    //Podsumowanie elementu do potwierdzenia usunięcia, hash w odpowiedzi zabezpiecza późniejsze usunięcie
+    pub async fn delete_manifest(
         &self,
         path: Vec<String>,
         item_hash: String,
-    ) -> Result<String, ErrorProcess> {
+    ) -> Result<HandlerDeleteManifestResponse, ErrorProcess> {
         let (path_base, path_last) = split_last(&path)?;
-        
+
         let session = self.session().await?;
         let (session, child) = session.extract_child(path_base, path_last).await?;
 
         session.should_eq(&child, &item_hash)?;
-        
-        let (session, result) = session.get_from_id(&child.id.to_string()).await?;
 
-        match result {
-            Some(GitBlob::Tree { list }) => {
-                if list.len() > 0 {
-                    return Err(ErrorProcess::user(format!("non-empty directory cannot be deleted {path:?}")));
-                }
-                //ok
-            },
-            Some(GitBlob::Blob { .. }) => {
-                //ok
-            },
-            None => {
-                return Err(ErrorProcess::user(format!("Missing hash {item_hash}")));
-            }
+        let (session, manifest) = session.manifest(&child, path_last).await?;
+        session.end();
+
+        Ok(manifest)
+    }
+
+    pub async fn delete_item(
+        &self,
+        author: Option<CommitAuthor>,
+        path: Vec<String>,
+        item_hash: String,
+        recursive: bool,            //true - usuwa katalog razem z zawartością
+    ) -> Result<String, ErrorProcess> {
+        let session = self.session().await?;
+        let (session, message) = git_operations::remove_item(session, &path, &item_hash, recursive, self.trash_dir.as_ref()).await?;
+        session.commit(author, message).await
+    }
+
+    //Wszystkie operacje w jednej sesji i jednym commicie. Błąd którejkolwiek (np. niezgodny hash)
+    //kończy sesję bez commita, więc żadna ze zmian nie zostanie zapisana
+    pub async fn batch(
+        &self,
+        author: Option<CommitAuthor>,
+        operations: Vec<HandlerBatchOperation>,
+    ) -> Result<String, ErrorProcess> {
+        if operations.is_empty() {
+            return ErrorProcess::user_result("empty list of operations");
+        }
+
+        let count = operations.len();
+        let mut session = self.session().await?;
+        let mut messages = Vec::new();
+
+        for (index, operation) in operations.into_iter().enumerate() {
+            let (next_session, message) = git_operations::batch_operation(session, operation, self.trash_dir.as_ref())
+                .await
+                .map_err(|err| err.context("batch operation", index))?;
+
+            session = next_session;
+            messages.push(message);
+        }
+
+        let message = format!("batch ({count} operations)\n\n{}", messages.join("\n"));
+        session.commit(author, message).await
+    }
+
+    fn require_trash_dir(&self) -> Result<&String, ErrorProcess> {
+        match &self.trash_dir {
+            Some(trash_dir) => Ok(trash_dir),
+            None => ErrorProcess::user_result("trash is disabled"),
+        }
+    }
+
+    pub async fn trash_list(&self) -> Result<Vec<HandlerTrashItem>, ErrorProcess> {
+        let Some(trash_dir) = &self.trash_dir else {
+            return Ok(Vec::new());
         };
 
-        let commit_message = format!("delete {}", path.join("/"));
-        let new_root_id = session.commit(commit_message).await?;
-        Ok(new_root_id)
+        let session = self.session().await?;
+        let (session, list) = trash::trash_list(session, trash_dir).await?;
+        session.end();
+
+        Ok(list)
     }
+
+    pub async fn trash_entry(&self, entry: &String) -> Result<HandlerTrashItem, ErrorProcess> {
+        let trash_dir = self.require_trash_dir()?;
+
+        let session = self.session().await?;
+        let (session, item) = trash::trash_entry(session, trash_dir, entry).await?;
+        session.end();
+
+        Ok(item)
+    }
+
+    pub async fn trash_restore(
+        &self,
+        author: Option<CommitAuthor>,
+        entry: String,
+        hash: String,
+    ) -> Result<String, ErrorProcess> {
+        let trash_dir = self.require_trash_dir()?;
+
+        let session = self.session().await?;
+        let (session, message) = trash::restore(session, &entry, &hash, trash_dir).await?;
+        session.commit(author, message).await
+    }
+
+    pub async fn trash_purge(
+        &self,
+        author: Option<CommitAuthor>,
+        entry: String,
+        hash: String,
+    ) -> Result<String, ErrorProcess> {
+        let trash_dir = self.require_trash_dir()?;
+
+        let session = self.session().await?;
+        let (session, message) = trash::purge(session, &entry, &hash, trash_dir).await?;
+        session.commit(author, message).await
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_restore_item() {
+    use git2::ObjectType;
+    use super::test_utils::{test_repo, test_change, test_git, test_read, test_path};
+
+    let hash = |content: &str| Oid::hash_object(ObjectType::Blob, content.as_bytes()).unwrap().to_string();
+
+    let repo = test_repo("restore");
+    let first = test_change(&repo, "first", &[("a.txt", Some("1"))]).to_string();
+    test_change(&repo, "second", &[("a.txt", Some("2"))]);
+    let git = test_git(&repo);
+
+    //element został zmieniony w międzyczasie
+    assert!(git.restore_item(None, test_path("a.txt"), hash("1"), first.clone()).await.is_err());
+    assert_eq!(test_read(&repo, "a.txt"), Some(String::from("2")));
+
+    git.restore_item(None, test_path("a.txt"), hash("2"), first.clone()).await.unwrap();
+    assert_eq!(test_read(&repo, "a.txt"), Some(String::from("1")));
+
+    test_change(&repo, "delete", &[("a.txt", None)]);
+
+    assert!(git.restore_item(None, test_path("a.txt"), hash("1"), first.clone()).await.is_err());
+    git.restore_item(None, test_path("a.txt"), String::from(""), first).await.unwrap();
+    assert_eq!(test_read(&repo, "a.txt"), Some(String::from("1")));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_from_path() {
+    use git2::ObjectType;
+    use super::test_utils::{test_repo, test_change, test_git, test_path};
+
+    let hash = |content: &str| Oid::hash_object(ObjectType::Blob, content.as_bytes()).unwrap().to_string();
+
+    let repo = test_repo("from-path");
+    test_change(&repo, "first", &[("public/a.txt", Some("a")), ("private/b.txt", Some("b"))]);
+    let git = test_git(&repo);
+
+    let Some(GitBlob::Blob { content }) = git.get_from_path(&test_path("public/a.txt"), &hash("a")).await.unwrap() else {
+        panic!("expected blob");
+    };
+    assert_eq!(content, b"a".to_vec());
+
+    //hash innego pliku podany razem z dostępną ścieżką
+    assert!(git.get_from_path(&test_path("public/a.txt"), &hash("b")).await.is_err());
+    assert!(git.get_from_path(&test_path("public/missing.txt"), &hash("b")).await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_diff_only_file_versions() {
+    use git2::ObjectType;
+    use super::test_utils::{test_repo, test_change, test_git, test_path};
+
+    let hash = |content: &str| Oid::hash_object(ObjectType::Blob, content.as_bytes()).unwrap().to_string();
+
+    let repo = test_repo("diff-versions");
+    test_change(&repo, "first", &[("a.txt", Some("1")), ("secret.txt", Some("s"))]);
+    test_change(&repo, "second", &[("a.txt", Some("2"))]);
+    let git = test_git(&repo);
+
+    let list = git.diff(test_path("a.txt"), hash("1"), hash("2")).await.unwrap();
+    assert_eq!(list.len(), 2);
+
+    assert!(git.diff(test_path("a.txt"), String::from(""), hash("s")).await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_delete_item_recursive() {
+    use super::test_utils::{test_repo, test_change, test_git, test_read, test_root, test_path};
+
+    let repo = test_repo("delete");
+    test_change(&repo, "first", &[("dir/a.txt", Some("a")), ("dir/sub/b.txt", Some("b"))]);
+    let git = test_git(&repo);
+
+    let root = test_root(&repo);
+    let dir = repo.find_tree(root).unwrap().get_path(std::path::Path::new("dir")).unwrap().id().to_string();
+
+    //niepusty katalog bez flagi recursive zostaje nietknięty
+    assert!(git.delete_item(None, test_path("dir"), dir.clone(), false).await.is_err());
+    assert_eq!(test_root(&repo), root);
+
+    git.delete_item(None, test_path("dir"), dir, true).await.unwrap();
+    assert_eq!(test_read(&repo, "dir/a.txt"), None);
+    assert_eq!(test_read(&repo, "dir/sub/b.txt"), None);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_copy_item() {
+    use super::test_utils::{test_repo, test_change, test_git, test_read, test_root, test_path};
+
+    let repo = test_repo("copy");
+    test_change(&repo, "first", &[("dir/a.txt", Some("a")), ("other/b.txt", Some("b"))]);
+    let git = test_git(&repo);
+
+    let root = test_root(&repo);
+    let dir = repo.find_tree(root).unwrap().get_path(std::path::Path::new("dir")).unwrap().id().to_string();
+
+    //element o tej nazwie już istnieje
+    assert!(git.copy_item(None, test_path("dir"), dir.clone(), test_path("other")).await.is_err());
+    assert!(git.copy_item(None, test_path("missing"), dir.clone(), test_path("copy")).await.is_err());
+    assert_eq!(test_root(&repo), root);
+
+    git.copy_item(None, test_path("dir"), dir, test_path("other/dir")).await.unwrap();
+    assert_eq!(test_read(&repo, "dir/a.txt"), Some(String::from("a")));
+    assert_eq!(test_read(&repo, "other/dir/a.txt"), Some(String::from("a")));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_batch_hash_mismatch() {
+    use git2::ObjectType;
+    use super::test_utils::{test_repo, test_change, test_git, test_read, test_root};
+
+    let hash = |content: &str| Oid::hash_object(ObjectType::Blob, content.as_bytes()).unwrap().to_string();
+
+    let repo = test_repo("batch");
+    test_change(&repo, "first", &[("a.txt", Some("a")), ("b.txt", Some("b"))]);
+    let git = test_git(&repo);
+    let root = test_root(&repo);
+
+    let operations = |b_hash: String| serde_json::from_value::<Vec<HandlerBatchOperation>>(serde_json::json!([
+        { "operation": "mkdir", "path": [], "name": "dir" },
+        { "operation": "save", "path": ["a.txt"], "hash": hash("a"), "content": "a2" },
+        { "operation": "delete", "path": ["b.txt"], "hash": b_hash },
+    ])).unwrap();
+
+    //niezgodny hash w ostatniej operacji - żadna z wcześniejszych zmian nie zostaje zapisana
+    assert!(git.batch(None, operations(hash("x"))).await.is_err());
+    assert_eq!(test_root(&repo), root);
+    assert_eq!(test_read(&repo, "a.txt"), Some(String::from("a")));
+
+    git.batch(None, operations(hash("b"))).await.unwrap();
+    assert_eq!(test_read(&repo, "a.txt"), Some(String::from("a2")));
+    assert_eq!(test_read(&repo, "b.txt"), None);
+
+    let unknown = serde_json::from_value::<HandlerBatchOperation>(serde_json::json!({ "operation": "chmod", "path": [] }));
+    assert!(unknown.is_err());
 }
@@ -0,0 +1,174 @@
+use crate::{utils::{ErrorProcess, now_seconds}, models::HandlerBatchOperation};
+use super::git_session::GitSession;
+use super::models::GitBlob;
+use super::trash;
+
+//Pojedyncze operacje na drzewie w ramach sesji. Zwracają opis zmiany do wiadomości commita,
+//dzięki czemu można je wykonać pojedynczo lub kilka w jednym commicie (batch)
+
+pub fn split_last(path: &[String]) -> Result<(&[String], &String), ErrorProcess> {
+    if let Some((last, begin)) = path.split_last() {
+        Ok((begin, last))
+    } else {
+        ErrorProcess::user_result("missing last element to split")
+    }
+}
+
+pub async fn create_file<'repo>(
+    session: GitSession<'repo>,
+    path: &[String],            //wskazuje na katalog w którym utworzymy nową treść
+    new_name: &String,
+    new_content: &String,
+) -> Result<(GitSession<'repo>, String), ErrorProcess> {
+    let (session, new_content_id) = session.create_file_content(new_content).await?;
+
+    let (session, old_child) = session.remove_child(path, new_name).await?;
+
+    if old_child.is_some() {
+        return Err(ErrorProcess::user(format!("File exists in this location: {}", new_name)));
+    }
+
+    let session = session.insert_child(path, new_name, new_content_id).await?;
+
+    Ok((session, format!("create file {}/{}", path.join("/"), new_name)))
+}
+
+pub async fn create_dir<'repo>(
+    session: GitSession<'repo>,
+    path: &[String],
+    dir: &String,
+) -> Result<(GitSession<'repo>, String), ErrorProcess> {
+    let (session, empty_dir) = session.create_empty_dir().await?;
+
+    let session = session.insert_child(path, dir, empty_dir).await?;
+
+    Ok((session, format!("create dir {}/{}", path.join("/"), dir)))
+}
+
+//Zapis bez scalania - hash musi się zgadzać z aktualną wersją pliku
+pub async fn save_content<'repo>(
+    session: GitSession<'repo>,
+    path: &[String],
+    prev_hash: &String,
+    new_content: String,
+) -> Result<(GitSession<'repo>, String), ErrorProcess> {
+    let (path_base, path_last) = split_last(path)?;
+
+    let (session, child) = session.extract_child(path_base, path_last).await?;
+    session.should_eq(&child, prev_hash)?;
+
+    let (session, new_content_id) = session.create_blob(new_content).await?;
+    let session = session.insert_child(path_base, path_last, new_content_id).await?;
+
+    Ok((session, format!("save {}", path.join("/"))))
+}
+
+pub async fn rename_item<'repo>(
+    session: GitSession<'repo>,
+    path: &[String],            //wskazuje na katalog
+    prev_name: &String,
+    prev_hash: &String,
+    new_name: &String,
+) -> Result<(GitSession<'repo>, String), ErrorProcess> {
+    let (session, child) = session.extract_child(path, prev_name).await?;
+    session.should_eq(&child, prev_hash)?;
+    let session = session.insert_child(path, new_name, child).await?;
+
+    Ok((session, format!("rename {} {prev_name} -> {new_name}", path.join("/"))))
+}
+
+pub async fn move_item<'repo>(
+    session: GitSession<'repo>,
+    path: &[String],            //dir lub file
+    hash: &String,
+    new_path: &[String],
+) -> Result<(GitSession<'repo>, String), ErrorProcess> {
+    let (path_base, path_last) = split_last(path)?;
+    let (new_path_base, new_path_last) = split_last(new_path)?;
+
+    let (session, child) = session.extract_child(path_base, path_last).await?;
+
+    session.should_eq(&child, hash)?;
+
+    let session = session.insert_child(new_path_base, new_path_last, child).await?;
+
+    Ok((session, format!("move item from={} to={}", path.join("/"), new_path.join("/"))))
+}
+
+pub async fn delete_item<'repo>(
+    session: GitSession<'repo>,
+    path: &[String],
+    item_hash: &String,
+    recursive: bool,            //true - usuwa katalog razem z zawartością
+) -> Result<(GitSession<'repo>, String), ErrorProcess> {
+    let (path_base, path_last) = split_last(path)?;
+
+    let (session, child) = session.extract_child(path_base, path_last).await?;
+
+    session.should_eq(&child, item_hash)?;
+
+    let (session, result) = session.get_from_id(&child.id.to_string()).await?;
+
+    match result {
+        Some(GitBlob::Tree { list }) => {
+            if !list.is_empty() && !recursive {
+                return Err(ErrorProcess::user(format!("non-empty directory cannot be deleted {path:?}")));
+            }
+
+            let (session, manifest) = session.manifest(&child, path_last).await?;
+            let message = format!("delete {} (files={}, dirs={})", path.join("/"), manifest.files, manifest.dirs);
+            Ok((session, message))
+        },
+        Some(GitBlob::Blob { .. }) => {
+            Ok((session, format!("delete {}", path.join("/"))))
+        },
+        None => {
+            Err(ErrorProcess::user(format!("Missing hash {item_hash}")))
+        }
+    }
+}
+
+//Przy włączonym koszu element jest do niego przenoszony, usunięcie w koszu jest trwałe
+pub async fn remove_item<'repo>(
+    session: GitSession<'repo>,
+    path: &[String],
+    item_hash: &String,
+    recursive: bool,
+    trash_dir: Option<&String>,
+) -> Result<(GitSession<'repo>, String), ErrorProcess> {
+    match trash_dir {
+        Some(trash_dir) if !trash::is_in_trash(path, trash_dir) => {
+            trash::trash_item(session, path, item_hash, recursive, trash_dir, now_seconds()).await
+        },
+        _ => {
+            delete_item(session, path, item_hash, recursive).await
+        }
+    }
+}
+
+pub async fn batch_operation<'repo>(
+    session: GitSession<'repo>,
+    operation: HandlerBatchOperation,
+    trash_dir: Option<&String>,
+) -> Result<(GitSession<'repo>, String), ErrorProcess> {
+    match operation {
+        HandlerBatchOperation::Create(operation) => {
+            create_file(session, &operation.path, &operation.name, &operation.content).await
+        },
+        HandlerBatchOperation::Mkdir(operation) => {
+            create_dir(session, &operation.path, &operation.name).await
+        },
+        HandlerBatchOperation::Save(operation) => {
+            save_content(session, &operation.path, &operation.hash, operation.content).await
+        },
+        HandlerBatchOperation::Rename(operation) => {
+            rename_item(session, &operation.path, &operation.name, &operation.hash, &operation.new_name).await
+        },
+        HandlerBatchOperation::Move(operation) => {
+            move_item(session, &operation.path, &operation.hash, &operation.new_path).await
+        },
+        HandlerBatchOperation::Delete(operation) => {
+            remove_item(session, &operation.path, &operation.hash, operation.recursive, trash_dir).await
+        },
+    }
+}
@@ -1,14 +1,23 @@
+use std::path::Path;
 use std::sync::Arc;
 
-use git2::{BranchType, ObjectType, Oid, Repository, Tree, TreeBuilder, TreeEntry, Signature};
+use git2::{
+    BranchType, Commit, ObjectType, Oid, Repository, Tree, TreeBuilder, TreeEntry, Signature, Sort,
+    Blob, DiffOptions, TreeWalkMode, TreeWalkResult,
+};
 use crate::utils::ErrorProcess;
 use tokio::sync::{MutexGuard, Notify};
-use crate::models::GitTreeItem;
+use crate::models::{GitTreeItem, FileHistoryItem, DiffLineItem, SearchResultItem, HandlerDeleteManifestResponse, TaskFileItem, TodoFileItem, TodoLineItem, DueItem};
 use tokio::task;
+use common::is_todo_name;
 
-use crate::git::GitBlob;
+use crate::git::{GitBlob, CommitAuthor};
+use super::content_index::{ContentIndex, split_phrase, search_lines, count_open_tasks, open_tasks, parse_due_date};
 
-#[derive(PartialEq, Eq, Debug)]
+const DIFF_CONTEXT_ALL: u32 = 1_000_000;
+const SEARCH_MAX_RESULTS: usize = 100;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
 pub struct GitId {
     pub id: Oid,
     is_file: bool,
@@ -51,6 +60,10 @@ impl GitId {
         }
     }
 
+    pub fn is_file(&self) -> bool {
+        self.is_file
+    }
+
     pub fn convert_to_string(self) -> String {
         self.id.to_string()
     }
@@ -299,8 +312,449 @@ fn command_find_blob<'repo>(
 }
 
 
+fn find_path_id(tree: &Tree, path: &[String]) -> Option<Oid> {
+    if path.is_empty() {
+        return Some(tree.id());
+    }
+
+    let path = path.join("/");
+    tree.get_path(Path::new(&path)).ok().map(|entry| entry.id())
+}
+
+//Filtr ścieżek widocznych dla użytkownika
+pub type PathFilter<'a> = &'a (dyn Fn(&[String]) -> bool + Sync);
+
+//Commit zmieniający katalog jest ukrywany, jeśli zmienia w nim choć jedną niewidoczną ścieżkę (np. jego opis może ją wymieniać)
+fn commit_visible(repo: &Repository, commit: &Commit, path: &[String], id: Oid, visible: PathFilter<'_>) -> Result<bool, ErrorProcess> {
+    //plik - uprawnienia sprawdzone są dla samej ścieżki
+    let Ok(tree) = repo.find_tree(id) else {
+        return Ok(true);
+    };
+
+    let mut parents = Vec::new();
+
+    for parent in commit.parents() {
+        parents.push(find_path_id(&parent.tree()?, path).and_then(|id| repo.find_tree(id).ok()));
+    }
+
+    if parents.is_empty() {
+        parents.push(None);
+    }
+
+    for parent in parents {
+        let diff = repo.diff_tree_to_tree(parent.as_ref(), Some(&tree), None)?;
+
+        for delta in diff.deltas() {
+            for file in [delta.old_file(), delta.new_file()] {
+                let Some(file_path) = file.path() else {
+                    continue;
+                };
+
+                let mut full_path = path.to_vec();
+                full_path.extend(file_path.iter().map(|item| item.to_string_lossy().to_string()));
+
+                if !visible(&full_path) {
+                    return Ok(false);
+                }
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+fn command_file_history<'repo>(
+    session: &GitSession<'repo>,
+    path: &[String],
+    visible: Option<PathFilter<'_>>,
+) -> Result<Vec<FileHistoryItem>, ErrorProcess> {
+    let branch = session.repo.find_branch(session.branch_name.as_str(), BranchType::Local)?;
+    let head = branch.get().peel_to_commit()?;
+
+    let mut revwalk = session.repo.revwalk()?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+    revwalk.push(head.id())?;
+
+    let mut list = Vec::new();
+
+    for commit_id in revwalk {
+        let commit = session.repo.find_commit(commit_id?)?;
+
+        let Some(id) = find_path_id(&commit.tree()?, path) else {
+            continue;
+        };
+
+        //commit scalający jest pomijany, jeśli plik jest taki sam jak w którymkolwiek z rodziców
+        let mut unchanged = false;
+
+        for parent in commit.parents() {
+            if find_path_id(&parent.tree()?, path) == Some(id) {
+                unchanged = true;
+            }
+        }
+
+        if unchanged {
+            continue;
+        }
+
+        if let Some(visible) = visible {
+            if !commit_visible(&session.repo, &commit, path, id, visible)? {
+                continue;
+            }
+        }
+
+        list.push(FileHistoryItem {
+            commit: commit.id().to_string(),
+            author: commit.author().name().unwrap_or("").to_string(),
+            timestamp: commit.time().seconds(),
+            message: commit.message().unwrap_or("").to_string(),
+            id: id.to_string(),
+        });
+    }
+
+    Ok(list)
+}
+
+fn command_find_in_commit<'repo>(
+    session: &GitSession<'repo>,
+    commit_id: &String,
+    path: &[String],
+) -> Result<GitId, ErrorProcess> {
+    let oid = create_id(commit_id)?;
+
+    let commit = match session.repo.find_commit(oid) {
+        Ok(commit) => commit,
+        Err(_) => {
+            return ErrorProcess::user_result(format!("Commit not found {commit_id}"));
+        }
+    };
+
+    let Some(id) = find_path_id(&commit.tree()?, path) else {
+        return ErrorProcess::user_result(format!("Element not found {} in commit {commit_id}", path.join("/")));
+    };
+
+    find_id(session, id)
+}
+
+//Element pod ścieżką w aktualnym stanie sesji (jeszcze nie zapisanym w commicie)
+fn command_find_path<'repo>(
+    session: &GitSession<'repo>,
+    path: &[String],
+) -> Result<Option<GitId>, ErrorProcess> {
+    let tree = session.repo.find_tree(session.root)?;
+
+    match find_path_id(&tree, path) {
+        Some(id) => Ok(Some(find_id(session, id)?)),
+        None => Ok(None),
+    }
+}
+
+//Podsumowanie elementu przed usunięciem - dla katalogu liczona jest cała zawartość
+fn command_manifest<'repo>(
+    session: &GitSession<'repo>,
+    child: &GitId,
+    name: &str,
+) -> Result<HandlerDeleteManifestResponse, ErrorProcess> {
+    let odb = session.repo.odb()?;
+
+    let mut manifest = HandlerDeleteManifestResponse {
+        hash: child.id.to_string(),
+        files: 0,
+        dirs: 0,
+        size: 0,
+        todo: 0,
+    };
+
+    if child.is_file {
+        let (size, _) = odb.read_header(child.id)?;
+        manifest.files = 1;
+        manifest.size = size as u64;
+        manifest.todo = u32::from(is_todo_name(name));
+        return Ok(manifest);
+    }
+
+    let tree = session.repo.find_tree(child.id)?;
+    let mut error = None;
+
+    tree.walk(TreeWalkMode::PreOrder, |_, entry| {
+        match entry.kind() {
+            Some(ObjectType::Tree) => {
+                manifest.dirs += 1;
+            },
+            Some(ObjectType::Blob) => {
+                match odb.read_header(entry.id()) {
+                    Ok((size, _)) => {
+                        manifest.size += size as u64;
+                    },
+                    Err(err) => {
+                        error = Some(err);
+                        return TreeWalkResult::Abort;
+                    }
+                }
+
+                manifest.files += 1;
+
+                if entry.name().map(is_todo_name).unwrap_or(false) {
+                    manifest.todo += 1;
+                }
+            },
+            _ => {}
+        }
+
+        TreeWalkResult::Ok
+    })?;
+
+    if let Some(error) = error {
+        return Err(error.into());
+    }
+
+    Ok(manifest)
+}
+
+fn command_search<'repo>(
+    session: &GitSession<'repo>,
+    index: &mut ContentIndex,
+    phrase: &String,
+) -> Result<Vec<SearchResultItem>, ErrorProcess> {
+    let phrase = split_phrase(phrase);
+
+    if phrase.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    index.update(&session.repo, session.root)?;
+
+    let mut result = Vec::new();
+
+    for (path, lines) in index.text_files() {
+        if result.len() >= SEARCH_MAX_RESULTS {
+            break;
+        }
+
+        if let Some(lines) = search_lines(lines, &phrase) {
+            result.push(SearchResultItem {
+                path: path.clone(),
+                lines,
+            });
+        }
+    }
+
+    Ok(result)
+}
+
+fn command_tasks<'repo>(
+    session: &GitSession<'repo>,
+    index: &mut ContentIndex,
+) -> Result<Vec<TaskFileItem>, ErrorProcess> {
+    index.update(&session.repo, session.root)?;
+
+    let mut result = Vec::new();
+
+    for (path, lines) in index.text_files() {
+        let open = count_open_tasks(lines);
+
+        if open > 0 {
+            result.push(TaskFileItem {
+                path: path.clone(),
+                open,
+            });
+        }
+    }
+
+    Ok(result)
+}
+
+//Pliki .todo oraz pliki z niezakończonymi zadaniami, razem z treścią zadań i czasem ostatniej zmiany
+fn command_todo_dashboard<'repo>(
+    session: &GitSession<'repo>,
+    index: &mut ContentIndex,
+) -> Result<Vec<TodoFileItem>, ErrorProcess> {
+    index.update(&session.repo, session.root)?;
+
+    let mut result = Vec::new();
+
+    for (path, lines) in index.text_files() {
+        let todo = path.last().map(|name| is_todo_name(name)).unwrap_or(false);
+
+        let tasks = open_tasks(lines)
+            .map(|(number, line)| TodoLineItem {
+                line: number as u32,
+                content: line.trim().to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        if todo || !tasks.is_empty() {
+            result.push(TodoFileItem {
+                path: path.clone(),
+                todo,
+                timestamp: 0,
+                tasks,
+            });
+        }
+    }
+
+    let branch = session.repo.find_branch(session.branch_name.as_str(), BranchType::Local)?;
+    let head = branch.get().peel_to_commit()?;
+
+    let paths = result.iter().map(|item| item.path.clone()).collect::<Vec<_>>();
+    let times = index.last_modified(&session.repo, head.id(), &paths)?;
+
+    for (item, timestamp) in result.iter_mut().zip(times) {
+        item.timestamp = timestamp;
+    }
+
+    Ok(result)
+}
+
+//Zadania i pliki .todo z terminem nie późniejszym niż limit, daty w formacie YYYY-MM-DD porównywane są jako tekst
+fn command_due<'repo>(
+    session: &GitSession<'repo>,
+    index: &mut ContentIndex,
+    today: &str,
+    limit: &str,
+) -> Result<Vec<DueItem>, ErrorProcess> {
+    index.update(&session.repo, session.root)?;
+
+    let mut result = Vec::new();
+
+    let mut push = |path: &Vec<String>, line: Option<u32>, content: &str, due: String| {
+        if due.as_str() <= limit {
+            result.push(DueItem {
+                path: path.clone(),
+                line,
+                content: content.trim().to_string(),
+                overdue: due.as_str() < today,
+                due,
+            });
+        }
+    };
+
+    for (path, lines) in index.text_files() {
+        if let Some(name) = path.last() {
+            if is_todo_name(name) {
+                if let Some(due) = parse_due_date(name) {
+                    push(path, None, name, due);
+                }
+            }
+        }
+
+        for (number, line) in open_tasks(lines) {
+            if let Some(due) = parse_due_date(line) {
+                push(path, Some(number as u32), line, due);
+            }
+        }
+    }
+
+    result.sort_by(|a, b| a.due.cmp(&b.due).then_with(|| a.path.cmp(&b.path)));
+    Ok(result)
+}
+
+fn find_blob_optional<'a, 'repo>(session: &'a GitSession<'repo>, id: &String) -> Result<Option<Blob<'a>>, ErrorProcess> {
+    if id.is_empty() {
+        return Ok(None);
+    }
+
+    let oid = create_id(id)?;
+
+    match session.repo.find_blob(oid) {
+        Ok(blob) => Ok(Some(blob)),
+        Err(_) => ErrorProcess::user_result(format!("Blob not found {id}")),
+    }
+}
+
+//Plik pod ścieżką w danym commicie, None - pusty commit lub plik jeszcze (albo już) nie istniał
+fn find_blob_in_commit<'a, 'repo>(
+    session: &'a GitSession<'repo>,
+    commit_id: &String,
+    path: &[String],
+) -> Result<Option<Blob<'a>>, ErrorProcess> {
+    if commit_id.is_empty() {
+        return Ok(None);
+    }
+
+    let oid = create_id(commit_id)?;
+
+    let commit = match session.repo.find_commit(oid) {
+        Ok(commit) => commit,
+        Err(_) => {
+            return ErrorProcess::user_result(format!("Commit not found {commit_id}"));
+        }
+    };
+
+    let Some(id) = find_path_id(&commit.tree()?, path) else {
+        return Ok(None);
+    };
+
+    match session.repo.find_blob(id) {
+        Ok(blob) => Ok(Some(blob)),
+        Err(_) => ErrorProcess::user_result(format!("Element is not a file {} in commit {commit_id}", path.join("/"))),
+    }
+}
+
+fn command_diff<'repo>(
+    session: &GitSession<'repo>,
+    prev_id: &String,
+    next_id: &String,
+) -> Result<Vec<DiffLineItem>, ErrorProcess> {
+    let prev = find_blob_optional(session, prev_id)?;
+    let next = find_blob_optional(session, next_id)?;
+    diff_blobs(session, prev.as_ref(), next.as_ref())
+}
+
+fn command_diff_commits<'repo>(
+    session: &GitSession<'repo>,
+    path: &[String],
+    prev_commit: &String,
+    next_commit: &String,
+) -> Result<Vec<DiffLineItem>, ErrorProcess> {
+    let prev = find_blob_in_commit(session, prev_commit, path)?;
+    let next = find_blob_in_commit(session, next_commit, path)?;
+    diff_blobs(session, prev.as_ref(), next.as_ref())
+}
+
+fn diff_blobs<'repo>(
+    session: &GitSession<'repo>,
+    prev: Option<&Blob>,
+    next: Option<&Blob>,
+) -> Result<Vec<DiffLineItem>, ErrorProcess> {
+    let mut options = DiffOptions::new();
+    //cała zawartość pliku, a nie tylko otoczenie zmian
+    options.context_lines(DIFF_CONTEXT_ALL);
+
+    let mut list = Vec::new();
+
+    session.repo.diff_blobs(
+        prev,
+        None,
+        next,
+        None,
+        Some(&mut options),
+        None,
+        None,
+        None,
+        Some(&mut |_delta, _hunk, line| {
+            let origin = line.origin();
+
+            if origin == '+' || origin == '-' || origin == ' ' {
+                let content = String::from_utf8_lossy(line.content());
+                let content = content.trim_end_matches('\n').trim_end_matches('\r');
+
+                list.push(DiffLineItem {
+                    origin: origin.to_string(),
+                    content: content.to_string(),
+                });
+            }
+
+            true
+        }),
+    )?;
+
+    Ok(list)
+}
+
 pub fn commit<'repo>(
     session: GitSession<'repo>,
+    author: Option<CommitAuthor>,
     message: String,
 ) -> Result<String, ErrorProcess> {
     let new_tree = find_tree(&session, session.root)?;
@@ -313,10 +767,14 @@ pub fn commit<'repo>(
     let update_ref = format!("refs/heads/{}", session.branch_name);
     //HEAD
 
-    let signatire = Signature::now(
-        commit.author().name().unwrap(),
-        commit.author().email().unwrap()
-    )?;
+    //bez zalogowanego użytkownika autorem zostaje autor poprzedniego commita
+    let signatire = match &author {
+        Some(author) => Signature::now(&author.name, &author.email)?,
+        None => Signature::now(
+            commit.author().name().unwrap(),
+            commit.author().email().unwrap()
+        )?,
+    };
 
     session.repo.commit(
         Some(update_ref.as_str()),   //"heads/master"),
@@ -381,9 +839,9 @@ impl<'repo> GitSession<'repo> {
         })
     }
 
-    pub async fn commit(self, message: String) -> Result<String, ErrorProcess> {
+    pub async fn commit(self, author: Option<CommitAuthor>, message: String) -> Result<String, ErrorProcess> {
         task::block_in_place(move || {
-            commit(self, message)
+            commit(self, author, message)
         })
     }
 
@@ -420,6 +878,76 @@ impl<'repo> GitSession<'repo> {
         })
     }
 
+    pub async fn file_history(self, path: &[String], visible: Option<PathFilter<'_>>) -> Result<(GitSession<'repo>, Vec<FileHistoryItem>), ErrorProcess> {
+        task::block_in_place(move || {
+            let list = command_file_history(&self, path, visible)?;
+            Ok((self, list))
+        })
+    }
+
+    pub async fn manifest(self, child: &GitId, name: &str) -> Result<(GitSession<'repo>, HandlerDeleteManifestResponse), ErrorProcess> {
+        task::block_in_place(move || {
+            let manifest = command_manifest(&self, child, name)?;
+            Ok((self, manifest))
+        })
+    }
+
+    pub async fn find_in_commit(self, commit_id: &String, path: &[String]) -> Result<(GitSession<'repo>, GitId), ErrorProcess> {
+        task::block_in_place(move || {
+            let id = command_find_in_commit(&self, commit_id, path)?;
+            Ok((self, id))
+        })
+    }
+
+    pub async fn find_path(self, path: &[String]) -> Result<(GitSession<'repo>, Option<GitId>), ErrorProcess> {
+        task::block_in_place(move || {
+            let id = command_find_path(&self, path)?;
+            Ok((self, id))
+        })
+    }
+
+    pub async fn search(self, index: &mut ContentIndex, phrase: &String) -> Result<(GitSession<'repo>, Vec<SearchResultItem>), ErrorProcess> {
+        task::block_in_place(move || {
+            let list = command_search(&self, index, phrase)?;
+            Ok((self, list))
+        })
+    }
+
+    pub async fn tasks(self, index: &mut ContentIndex) -> Result<(GitSession<'repo>, Vec<TaskFileItem>), ErrorProcess> {
+        task::block_in_place(move || {
+            let list = command_tasks(&self, index)?;
+            Ok((self, list))
+        })
+    }
+
+    pub async fn todo_dashboard(self, index: &mut ContentIndex) -> Result<(GitSession<'repo>, Vec<TodoFileItem>), ErrorProcess> {
+        task::block_in_place(move || {
+            let list = command_todo_dashboard(&self, index)?;
+            Ok((self, list))
+        })
+    }
+
+    pub async fn due(self, index: &mut ContentIndex, today: &str, limit: &str) -> Result<(GitSession<'repo>, Vec<DueItem>), ErrorProcess> {
+        task::block_in_place(move || {
+            let list = command_due(&self, index, today, limit)?;
+            Ok((self, list))
+        })
+    }
+
+    pub async fn diff(self, prev_id: &String, next_id: &String) -> Result<(GitSession<'repo>, Vec<DiffLineItem>), ErrorProcess> {
+        task::block_in_place(move || {
+            let list = command_diff(&self, prev_id, next_id)?;
+            Ok((self, list))
+        })
+    }
+
+    pub async fn diff_commits(self, path: &[String], prev_commit: &String, next_commit: &String) -> Result<(GitSession<'repo>, Vec<DiffLineItem>), ErrorProcess> {
+        task::block_in_place(move || {
+            let list = command_diff_commits(&self, path, prev_commit, next_commit)?;
+            Ok((self, list))
+        })
+    }
+
     pub async fn create_file_content(self, content: &String) -> Result<(GitSession<'repo>, GitId), ErrorProcess> {
         task::block_in_place(move || {
             let new_content_id = create_file_content(&self, content)?;
@@ -511,4 +1039,120 @@ impl<'repo> GitSession<'repo> {
     }
 }
 
+#[cfg(test)]
+fn test_history(repo: &tokio::sync::Mutex<Repository>, path: &str) -> Vec<String> {
+    use super::test_utils::{test_session, test_path};
+
+    let session = test_session(repo);
+    let list = command_file_history(&session, &test_path(path), None).unwrap();
+    list.into_iter().map(|item| item.message).collect()
+}
+
+#[test]
+fn test_file_history_rename_delete() {
+    use super::test_utils::{test_repo, test_change};
+
+    let repo = test_repo("history");
+    test_change(&repo, "start", &[("a.txt", Some("1")), ("dir/b.txt", Some("1"))]);
+    test_change(&repo, "edit a", &[("a.txt", Some("2"))]);
+    test_change(&repo, "edit b", &[("dir/b.txt", Some("2"))]);
+    test_change(&repo, "rename a", &[("a.txt", None), ("c.txt", Some("2"))]);
+    test_change(&repo, "delete b", &[("dir/b.txt", None)]);
+    test_change(&repo, "restore b", &[("dir/b.txt", Some("2"))]);
+
+    let repo = tokio::sync::Mutex::new(repo);
+
+    //usunięcie nie jest wersją pliku, po zmianie nazwy historia zaczyna się od nowa
+    assert_eq!(test_history(&repo, "a.txt"), vec!["edit a", "start"]);
+    assert_eq!(test_history(&repo, "c.txt"), vec!["rename a"]);
+    assert_eq!(test_history(&repo, "dir/b.txt"), vec!["restore b", "edit b", "start"]);
+    assert_eq!(test_history(&repo, "dir"), vec!["restore b", "edit b", "start"]);
+}
+
+#[test]
+fn test_file_history_hidden() {
+    use super::test_utils::{test_repo, test_change, test_session, test_path};
+
+    let repo = test_repo("history-hidden");
+    test_change(&repo, "start", &[("a.txt", Some("1"))]);
+    test_change(&repo, "private b", &[("private/b.txt", Some("1"))]);
+    test_change(&repo, "edit a", &[("a.txt", Some("2"))]);
+    test_change(&repo, "move b", &[("private/b.txt", None), ("c.txt", Some("1"))]);
+
+    let repo = tokio::sync::Mutex::new(repo);
+    let session = test_session(&repo);
+    let visible = |path: &[String]| path.first().map(String::as_str) != Some("private");
+
+    let list = command_file_history(&session, &test_path(""), Some(&visible)).unwrap();
+    let list = list.into_iter().map(|item| item.message).collect::<Vec<_>>();
+    assert_eq!(list, vec!["edit a", "start"]);
+
+    let list = command_file_history(&session, &test_path("a.txt"), Some(&visible)).unwrap();
+    assert_eq!(list.len(), 2);
+}
+
+#[test]
+fn test_file_history_merge() {
+    use super::test_utils::{test_repo, test_change, test_commit_tree, test_set_master};
+
+    let repo = test_repo("history-merge");
+    let start = test_change(&repo, "start", &[("a.txt", Some("1")), ("b.txt", Some("1"))]);
+    let side = test_commit_tree(&repo, "side", &[start], &[("a.txt", Some("2"))]);
+    let main = test_commit_tree(&repo, "main", &[start], &[("b.txt", Some("2"))]);
+    let merge = test_commit_tree(&repo, "merge", &[main, side], &[("a.txt", Some("2"))]);
+    test_set_master(&repo, merge);
+
+    let repo = tokio::sync::Mutex::new(repo);
+
+    assert_eq!(test_history(&repo, "a.txt"), vec!["side", "start"]);
+    assert_eq!(test_history(&repo, "b.txt"), vec!["main", "start"]);
+}
+
+#[test]
+fn test_diff_commits() {
+    use super::test_utils::{test_repo, test_change, test_session, test_path};
+
+    let repo = test_repo("diff-commits");
+    let first = test_change(&repo, "first", &[("a.txt", Some("1\n2\n"))]);
+    let second = test_change(&repo, "second", &[("a.txt", Some("1\n3\n"))]);
+    let third = test_change(&repo, "third", &[("a.txt", None)]);
+
+    let repo = tokio::sync::Mutex::new(repo);
+    let session = test_session(&repo);
+    let path = test_path("a.txt");
+
+    let origins = |list: Vec<DiffLineItem>| list
+        .into_iter()
+        .map(|item| format!("{}{}", item.origin, item.content))
+        .collect::<Vec<_>>();
+
+    let list = command_diff_commits(&session, &path, &first.to_string(), &second.to_string()).unwrap();
+    assert_eq!(origins(list), vec!(" 1", "-2", "+3"));
+
+    let list = command_diff_commits(&session, &path, &String::from(""), &first.to_string()).unwrap();
+    assert_eq!(origins(list), vec!("+1", "+2"));
 
+    //w ostatnim commicie pliku już nie ma
+    let list = command_diff_commits(&session, &path, &second.to_string(), &third.to_string()).unwrap();
+    assert_eq!(origins(list), vec!("-1", "-3"));
+}
+
+#[test]
+fn test_manifest() {
+    use super::test_utils::{test_repo, test_change, test_session, test_path};
+
+    let repo = test_repo("manifest");
+    test_change(&repo, "first", &[("dir/a.txt", Some("12345")), ("dir/sub/b.todo", Some("[ ] x")), ("c.todo", Some("1"))]);
+
+    let repo = tokio::sync::Mutex::new(repo);
+    let session = test_session(&repo);
+
+    let dir = command_find_path(&session, &test_path("dir")).unwrap().unwrap();
+    let manifest = command_manifest(&session, &dir, "dir").unwrap();
+    assert_eq!(manifest.hash, dir.id.to_string());
+    assert_eq!((manifest.files, manifest.dirs, manifest.size, manifest.todo), (2, 1, 10, 1));
+
+    let file = command_find_path(&session, &test_path("c.todo")).unwrap().unwrap();
+    let manifest = command_manifest(&session, &file, "c.todo").unwrap();
+    assert_eq!((manifest.files, manifest.dirs, manifest.size, manifest.todo), (1, 0, 1, 1));
+}
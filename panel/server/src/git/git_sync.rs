@@ -0,0 +1,355 @@
+use git2::{
+    BranchType, Cred, CredentialType, FetchOptions, Oid, PushOptions, RemoteCallbacks, Repository,
+    Signature, Sort,
+};
+use crate::utils::ErrorProcess;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncStatus {
+    InSync,
+    Ahead,          //lokalne commity nie zostały wysłane
+    Behind,         //zmiany z serwera nie zostały pobrane
+    Diverged,       //obie strony mają nowe commity, nie udało się ich połączyć
+    Conflict,       //rebase i merge zakończyły się konfliktem
+    Offline,        //brak połączenia ze zdalnym repozytorium
+    Error,          //błąd lokalnego repozytorium podczas integracji
+}
+
+impl SyncStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SyncStatus::InSync => "InSync",
+            SyncStatus::Ahead => "Ahead",
+            SyncStatus::Behind => "Behind",
+            SyncStatus::Diverged => "Diverged",
+            SyncStatus::Conflict => "Conflict",
+            SyncStatus::Offline => "Offline",
+            SyncStatus::Error => "Error",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncResult {
+    pub status: SyncStatus,
+    pub ahead: usize,
+    pub behind: usize,
+    pub error: Option<String>,
+}
+
+impl SyncResult {
+    pub fn new(repo: &Repository, remote_name: &str, branch: &str, status: SyncStatus, error: Option<ErrorProcess>) -> SyncResult {
+        let (ahead, behind) = ahead_behind(repo, remote_name, branch).unwrap_or((0, 0));
+
+        SyncResult {
+            status,
+            ahead,
+            behind,
+            error: error.map(|error| error.to_string().1),
+        }
+    }
+}
+
+fn remote_callbacks<'a>() -> RemoteCallbacks<'a> {
+    let mut callbacks = RemoteCallbacks::new();
+
+    callbacks.credentials(|_url, username, allowed| {
+        if allowed.contains(CredentialType::SSH_KEY) {
+            return Cred::ssh_key_from_agent(username.unwrap_or("git"));
+        }
+
+        Cred::default()
+    });
+
+    callbacks
+}
+
+fn branch_id(repo: &Repository, name: &str, branch_type: BranchType) -> Result<Option<Oid>, ErrorProcess> {
+    let branch = match repo.find_branch(name, branch_type) {
+        Ok(branch) => branch,
+        Err(_) => {
+            return Ok(None);
+        }
+    };
+
+    let commit = branch.get().peel_to_commit()?;
+    Ok(Some(commit.id()))
+}
+
+fn remote_branch_name(remote_name: &str, branch: &str) -> String {
+    format!("{remote_name}/{branch}")
+}
+
+fn set_branch(repo: &Repository, branch: &str, id: Oid, message: &str) -> Result<(), ErrorProcess> {
+    repo.reference(format!("refs/heads/{branch}").as_str(), id, true, message)?;
+    repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))?;
+    Ok(())
+}
+
+pub fn fetch(repo: &Repository, remote_name: &str, branch: &str) -> Result<(), ErrorProcess> {
+    let mut remote = repo.find_remote(remote_name)?;
+
+    let mut options = FetchOptions::new();
+    options.remote_callbacks(remote_callbacks());
+
+    let refspec = format!("+refs/heads/{branch}:refs/remotes/{remote_name}/{branch}");
+    remote.fetch(&[refspec.as_str()], Some(&mut options), None)?;
+
+    Ok(())
+}
+
+pub fn push(repo: &Repository, remote_name: &str, branch: &str) -> Result<(), ErrorProcess> {
+    let mut remote = repo.find_remote(remote_name)?;
+    let mut rejected = None;
+
+    {
+        let mut callbacks = remote_callbacks();
+        callbacks.push_update_reference(|refname, status| {
+            if let Some(status) = status {
+                rejected = Some(format!("{refname} {status}"));
+            }
+            Ok(())
+        });
+
+        let mut options = PushOptions::new();
+        options.remote_callbacks(callbacks);
+
+        let refspec = format!("refs/heads/{branch}:refs/heads/{branch}");
+        remote.push(&[refspec.as_str()], Some(&mut options))?;
+    }
+
+    if let Some(rejected) = rejected {
+        return ErrorProcess::server_result(format!("push rejected {rejected}"));
+    }
+
+    Ok(())
+}
+
+//liczba commitów (lokalnie nowych, zdalnie nowych) względem gałęzi zdalnej
+pub fn ahead_behind(repo: &Repository, remote_name: &str, branch: &str) -> Result<(usize, usize), ErrorProcess> {
+    let Some(local) = branch_id(repo, branch, BranchType::Local)? else {
+        return ErrorProcess::server_result(format!("missing local branch {branch}"));
+    };
+
+    let Some(remote) = branch_id(repo, &remote_branch_name(remote_name, branch), BranchType::Remote)? else {
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(local)?;
+        return Ok((revwalk.count(), 0));
+    };
+
+    Ok(repo.graph_ahead_behind(local, remote)?)
+}
+
+pub fn status(repo: &Repository, remote_name: &str, branch: &str) -> Result<SyncStatus, ErrorProcess> {
+    let status = match ahead_behind(repo, remote_name, branch)? {
+        (0, 0) => SyncStatus::InSync,
+        (_, 0) => SyncStatus::Ahead,
+        (0, _) => SyncStatus::Behind,
+        (_, _) => SyncStatus::Diverged,
+    };
+
+    Ok(status)
+}
+
+//przenosi lokalne commity na koniec gałęzi zdalnej, None - wystąpił konflikt
+fn rebase(repo: &Repository, local: Oid, remote: Oid) -> Result<Option<Oid>, ErrorProcess> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+    revwalk.push(local)?;
+    revwalk.hide(remote)?;
+
+    let mut onto = repo.find_commit(remote)?;
+
+    for commit_id in revwalk {
+        let commit = repo.find_commit(commit_id?)?;
+
+        if commit.parent_count() > 1 {
+            return Ok(None);
+        }
+
+        let mut index = repo.cherrypick_commit(&commit, &onto, 0, None)?;
+
+        if index.has_conflicts() {
+            return Ok(None);
+        }
+
+        let tree_id = index.write_tree_to(repo)?;
+        let tree = repo.find_tree(tree_id)?;
+
+        let new_id = repo.commit(
+            None,
+            &commit.author(),
+            &commit.committer(),
+            commit.message().unwrap_or(""),
+            &tree,
+            &[&onto],
+        )?;
+
+        onto = repo.find_commit(new_id)?;
+    }
+
+    Ok(Some(onto.id()))
+}
+
+//commit scalający, None - wystąpił konflikt
+fn merge(repo: &Repository, local: Oid, remote: Oid, remote_name: &str, branch: &str) -> Result<Option<Oid>, ErrorProcess> {
+    let local_commit = repo.find_commit(local)?;
+    let remote_commit = repo.find_commit(remote)?;
+
+    let mut index = repo.merge_commits(&local_commit, &remote_commit, None)?;
+
+    if index.has_conflicts() {
+        return Ok(None);
+    }
+
+    let tree_id = index.write_tree_to(repo)?;
+    let tree = repo.find_tree(tree_id)?;
+
+    let author = local_commit.author();
+    let signature = Signature::now(
+        author.name().unwrap_or(""),
+        author.email().unwrap_or("")
+    )?;
+
+    let message = format!("Merge {}", remote_branch_name(remote_name, branch));
+    let new_id = repo.commit(None, &signature, &signature, message.as_str(), &tree, &[&local_commit, &remote_commit])?;
+
+    Ok(Some(new_id))
+}
+
+//łączy gałąź lokalną ze zdalną: fast-forward, a gdy obie strony mają zmiany - rebase lub merge
+pub fn integrate(repo: &Repository, remote_name: &str, branch: &str) -> Result<SyncStatus, ErrorProcess> {
+    let current = status(repo, remote_name, branch)?;
+
+    if current != SyncStatus::Behind && current != SyncStatus::Diverged {
+        return Ok(current);
+    }
+
+    let Some(local) = branch_id(repo, branch, BranchType::Local)? else {
+        return ErrorProcess::server_result(format!("missing local branch {branch}"));
+    };
+
+    let Some(remote) = branch_id(repo, &remote_branch_name(remote_name, branch), BranchType::Remote)? else {
+        return ErrorProcess::server_result(format!("missing remote branch {}", remote_branch_name(remote_name, branch)));
+    };
+
+    if current == SyncStatus::Behind {
+        set_branch(repo, branch, remote, "sync: fast-forward")?;
+        return status(repo, remote_name, branch);
+    }
+
+    if let Some(new_id) = rebase(repo, local, remote)? {
+        set_branch(repo, branch, new_id, "sync: rebase")?;
+        return status(repo, remote_name, branch);
+    }
+
+    if let Some(new_id) = merge(repo, local, remote, remote_name, branch)? {
+        set_branch(repo, branch, new_id, "sync: merge")?;
+        return status(repo, remote_name, branch);
+    }
+
+    Ok(SyncStatus::Conflict)
+}
+
+//zdalne repozytorium (bare) oraz dwie kopie robocze połączone z nim jako "origin"
+#[cfg(test)]
+async fn test_setup(name: &str) -> ((Repository, super::Git), (Repository, super::Git)) {
+    use super::test_utils::{test_dir, test_repo, test_change, test_git};
+
+    let remote_path = test_dir(name).join("remote.git");
+
+    let mut options = git2::RepositoryInitOptions::new();
+    options.bare(true).initial_head("master");
+    Repository::init_opts(&remote_path, &options).unwrap();
+
+    let first = test_repo(name);
+    first.remote("origin", remote_path.to_str().unwrap()).unwrap();
+    test_change(&first, "start", &[("start", Some("start"))]);
+    let first_git = test_git(&first);
+    assert_eq!(first_git.sync("origin").await.status, SyncStatus::InSync);
+
+    let second = Repository::clone(remote_path.to_str().unwrap(), test_dir(name).join("second")).unwrap();
+    let second_git = test_git(&second);
+
+    ((first, first_git), (second, second_git))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sync_fast_forward() {
+    use super::test_utils::{test_change, test_read};
+
+    let ((first, first_git), (second, second_git)) = test_setup("ff").await;
+
+    test_change(&first, "a", &[("a", Some("first"))]);
+    assert_eq!(status(&first, "origin", "master").unwrap(), SyncStatus::Ahead);
+    assert_eq!(first_git.sync("origin").await.status, SyncStatus::InSync);
+
+    assert_eq!(second_git.sync("origin").await.status, SyncStatus::InSync);
+    assert_eq!(test_read(&second, "a"), Some("first".to_string()));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sync_rebase() {
+    use super::test_utils::{test_change, test_read};
+
+    let ((first, first_git), (second, second_git)) = test_setup("rebase").await;
+
+    test_change(&first, "a", &[("a", Some("first"))]);
+    assert_eq!(first_git.sync("origin").await.status, SyncStatus::InSync);
+
+    test_change(&second, "b", &[("b", Some("second"))]);
+    assert_eq!(second_git.sync("origin").await.status, SyncStatus::InSync);
+    assert_eq!(test_read(&second, "a"), Some("first".to_string()));
+    assert_eq!(test_read(&second, "b"), Some("second".to_string()));
+
+    let head = second.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.parent_count(), 1);
+
+    assert_eq!(first_git.sync("origin").await.status, SyncStatus::InSync);
+    assert_eq!(test_read(&first, "b"), Some("second".to_string()));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sync_conflict() {
+    use super::test_utils::{test_change, test_read};
+
+    let ((first, first_git), (second, second_git)) = test_setup("conflict").await;
+
+    test_change(&first, "a", &[("a", Some("first"))]);
+    assert_eq!(first_git.sync("origin").await.status, SyncStatus::InSync);
+
+    test_change(&second, "a", &[("a", Some("second"))]);
+
+    let result = second_git.sync("origin").await;
+    assert_eq!(result.status, SyncStatus::Conflict);
+    assert_eq!((result.ahead, result.behind), (1, 1));
+    assert_eq!(test_read(&second, "a"), Some("second".to_string()));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sync_offline() {
+    use super::test_utils::test_change;
+
+    let ((first, first_git), _) = test_setup("offline").await;
+
+    first.remote_set_url("origin", "/nonexistent/notatki-remote.git").unwrap();
+
+    test_change(&first, "a", &[("a", Some("first"))]);
+
+    let result = first_git.sync("origin").await;
+    assert_eq!(result.status, SyncStatus::Offline);
+    assert!(result.error.is_some());
+    assert_eq!(status(&first, "origin", "master").unwrap(), SyncStatus::Ahead);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sync_error() {
+    let ((first, first_git), _) = test_setup("error").await;
+
+    //brak lokalnej gałęzi - integracja kończy się błędem, a nie stanem Diverged
+    first.find_reference("refs/heads/master").unwrap().delete().unwrap();
+
+    let result = first_git.sync("origin").await;
+    assert_eq!(result.status, SyncStatus::Error);
+    assert!(result.error.is_some());
+}
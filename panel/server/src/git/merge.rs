@@ -0,0 +1,314 @@
+use crate::models::MergeChunk;
+
+pub enum MergeResult {
+    Clean(String),
+    Conflict(Vec<MergeChunk>),
+}
+
+//długości najdłuższego wspólnego podciągu `a` i każdego prefiksu `b`, pamięć O(len(b))
+fn lcs_row(a: &[&str], b: &[&str]) -> Vec<usize> {
+    let mut row = vec![0usize; b.len() + 1];
+
+    for line in a {
+        let mut diagonal = 0;
+
+        for (j, other) in b.iter().enumerate() {
+            let next = if line == other {
+                diagonal + 1
+            } else {
+                row[j + 1].max(row[j])
+            };
+
+            diagonal = row[j + 1];
+            row[j + 1] = next;
+        }
+    }
+
+    row
+}
+
+//algorytm Hirschberga - najdłuższy wspólny podciąg w pamięci liniowej
+fn match_range(base: &[&str], other: &[&str], offset_base: usize, offset_other: usize, result: &mut [Option<usize>]) {
+    if base.is_empty() || other.is_empty() {
+        return;
+    }
+
+    if base.len() == 1 {
+        if let Some(j) = other.iter().position(|line| *line == base[0]) {
+            result[offset_base] = Some(offset_other + j);
+        }
+        return;
+    }
+
+    let middle = base.len() / 2;
+    let (base_top, base_bottom) = base.split_at(middle);
+
+    let forward = lcs_row(base_top, other);
+
+    let base_bottom_rev = base_bottom.iter().rev().copied().collect::<Vec<_>>();
+    let other_rev = other.iter().rev().copied().collect::<Vec<_>>();
+    let backward = lcs_row(&base_bottom_rev, &other_rev);
+
+    let mut split = 0;
+    for k in 0..=other.len() {
+        if forward[k] + backward[other.len() - k] > forward[split] + backward[other.len() - split] {
+            split = k;
+        }
+    }
+
+    let (other_top, other_bottom) = other.split_at(split);
+    match_range(base_top, other_top, offset_base, offset_other, result);
+    match_range(base_bottom, other_bottom, offset_base + middle, offset_other + split, result);
+}
+
+//dla każdej linii z `base` indeks odpowiadającej jej linii z `other` (najdłuższy wspólny podciąg)
+fn match_lines(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let mut result = vec![None; base.len()];
+
+    //wspólny początek i koniec nie wymagają liczenia
+    let prefix = base.iter().zip(other.iter()).take_while(|(a, b)| a == b).count();
+    let suffix = base[prefix..].iter().rev().zip(other[prefix..].iter().rev()).take_while(|(a, b)| a == b).count();
+
+    for (i, item) in result.iter_mut().enumerate().take(prefix) {
+        *item = Some(i);
+    }
+
+    for i in 0..suffix {
+        result[base.len() - 1 - i] = Some(other.len() - 1 - i);
+    }
+
+    match_range(
+        &base[prefix..base.len() - suffix],
+        &other[prefix..other.len() - suffix],
+        prefix,
+        prefix,
+        &mut result,
+    );
+
+    result
+}
+
+fn to_vec(lines: &[&str]) -> Vec<String> {
+    lines.iter().map(|line| line.to_string()).collect()
+}
+
+fn push_lines(chunks: &mut Vec<MergeChunk>, lines: &[&str]) {
+    if lines.is_empty() {
+        return;
+    }
+
+    if let Some(MergeChunk { conflict: false, lines: last, .. }) = chunks.last_mut() {
+        last.extend(lines.iter().map(|line| line.to_string()));
+        return;
+    }
+
+    chunks.push(MergeChunk {
+        conflict: false,
+        lines: to_vec(lines),
+        current: Vec::new(),
+        edited: Vec::new(),
+    });
+}
+
+fn push_unstable(chunks: &mut Vec<MergeChunk>, base: &[&str], current: &[&str], edited: &[&str]) {
+    if current == base {
+        push_lines(chunks, edited);
+    } else if edited == base || edited == current {
+        push_lines(chunks, current);
+    } else {
+        chunks.push(MergeChunk {
+            conflict: true,
+            lines: Vec::new(),
+            current: to_vec(current),
+            edited: to_vec(edited),
+        });
+    }
+}
+
+//Scalanie trójstronne (diff3) na poziomie linii.
+//base - wersja od której zaczęła się edycja, current - aktualna wersja w repozytorium, edited - wersja użytkownika
+pub fn merge3(base: &str, current: &str, edited: &str) -> MergeResult {
+    let base = base.split('\n').collect::<Vec<_>>();
+    let current = current.split('\n').collect::<Vec<_>>();
+    let edited = edited.split('\n').collect::<Vec<_>>();
+
+    let match_current = match_lines(&base, &current);
+    let match_edited = match_lines(&base, &edited);
+
+    let mut chunks = Vec::new();
+    let (mut i_base, mut i_current, mut i_edited) = (0, 0, 0);
+
+    loop {
+        let mut stable = 0;
+        while i_base + stable < base.len()
+            && match_current[i_base + stable] == Some(i_current + stable)
+            && match_edited[i_base + stable] == Some(i_edited + stable)
+        {
+            stable += 1;
+        }
+
+        if stable > 0 {
+            push_lines(&mut chunks, &base[i_base..i_base + stable]);
+            i_base += stable;
+            i_current += stable;
+            i_edited += stable;
+            continue;
+        }
+
+        let next = (i_base..base.len()).find_map(|index| {
+            match (match_current[index], match_edited[index]) {
+                (Some(pos_current), Some(pos_edited)) => Some((index, pos_current, pos_edited)),
+                _ => None,
+            }
+        });
+
+        match next {
+            Some((next_base, next_current, next_edited)) => {
+                push_unstable(
+                    &mut chunks,
+                    &base[i_base..next_base],
+                    &current[i_current..next_current],
+                    &edited[i_edited..next_edited],
+                );
+                i_base = next_base;
+                i_current = next_current;
+                i_edited = next_edited;
+            },
+            None => {
+                push_unstable(
+                    &mut chunks,
+                    &base[i_base..],
+                    &current[i_current..],
+                    &edited[i_edited..],
+                );
+                break;
+            }
+        }
+    }
+
+    if chunks.iter().any(|chunk| chunk.conflict) {
+        return MergeResult::Conflict(chunks);
+    }
+
+    let lines = chunks
+        .into_iter()
+        .flat_map(|chunk| chunk.lines)
+        .collect::<Vec<String>>();
+
+    MergeResult::Clean(lines.join("\n"))
+}
+
+#[cfg(test)]
+fn test_merge(base: &[&str], current: &[&str], edited: &[&str]) -> MergeResult {
+    merge3(&base.join("\n"), &current.join("\n"), &edited.join("\n"))
+}
+
+#[test]
+fn test_match_lines_is_longest() {
+    //referencyjna długość najdłuższego wspólnego podciągu
+    fn lcs_len(a: &[&str], b: &[&str]) -> usize {
+        *lcs_row(a, b).last().unwrap()
+    }
+
+    let alphabet = ["a", "b", "c", "d"];
+    let mut seed = 7u32;
+    let mut next = move |limit: usize| {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        (seed >> 16) as usize % limit
+    };
+
+    for _ in 0..200 {
+        let base = (0..next(12)).map(|_| alphabet[next(4)]).collect::<Vec<_>>();
+        let other = (0..next(12)).map(|_| alphabet[next(4)]).collect::<Vec<_>>();
+
+        let result = match_lines(&base, &other);
+        let pairs = result
+            .iter()
+            .enumerate()
+            .filter_map(|(i, j)| j.map(|j| (i, j)))
+            .collect::<Vec<_>>();
+
+        for (i, j) in pairs.iter() {
+            assert_eq!(base[*i], other[*j]);
+        }
+
+        for window in pairs.windows(2) {
+            assert!(window[0].1 < window[1].1);
+        }
+
+        assert_eq!(pairs.len(), lcs_len(&base, &other), "{base:?} {other:?}");
+    }
+}
+
+#[test]
+fn test_merge3_clean() {
+    let base = ["a", "b", "c", "d", "e"];
+    let current = ["a", "B", "c", "d", "e"];
+    let edited = ["a", "b", "c", "D", "e"];
+
+    let MergeResult::Clean(result) = test_merge(&base, &current, &edited) else {
+        panic!("expected clean merge");
+    };
+
+    assert_eq!(result, "a\nB\nc\nD\ne");
+}
+
+#[test]
+fn test_merge3_same_change() {
+    let base = ["a", "b", "c"];
+    let changed = ["a", "X", "c"];
+
+    let MergeResult::Clean(result) = test_merge(&base, &changed, &changed) else {
+        panic!("expected clean merge");
+    };
+
+    assert_eq!(result, "a\nX\nc");
+}
+
+#[test]
+fn test_merge3_conflict() {
+    let base = ["a", "b", "c"];
+    let current = ["a", "X", "c"];
+    let edited = ["a", "Y", "Z", "c"];
+
+    let MergeResult::Conflict(chunks) = test_merge(&base, &current, &edited) else {
+        panic!("expected conflict");
+    };
+
+    let line = |lines: &[&str]| MergeChunk {
+        conflict: false,
+        lines: to_vec(lines),
+        current: Vec::new(),
+        edited: Vec::new(),
+    };
+
+    assert_eq!(chunks, vec!(
+        line(&["a"]),
+        MergeChunk {
+            conflict: true,
+            lines: Vec::new(),
+            current: to_vec(&["X"]),
+            edited: to_vec(&["Y", "Z"]),
+        },
+        line(&["c"]),
+    ));
+}
+
+#[test]
+fn test_merge3_insert_both_ends() {
+    let base = ["a", "b"];
+    let current = ["start", "a", "b"];
+    let edited = ["a", "b", "end"];
+
+    let MergeResult::Clean(result) = test_merge(&base, &current, &edited) else {
+        panic!("expected clean merge");
+    };
+
+    assert_eq!(result, "start\na\nb\nend");
+
+    //obie strony dopisują na końcu - konflikt
+    let current = ["a", "b", "x"];
+    let edited = ["a", "b", "y"];
+
+    assert!(matches!(test_merge(&base, &current, &edited), MergeResult::Conflict(_)));
+}
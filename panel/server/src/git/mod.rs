@@ -1,6 +1,15 @@
 mod git;
 mod git_session;
 mod models;
+mod content_index;
+mod merge;
+mod git_sync;
+mod git_operations;
+mod trash;
+#[cfg(test)]
+mod test_utils;
 
-pub use models::GitBlob;
+pub use models::{GitBlob, CommitAuthor};
 pub use git::Git;
+pub use git_session::PathFilter;
+pub use git_sync::{SyncStatus, SyncResult};
@@ -11,3 +11,10 @@ pub enum GitBlob {
         list: Vec<GitTreeItem>,
     }
 }
+
+//Autor zmian - zalogowany użytkownik
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitAuthor {
+    pub name: String,
+    pub email: String,
+}
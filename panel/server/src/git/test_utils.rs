@@ -0,0 +1,136 @@
+//Pomocnicze funkcje dla testów warstwy git: tymczasowe repozytorium i commity ze zmianami plików
+use std::path::PathBuf;
+use std::sync::Arc;
+use git2::{BranchType, FileMode, Oid, Repository, Signature, build::TreeUpdateBuilder};
+use tokio::sync::{Mutex, Notify};
+use super::git_session::GitSession;
+use super::Git;
+
+pub fn test_dir(name: &str) -> PathBuf {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+    let dir = std::env::temp_dir().join(format!("notatki-{name}-{}-{nanos}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+pub fn test_repo(name: &str) -> Repository {
+    let mut options = git2::RepositoryInitOptions::new();
+    options.initial_head("master");
+    Repository::init_opts(test_dir(name), &options).unwrap()
+}
+
+pub fn test_master(repo: &Repository) -> Option<Oid> {
+    let branch = repo.find_branch("master", BranchType::Local).ok()?;
+    branch.get().target()
+}
+
+//Commit bez przesuwania gałęzi. Zmiany nakładane są na drzewo pierwszego rodzica,
+//ścieżka w postaci "a/b.txt", Some - zapis pliku, None - usunięcie
+pub fn test_commit_tree(repo: &Repository, message: &str, parents: &[Oid], changes: &[(&str, Option<&str>)]) -> Oid {
+    let changes = changes
+        .iter()
+        .map(|(path, content)| (*path, content.map(|content| content.as_bytes())))
+        .collect::<Vec<_>>();
+
+    test_commit_tree_bytes(repo, message, parents, &changes)
+}
+
+pub fn test_commit_tree_bytes(repo: &Repository, message: &str, parents: &[Oid], changes: &[(&str, Option<&[u8]>)]) -> Oid {
+    let signature = Signature::now("test", "test@example.com").unwrap();
+    commit_tree(repo, &signature, message, parents, changes)
+}
+
+//Commit z podanym czasem (sekundy), dla testów zależnych od kolejności czasu commitów
+pub fn test_commit_tree_at(repo: &Repository, message: &str, parents: &[Oid], changes: &[(&str, Option<&str>)], time: i64) -> Oid {
+    let changes = changes
+        .iter()
+        .map(|(path, content)| (*path, content.map(|content| content.as_bytes())))
+        .collect::<Vec<_>>();
+
+    let signature = Signature::new("test", "test@example.com", &git2::Time::new(time, 0)).unwrap();
+    commit_tree(repo, &signature, message, parents, &changes)
+}
+
+fn commit_tree(repo: &Repository, signature: &Signature, message: &str, parents: &[Oid], changes: &[(&str, Option<&[u8]>)]) -> Oid {
+    let parents = parents.iter().map(|id| repo.find_commit(*id).unwrap()).collect::<Vec<_>>();
+
+    let baseline = match parents.first() {
+        Some(parent) => parent.tree().unwrap(),
+        None => repo.find_tree(repo.treebuilder(None).unwrap().write().unwrap()).unwrap(),
+    };
+
+    let mut builder = TreeUpdateBuilder::new();
+
+    for (path, content) in changes {
+        match content {
+            Some(content) => {
+                let blob = repo.blob(content).unwrap();
+                builder.upsert(*path, blob, FileMode::Blob);
+            },
+            None => {
+                builder.remove(*path);
+            }
+        }
+    }
+
+    let tree = repo.find_tree(builder.create_updated(repo, &baseline).unwrap()).unwrap();
+    let parents = parents.iter().collect::<Vec<_>>();
+
+    repo.commit(None, signature, signature, message, &tree, &parents).unwrap()
+}
+
+//Commit na gałęzi master
+pub fn test_change(repo: &Repository, message: &str, changes: &[(&str, Option<&str>)]) -> Oid {
+    let parents = test_master(repo).into_iter().collect::<Vec<_>>();
+    let id = test_commit_tree(repo, message, &parents, changes);
+    test_set_master(repo, id);
+    id
+}
+
+pub fn test_change_bytes(repo: &Repository, message: &str, changes: &[(&str, Option<&[u8]>)]) -> Oid {
+    let parents = test_master(repo).into_iter().collect::<Vec<_>>();
+    let id = test_commit_tree_bytes(repo, message, &parents, changes);
+    test_set_master(repo, id);
+    id
+}
+
+pub fn test_set_master(repo: &Repository, id: Oid) {
+    repo.reference("refs/heads/master", id, true, "test").unwrap();
+}
+
+pub fn test_session(repo: &Mutex<Repository>) -> GitSession<'_> {
+    GitSession::new(Arc::new(Notify::new()), repo.blocking_lock(), "master").unwrap()
+}
+
+//Wersja dla testów async - blocking_lock nie może być wywołany w runtime tokio
+pub async fn test_session_async(repo: &Mutex<Repository>) -> GitSession<'_> {
+    GitSession::new(Arc::new(Notify::new()), repo.lock().await, "master").unwrap()
+}
+
+pub fn test_path(path: &str) -> Vec<String> {
+    path.split('/').filter(|item| !item.is_empty()).map(String::from).collect()
+}
+
+pub fn test_git(repo: &Repository) -> Git {
+    let path = repo.path().to_string_lossy().to_string();
+    Git::new(Arc::new(Notify::new()), path, String::from("master"), None).unwrap()
+}
+
+pub fn test_git_trash(repo: &Repository, trash_dir: &str) -> Git {
+    let path = repo.path().to_string_lossy().to_string();
+    Git::new(Arc::new(Notify::new()), path, String::from("master"), Some(String::from(trash_dir))).unwrap()
+}
+
+pub fn test_root(repo: &Repository) -> Oid {
+    let commit = repo.find_commit(test_master(repo).unwrap()).unwrap();
+    commit.tree_id()
+}
+
+pub fn test_read(repo: &Repository, path: &str) -> Option<String> {
+    let commit = repo.find_commit(test_master(repo)?).unwrap();
+    let entry = commit.tree().unwrap().get_path(std::path::Path::new(path)).ok()?;
+    let blob = repo.find_blob(entry.id()).ok()?;
+    Some(String::from_utf8(blob.content().to_vec()).unwrap())
+}
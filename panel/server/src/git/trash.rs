@@ -0,0 +1,335 @@
+use crate::{utils::ErrorProcess, models::HandlerTrashItem};
+use super::git_session::{GitSession, GitId};
+use super::git_operations::split_last;
+use super::models::GitBlob;
+
+//Każde usunięcie dostaje osobny katalog <trash_dir>/<timestamp>, w którym są dokładnie dwa elementy:
+//"item" - usunięty plik lub katalog, "path" - plik z oryginalną ścieżką elementu (np. "a/b/c.txt").
+//Przy kilku usunięciach w tej samej sekundzie nazwa wpisu dostaje licznik: <timestamp>-1, <timestamp>-2 ...
+//
+//Celowo nie odtwarzamy oryginalnej ścieżki w postaci <trash_dir>/<timestamp>/a/b/c.txt - z samego drzewa
+//nie da się wtedy rozpoznać, gdzie kończy się ścieżka, a zaczyna usunięty katalog (np. usunięty katalog "a"
+//zawierający "b/c.txt"), dwa usunięcia w tej samej sekundzie trafiały do wspólnego katalogu, a po przywróceniu
+//trzeba było sprzątać puste katalogi pośrednie. Wpis z jednym elementem i zapisaną ścieżką nie ma tych problemów
+const ENTRY_ITEM: &str = "item";
+const ENTRY_PATH: &str = "path";
+
+pub fn is_in_trash(path: &[String], trash_dir: &String) -> bool {
+    path.first() == Some(trash_dir)
+}
+
+fn parse_timestamp(entry: &str) -> i64 {
+    let timestamp = match entry.split_once('-') {
+        Some((timestamp, _)) => timestamp,
+        None => entry,
+    };
+
+    timestamp.parse().unwrap_or(0)
+}
+
+//Tworzy brakujące katalogi na ścieżce
+async fn ensure_dir<'repo>(
+    session: GitSession<'repo>,
+    path: &[String],
+) -> Result<GitSession<'repo>, ErrorProcess> {
+    let mut session = session;
+
+    for index in 0..path.len() {
+        let (next_session, id) = session.find_path(&path[..=index]).await?;
+
+        session = match id {
+            Some(id) if id.is_file() => {
+                return ErrorProcess::user_result(format!("Expected directory, found file {}", path[..=index].join("/")));
+            },
+            Some(_) => next_session,
+            None => {
+                let (next_session, empty_dir) = next_session.create_empty_dir().await?;
+                next_session.insert_child(&path[..index], &path[index], empty_dir).await?
+            }
+        };
+    }
+
+    Ok(session)
+}
+
+//Wolna nazwa katalogu dla usunięcia, przy kilku usunięciach w tej samej sekundzie dochodzi licznik
+async fn free_entry<'repo>(
+    session: GitSession<'repo>,
+    trash_dir: &String,
+    timestamp: i64,
+) -> Result<(GitSession<'repo>, String), ErrorProcess> {
+    let mut session = session;
+    let mut entry = timestamp.to_string();
+    let mut counter = 0;
+
+    loop {
+        let (next_session, id) = session.find_path(&[trash_dir.clone(), entry.clone()]).await?;
+        session = next_session;
+
+        if id.is_none() {
+            return Ok((session, entry));
+        }
+
+        counter += 1;
+        entry = format!("{timestamp}-{counter}");
+    }
+}
+
+pub async fn trash_item<'repo>(
+    session: GitSession<'repo>,
+    path: &[String],
+    item_hash: &String,
+    recursive: bool,
+    trash_dir: &String,
+    timestamp: i64,
+) -> Result<(GitSession<'repo>, String), ErrorProcess> {
+    let (path_base, path_last) = split_last(path)?;
+
+    let (session, child) = session.extract_child(path_base, path_last).await?;
+    session.should_eq(&child, item_hash)?;
+
+    let (session, result) = session.get_from_id(&child.id.to_string()).await?;
+
+    if let Some(GitBlob::Tree { list }) = result {
+        if !list.is_empty() && !recursive {
+            return Err(ErrorProcess::user(format!("non-empty directory cannot be deleted {path:?}")));
+        }
+    }
+
+    let (session, entry) = free_entry(session, trash_dir, timestamp).await?;
+    let target = vec![trash_dir.clone(), entry];
+
+    let session = ensure_dir(session, &target).await?;
+    let session = session.insert_child(&target, &String::from(ENTRY_ITEM), child).await?;
+    let (session, original) = session.create_file_content(&path.join("/")).await?;
+    let session = session.insert_child(&target, &String::from(ENTRY_PATH), original).await?;
+
+    Ok((session, format!("trash {} -> {}", path.join("/"), target.join("/"))))
+}
+
+//None - katalog nie jest poprawnym wpisem kosza
+async fn read_entry<'repo>(
+    session: GitSession<'repo>,
+    trash_dir: &String,
+    entry: &String,
+) -> Result<(GitSession<'repo>, Option<HandlerTrashItem>), ErrorProcess> {
+    let (session, id) = session.find_path(&[trash_dir.clone(), entry.clone()]).await?;
+
+    let Some(id) = id else {
+        return Ok((session, None));
+    };
+
+    let (session, blob) = session.get_from_id(&id.id.to_string()).await?;
+
+    let Some(GitBlob::Tree { list }) = blob else {
+        return Ok((session, None));
+    };
+
+    let item = list.iter().find(|child| child.name == ENTRY_ITEM);
+    let path = list.iter().find(|child| child.name == ENTRY_PATH && !child.dir);
+
+    let (Some(item), Some(path), 2) = (item, path, list.len()) else {
+        return Ok((session, None));
+    };
+
+    let (session, blob) = session.get_from_id(&path.id).await?;
+
+    let Some(GitBlob::Blob { content }) = blob else {
+        return Ok((session, None));
+    };
+
+    let original = String::from_utf8_lossy(&content)
+        .split('/')
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    if original.is_empty() {
+        return Ok((session, None));
+    }
+
+    Ok((session, Some(HandlerTrashItem {
+        entry: entry.clone(),
+        original,
+        hash: item.id.clone(),
+        dir: item.dir,
+        timestamp: parse_timestamp(entry),
+    })))
+}
+
+pub async fn trash_entry<'repo>(
+    session: GitSession<'repo>,
+    trash_dir: &String,
+    entry: &String,
+) -> Result<(GitSession<'repo>, HandlerTrashItem), ErrorProcess> {
+    let (session, item) = read_entry(session, trash_dir, entry).await?;
+
+    let Some(item) = item else {
+        return ErrorProcess::user_result(format!("Not a trash entry {entry}"));
+    };
+
+    Ok((session, item))
+}
+
+pub async fn trash_list<'repo>(
+    session: GitSession<'repo>,
+    trash_dir: &String,
+) -> Result<(GitSession<'repo>, Vec<HandlerTrashItem>), ErrorProcess> {
+    let (session, trash) = session.find_path(std::slice::from_ref(trash_dir)).await?;
+
+    let Some(trash) = trash else {
+        return Ok((session, Vec::new()));
+    };
+
+    let (mut session, entries) = session.get_from_id(&trash.id.to_string()).await?;
+
+    let Some(GitBlob::Tree { list: entries }) = entries else {
+        return Ok((session, Vec::new()));
+    };
+
+    let mut result = Vec::new();
+
+    for entry in entries {
+        let (next_session, item) = read_entry(session, trash_dir, &entry.name).await?;
+        session = next_session;
+        result.extend(item);
+    }
+
+    result.sort_by_key(|item| std::cmp::Reverse(item.timestamp));
+
+    Ok((session, result))
+}
+
+//Wyjmuje element z wpisu kosza i usuwa cały wpis
+async fn take_entry<'repo>(
+    session: GitSession<'repo>,
+    trash_dir: &String,
+    entry: &String,
+    item_hash: &String,
+) -> Result<(GitSession<'repo>, HandlerTrashItem, GitId), ErrorProcess> {
+    let (session, item) = trash_entry(session, trash_dir, entry).await?;
+
+    let entry_path = [trash_dir.clone(), entry.clone()];
+    let (session, child) = session.extract_child(&entry_path, &String::from(ENTRY_ITEM)).await?;
+    session.should_eq(&child, item_hash)?;
+
+    let (session, _) = session.remove_child(std::slice::from_ref(trash_dir), entry).await?;
+
+    Ok((session, item, child))
+}
+
+pub async fn restore<'repo>(
+    session: GitSession<'repo>,
+    entry: &String,
+    item_hash: &String,
+    trash_dir: &String,
+) -> Result<(GitSession<'repo>, String), ErrorProcess> {
+    let (session, item, child) = take_entry(session, trash_dir, entry, item_hash).await?;
+    let (original_base, original_last) = split_last(&item.original)?;
+
+    let session = ensure_dir(session, original_base).await?;
+    let session = session.insert_child(original_base, original_last, child).await?;
+
+    Ok((session, format!("restore {} from trash", item.original.join("/"))))
+}
+
+pub async fn purge<'repo>(
+    session: GitSession<'repo>,
+    entry: &String,
+    item_hash: &String,
+    trash_dir: &String,
+) -> Result<(GitSession<'repo>, String), ErrorProcess> {
+    let (session, item, _) = take_entry(session, trash_dir, entry, item_hash).await?;
+
+    Ok((session, format!("purge {} from trash", item.original.join("/"))))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ensure_dir() {
+    use super::test_utils::{test_repo, test_change, test_session_async, test_path};
+
+    let repo = test_repo("trash-ensure");
+    test_change(&repo, "first", &[("a/b.txt", Some("b"))]);
+    let repo = tokio::sync::Mutex::new(repo);
+
+    let session = test_session_async(&repo).await;
+    let session = ensure_dir(session, &test_path("a/x/y")).await.unwrap();
+
+    let (session, id) = session.find_path(&test_path("a/x/y")).await.unwrap();
+    assert!(id.map(|id| !id.is_file()).unwrap_or(false));
+    let (session, id) = session.find_path(&test_path("a/b.txt")).await.unwrap();
+    assert!(id.is_some());
+
+    //na ścieżce jest plik
+    assert!(ensure_dir(session, &test_path("a/b.txt/c")).await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_trash_timestamp_collision() {
+    use super::test_utils::{test_repo, test_change, test_session_async, test_path};
+
+    let repo = test_repo("trash-collision");
+    let hash = |content: &str| git2::Oid::hash_object(git2::ObjectType::Blob, content.as_bytes()).unwrap().to_string();
+    test_change(&repo, "first", &[("a.txt", Some("a")), ("b.txt", Some("b")), (".trash/old/x.txt", Some("x"))]);
+    let repo = tokio::sync::Mutex::new(repo);
+    let trash_dir = String::from(".trash");
+
+    let session = test_session_async(&repo).await;
+    let (session, _) = trash_item(session, &test_path("a.txt"), &hash("a"), false, &trash_dir, 100).await.unwrap();
+    let (session, _) = trash_item(session, &test_path("b.txt"), &hash("b"), false, &trash_dir, 100).await.unwrap();
+
+    //katalog bez pliku z oryginalną ścieżką nie jest wpisem kosza
+    let (_, list) = trash_list(session, &trash_dir).await.unwrap();
+
+    let mut list = list
+        .into_iter()
+        .map(|item| (item.entry, item.original.join("/"), item.hash, item.timestamp))
+        .collect::<Vec<_>>();
+    list.sort();
+
+    assert_eq!(list, vec![
+        (String::from("100"), String::from("a.txt"), hash("a"), 100),
+        (String::from("100-1"), String::from("b.txt"), hash("b"), 100),
+    ]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_trash_restore_purge() {
+    use super::test_utils::{test_repo, test_change, test_git_trash, test_read, test_root, test_path};
+
+    let repo = test_repo("trash-restore");
+    let hash = |content: &str| git2::Oid::hash_object(git2::ObjectType::Blob, content.as_bytes()).unwrap().to_string();
+    test_change(&repo, "first", &[("dir/a.txt", Some("a")), ("c.txt", Some("c"))]);
+    let git = test_git_trash(&repo, ".trash");
+
+    git.delete_item(None, test_path("dir/a.txt"), hash("a"), false).await.unwrap();
+    git.delete_item(None, test_path("c.txt"), hash("c"), false).await.unwrap();
+
+    let list = git.trash_list().await.unwrap();
+    assert_eq!(list.len(), 2);
+
+    let entry_a = list.iter().find(|item| item.original == test_path("dir/a.txt")).unwrap().entry.clone();
+    let entry_c = list.iter().find(|item| item.original == test_path("c.txt")).unwrap().entry.clone();
+    assert_eq!(test_read(&repo, &format!(".trash/{entry_a}/path")), Some(String::from("dir/a.txt")));
+    assert_eq!(test_read(&repo, &format!(".trash/{entry_a}/item")), Some(String::from("a")));
+
+    //brakujące katalogi na pierwotnej ścieżce są tworzone przy przywracaniu
+    assert_eq!(test_read(&repo, "dir/a.txt"), None);
+
+    let root = test_root(&repo);
+    assert!(git.trash_restore(None, entry_a.clone(), hash("c")).await.is_err());
+    assert!(git.trash_purge(None, String::from("missing"), hash("c")).await.is_err());
+    assert_eq!(test_root(&repo), root);
+
+    git.trash_restore(None, entry_a.clone(), hash("a")).await.unwrap();
+    assert_eq!(test_read(&repo, "dir/a.txt"), Some(String::from("a")));
+    assert_eq!(test_read(&repo, &format!(".trash/{entry_a}/path")), None);
+
+    //element o tej nazwie już istnieje
+    test_change(&repo, "new c", &[("c.txt", Some("c2"))]);
+    assert!(git.trash_restore(None, entry_c.clone(), hash("c")).await.is_err());
+
+    git.trash_purge(None, entry_c, hash("c")).await.unwrap();
+    assert!(git.trash_list().await.unwrap().is_empty());
+    assert_eq!(test_read(&repo, "c.txt"), Some(String::from("c2")));
+}
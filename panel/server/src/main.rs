@@ -11,6 +11,9 @@ use poem::{
     listener::TcpListener,
     Server,
     Route,
+    get,
+    post,
+    EndpointExt,
 };
 use poem_openapi::{
     OpenApiService,
@@ -19,23 +22,74 @@ use poem_openapi::{
 use std::{net::Ipv4Addr, sync::Arc};
 use serde::{Deserialize};
 use std::net::SocketAddr;
-use tokio::sync::Notify;
 
 mod sync;
 mod git;
 mod utils;
 mod static_response;
 mod api;
-
-use git::{Git};
-
-use crate::{api::Api, sync::start_sync};
+mod root_watch;
+mod workspace;
+mod auth;
+mod acl;
+mod content_type;
+mod thumbnail;
+
+use crate::{
+    api::Api,
+    root_watch::handler_root_ws,
+    workspace::{Workspace, parse_repos},
+    auth::{Auth, AuthMiddleware, hash_password, handler_login, handler_logout, handler_whoami},
+    acl::Acl,
+    thumbnail::Thumbnails,
+};
 
 #[derive(Deserialize)]
 struct Config {
     http_host: Ipv4Addr,
     http_port: u16,
-    git_repo: String,
+    git_repo: Option<String>,
+    #[serde(default)]
+    git_repos: Vec<String>,
+    #[serde(default = "default_git_branch")]
+    git_branch: String,
+    #[serde(default = "default_git_remote")]
+    git_remote: String,
+    users_file: Option<String>,
+    auth_proxy_header: Option<String>,
+    #[serde(default = "default_session_ttl")]
+    session_ttl: i64,               //w sekundach
+    #[serde(default = "default_cookie_secure")]
+    cookie_secure: bool,            //false - serwer dostępny tylko po zwykłym http
+    acl_file: Option<String>,
+    #[serde(default = "default_trash_dir")]
+    trash_dir: String,              //pusty - usuwanie bez kosza
+    #[serde(default = "default_thumbnail_dir")]
+    thumbnail_dir: String,          //pusty - miniatury bez pamięci podręcznej na dysku
+}
+
+fn default_git_branch() -> String {
+    "master".into()
+}
+
+fn default_git_remote() -> String {
+    "origin".into()
+}
+
+fn default_session_ttl() -> i64 {
+    7 * 24 * 3600
+}
+
+fn default_cookie_secure() -> bool {
+    true
+}
+
+fn default_trash_dir() -> String {
+    ".trash".into()
+}
+
+fn default_thumbnail_dir() -> String {
+    "./thumbnails".into()
 }
 
 
@@ -43,23 +97,86 @@ struct Config {
 async fn main() {
     pretty_env_logger::init();
 
+    //server hash_password <hasło> - hash do wpisania w pliku użytkowników
+    let args = std::env::args().collect::<Vec<_>>();
+    if let [_, command, password] = args.as_slice() {
+        if command == "hash_password" {
+            match hash_password(password) {
+                Ok(hash) => println!("{hash}"),
+                Err(error) => panic!("{error}"),
+            }
+            return;
+        }
+    }
+
     let config = match envy::from_env::<Config>() {
         Ok(config) => config,
         Err(error) => panic!("Service started with invalid environment variables {error:#?}")
     };
 
-    let notify = Arc::new(Notify::new());
+    let repos = match parse_repos(config.git_repo.clone(), config.git_repos.clone()) {
+        Ok(repos) => repos,
+        Err(error) => panic!("Service started with invalid repositories configuration {error}")
+    };
+
+    let auth = match Auth::load(
+        config.users_file.clone(),
+        config.auth_proxy_header.clone(),
+        config.session_ttl,
+        config.cookie_secure,
+    ) {
+        Ok(auth) => auth,
+        Err(error) => panic!("Service started with invalid users configuration {error}")
+    };
+
+    if !auth.enabled() {
+        log::warn!("USERS_FILE and AUTH_PROXY_HEADER are not set - authentication is disabled");
+    }
 
-    let task_synchronize = start_sync(notify.clone(), config.git_repo.clone()).await;
+    let acl = match Acl::load(config.acl_file.clone()) {
+        Ok(acl) => acl,
+        Err(error) => panic!("Service started with invalid acl configuration {error}")
+    };
+
+    let trash_dir = match config.trash_dir.as_str() {
+        "" => None,
+        dir if dir.contains('/') => panic!("Service started with invalid TRASH_DIR {dir:?}, expected a single directory name"),
+        dir => Some(String::from(dir)),
+    };
 
-    println!("start git test: {}", &config.git_repo);
-    let git = Git::new(notify, config.git_repo.clone(), "master".into()).unwrap();
+    let thumbnails = match config.thumbnail_dir.as_str() {
+        "" => Thumbnails::new(None),
+        dir => Thumbnails::new(Some(String::from(dir))),
+    };
 
-    let api = Api::new(git.clone());
+    let names = Arc::new(repos.iter().map(|repo| repo.name.clone()).collect::<Vec<_>>());
 
+    let mut workspaces = Vec::new();
+
+    for repo in repos {
+        let workspace = Workspace::start(repo, config.git_branch.clone(), config.git_remote.clone(), trash_dir.clone()).await;
+        workspaces.push(workspace);
+    }
 
     log::info!("Start - {}:{}", config.http_host, config.http_port);
 
+    let mut route = Route::new()
+        .nest("/build", StaticFilesEndpoint::new("./build").show_files_listing());
+
+    for workspace in workspaces.iter() {
+        let api = Api::new(workspace.git.clone(), workspace.sync.clone(), acl.clone(), thumbnails.clone(), workspace.name.clone(), names.clone());
+        let api_service = OpenApiService::new(api, "Server", "1.0");
+
+        route = route
+            .at(format!("/repo/{}/root_ws", workspace.name), get(handler_root_ws).data(workspace.root_watch.clone()))
+            .nest(format!("/repo/{}", workspace.name), api_service);
+    }
+
+    //Pierwsze repozytorium jest dostępne również bez prefiksu (parse_repos zwraca co najmniej jedno)
+    let default = &workspaces[0];
+
+    let api = Api::new(default.git.clone(), default.sync.clone(), acl.clone(), thumbnails.clone(), default.name.clone(), names.clone());
+
     let api_service = OpenApiService::new(
             api,
             "Server",
@@ -73,13 +190,18 @@ async fn main() {
     let addr = SocketAddr::from(([0, 0, 0, 0], config.http_port));
     
     Server::new(TcpListener::bind(addr)) //"127.0.0.1:3000"))
-        .run(Route::new()
+        .run(route
             .nest("/swagger", ui)
-            .nest("/build", StaticFilesEndpoint::new("./build").show_files_listing())
+            .at("/root_ws", get(handler_root_ws).data(default.root_watch.clone()))
+            .at("/login", post(handler_login).data(auth.clone()))
+            .at("/logout", post(handler_logout).data(auth.clone()))
+            .at("/whoami", get(handler_whoami).data(auth.clone()))
             .nest_no_strip("/", api_service)
+            .with(AuthMiddleware::new(auth))
         )
         .await.unwrap();
 
-    task_synchronize.off();
+    for workspace in workspaces {
+        workspace.off();
+    }
 }
-
@@ -1,7 +1,7 @@
 #![allow(clippy::new_without_default)]
 
 use serde::{Deserialize, Serialize};
-use poem_openapi::{Object};
+use poem_openapi::{Object, Union};
 
 // pub type TimestampType = u128;
 
@@ -15,6 +15,7 @@ pub struct RootResponse {
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
 pub struct HandlerFetchDirBody {
     pub id: String,
+    pub path: Vec<String>,      //ścieżka katalogu - do sprawdzenia uprawnień
 }
 
 
@@ -46,6 +47,7 @@ impl HandlerFetchDirResponse {
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
 pub struct HandlerFetchNodeBody {
     pub hash: String,
+    pub path: Vec<String>,
 }
 
 
@@ -62,6 +64,26 @@ pub struct HandlerSaveContentBody {
     pub new_content: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct MergeChunk {
+    pub conflict: bool,
+    pub lines: Vec<String>,         //conflict == false
+    pub current: Vec<String>,       //conflict == true - wersja z repozytorium
+    pub edited: Vec<String>,        //conflict == true - wersja użytkownika
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct SaveContentConflict {
+    pub current_hash: String,       //hash aktualnej zawartości pliku, z którą wystąpił konflikt
+    pub chunks: Vec<MergeChunk>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerSaveContentResponse {
+    pub root: String,
+    pub conflict: Option<SaveContentConflict>,      //Some - zmiany nie zostały zapisane
+}
+
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
 pub struct HandlerCreateFileBody {
@@ -93,6 +115,9 @@ pub struct HandlerRenameItemBody {
 pub struct HandlerDeleteItemBody {
     pub path: Vec<String>,
     pub hash: String,
+    #[serde(default)]
+    #[oai(default)]
+    pub recursive: bool,        //true - katalog razem z zawartością
 }
 
 
@@ -103,6 +128,81 @@ pub struct HandlerMoveItemBody {
     pub new_path: Vec<String>,
 }
 
+//Operacja w ramach /batch, rodzaj operacji w polu "operation"
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Union)]
+#[serde(tag = "operation", rename_all = "lowercase")]
+#[oai(discriminator_name = "operation")]
+pub enum HandlerBatchOperation {
+    #[oai(mapping = "create")]
+    Create(HandlerBatchCreate),
+    #[oai(mapping = "mkdir")]
+    Mkdir(HandlerBatchMkdir),
+    #[oai(mapping = "save")]
+    Save(HandlerBatchSave),
+    #[oai(mapping = "rename")]
+    Rename(HandlerBatchRename),
+    #[oai(mapping = "move")]
+    Move(HandlerBatchMove),
+    #[oai(mapping = "delete")]
+    Delete(HandlerBatchDelete),
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerBatchCreate {
+    pub path: Vec<String>,          //katalog
+    pub name: String,
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerBatchMkdir {
+    pub path: Vec<String>,          //katalog
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerBatchSave {
+    pub path: Vec<String>,
+    pub hash: String,
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerBatchRename {
+    pub path: Vec<String>,          //katalog
+    pub name: String,
+    pub hash: String,
+    pub new_name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerBatchMove {
+    pub path: Vec<String>,
+    pub hash: String,
+    pub new_path: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerBatchDelete {
+    pub path: Vec<String>,
+    pub hash: String,
+    #[serde(default)]
+    #[oai(default)]
+    pub recursive: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerBatchBody {
+    pub operations: Vec<HandlerBatchOperation>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerCopyItemBody {
+    pub path: Vec<String>,
+    pub hash: String,
+    pub new_path: Vec<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
 pub struct HandlerAddFiles {
     pub path: Vec<String>,
@@ -113,4 +213,187 @@ pub struct HandlerAddFiles {
 pub struct HandlerAddFilesFile {
     pub name: String,
     pub blob_id: String,
-}
\ No newline at end of file
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerFileHistoryBody {
+    pub path: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct FileHistoryItem {
+    pub commit: String,
+    pub author: String,
+    pub timestamp: i64,
+    pub message: String,
+    pub id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerFileHistoryResponse {
+    pub list: Vec<FileHistoryItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerDiffBody {
+    pub path: Vec<String>,
+    pub prev_id: String,        //pusty string - porównanie z pustą zawartością
+    pub next_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerDiffCommitsBody {
+    pub path: Vec<String>,
+    pub prev_commit: String,    //pusty string - porównanie z pustą zawartością
+    pub next_commit: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct DiffLineItem {
+    pub origin: String,         //"+", "-" lub " "
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerDiffResponse {
+    pub list: Vec<DiffLineItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerRestoreItemBody {
+    pub path: Vec<String>,
+    pub prev_hash: String,          //hash bieżącej wersji elementu, pusty string - element nie istnieje
+    pub commit: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerSearchBody {
+    pub phrase: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct SearchLineItem {
+    pub number: u32,
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct SearchResultItem {
+    pub path: Vec<String>,
+    pub lines: Vec<SearchLineItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerSearchResponse {
+    pub list: Vec<SearchResultItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct TaskFileItem {
+    pub path: Vec<String>,
+    pub open: u32,              //ilość niezakończonych zadań "[ ]" w pliku
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerTasksResponse {
+    pub list: Vec<TaskFileItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct TodoLineItem {
+    pub line: u32,              //numer linii liczony od zera
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct TodoFileItem {
+    pub path: Vec<String>,
+    pub todo: bool,             //plik z rozszerzeniem .todo
+    pub timestamp: i64,         //czas commita z ostatnią zmianą pliku, 0 - brak w historii
+    pub tasks: Vec<TodoLineItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerTodoDashboardResponse {
+    pub list: Vec<TodoFileItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct DueItem {
+    pub path: Vec<String>,
+    pub line: Option<u32>,      //numer linii zadania liczony od zera, None - termin w nazwie pliku .todo
+    pub content: String,
+    pub due: String,            //YYYY-MM-DD
+    pub overdue: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerDueResponse {
+    pub today: String,
+    pub list: Vec<DueItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerSyncStatusResponse {
+    pub status: String,         //InSync, Ahead, Behind, Diverged, Conflict, Offline, Error
+    pub last_attempt: i64,      //0 - synchronizacja jeszcze się nie odbyła
+    pub last_success: i64,
+    pub ahead: u32,
+    pub behind: u32,
+    pub last_error: String,     //pusty string - brak błędu
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerReposResponse {
+    pub current: String,
+    pub list: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerLoginBody {
+    pub login: String,
+    pub password: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerWhoamiResponse {
+    pub auth: bool,             //false - logowanie wyłączone
+    pub login: String,          //pusty string - brak sesji
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerDeleteManifestBody {
+    pub path: Vec<String>,
+    pub hash: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerDeleteManifestResponse {
+    pub hash: String,           //hash elementu, który należy przekazać do /delete_item
+    pub files: u32,
+    pub dirs: u32,              //podkatalogi, bez samego usuwanego katalogu
+    pub size: u64,              //suma rozmiarów plików w bajtach
+    pub todo: u32,              //ilość plików *.todo
+}
+
+//entry - nazwa wpisu w koszu (<timestamp> lub <timestamp>-<n>), original - ścieżka elementu przed usunięciem
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerTrashItem {
+    pub entry: String,
+    pub original: Vec<String>,
+    pub hash: String,
+    pub dir: bool,
+    pub timestamp: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerTrashResponse {
+    pub list: Vec<HandlerTrashItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Object)]
+pub struct HandlerTrashEntryBody {
+    pub entry: String,
+    pub hash: String,
+}
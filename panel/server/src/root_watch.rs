@@ -0,0 +1,69 @@
+use futures::SinkExt;
+use poem::{
+    handler,
+    IntoResponse,
+    web::{Data, websocket::{WebSocket, Message}},
+};
+use tokio::sync::watch;
+use crate::git::Git;
+
+//Aktualny root, zmienia się po lokalnym commicie lub po synchronizacji z innej maszyny
+#[derive(Clone)]
+pub struct RootWatch {
+    receiver: watch::Receiver<String>,
+}
+
+//Odświeżany przez zadanie synchronizacji, które budzi się po każdym lokalnym commicie (notify)
+pub struct RootPublisher {
+    git: Git,
+    sender: watch::Sender<String>,
+}
+
+impl RootWatch {
+    pub async fn new(git: Git) -> (RootWatch, RootPublisher) {
+        let root = git.main_commit().await.unwrap_or_default();
+        let (sender, receiver) = watch::channel(root);
+
+        (RootWatch { receiver }, RootPublisher { git, sender })
+    }
+}
+
+impl RootPublisher {
+    pub async fn refresh(&self) {
+        match self.git.main_commit().await {
+            Ok(root) => {
+                self.sender.send_if_modified(|current| {
+                    if *current == root {
+                        return false;
+                    }
+
+                    log::info!("New root {root}");
+                    *current = root;
+                    true
+                });
+            },
+            Err(err) => {
+                log::error!("Root watch error {:?}", err.to_string());
+            }
+        }
+    }
+}
+
+#[handler]
+pub fn handler_root_ws(ws: WebSocket, root_watch: Data<&RootWatch>) -> impl IntoResponse {
+    let mut receiver = root_watch.receiver.clone();
+
+    ws.on_upgrade(move |mut socket| async move {
+        loop {
+            let root = receiver.borrow_and_update().clone();
+
+            if socket.send(Message::Text(root)).await.is_err() {
+                break;
+            }
+
+            if receiver.changed().await.is_err() {
+                break;
+            }
+        }
+    })
+}
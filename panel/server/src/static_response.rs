@@ -1,4 +1,6 @@
 use poem_openapi::{ApiResponse, payload::{PlainText, Binary}};
+use crate::utils::ErrorProcess;
+use crate::content_type::Download;
 
 #[derive(ApiResponse)]
 pub enum StaticResponse {
@@ -8,6 +10,12 @@ pub enum StaticResponse {
         #[oai(header = "ContentType")] String
     ),
 
+    #[oai(status = 403)]
+    Forbidden(
+        PlainText<String>,
+        #[oai(header = "ContentType")] String
+    ),
+
     #[oai(status = 404)]
     NotFound(
         PlainText<String>,
@@ -19,6 +27,15 @@ pub enum StaticResponse {
         Binary<Vec<u8>>,
         #[oai(header = "ContentType")] String
     ),
+
+    #[oai(status = 200)]
+    Download(
+        Binary<Vec<u8>>,
+        #[oai(header = "content-type")] String,
+        #[oai(header = "content-disposition")] String,
+        #[oai(header = "x-content-type-options")] String,
+        #[oai(header = "content-security-policy")] Option<String>
+    ),
 }
 
 impl StaticResponse {
@@ -30,6 +47,28 @@ impl StaticResponse {
         )
     }
 
+    pub fn forbidden(message: impl Into<String>) -> StaticResponse {
+        let message = message.into();
+        StaticResponse::Forbidden(
+            PlainText(message),
+            "text/html".into(),
+        )
+    }
+
+    pub fn from_error_process(error: ErrorProcess) -> StaticResponse {
+        if error.is_forbidden() {
+            let (_, message) = error.to_string();
+            return StaticResponse::forbidden(message);
+        }
+
+        let message = match error.to_string() {
+            (false, message) => format!("User error: {message}"),
+            (true, message) => format!("Internal error: {message}"),
+        };
+
+        StaticResponse::internal_server(message)
+    }
+
     pub fn not_found() -> StaticResponse {
         StaticResponse::NotFound(
             PlainText("Not found".into()),
@@ -43,4 +82,15 @@ impl StaticResponse {
             header.into(),
         )
     }
+
+    //Pliki użytkowników - przeglądarka nie zgaduje typu, a typy aktywne (html, svg) nie uruchomią skryptów
+    pub fn download(download: Download, body: Vec<u8>) -> StaticResponse {
+        StaticResponse::Download(
+            Binary(body),
+            download.content_type.into(),
+            download.disposition,
+            "nosniff".into(),
+            download.sandbox.then(|| "sandbox".into()),
+        )
+    }
 }
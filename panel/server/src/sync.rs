@@ -1,187 +1,107 @@
-use tokio::sync::Notify;
+use tokio::sync::{Notify, watch};
 use tokio::time::sleep;
 use std::sync::Arc;
 use std::time::Duration;
-use crate::utils::{SpawnOwner, spawn_and_wait};
-use tokio::process::Command;
-use tokio::time::timeout;
-
-fn convert_to_lines(data: Vec<u8>) -> Vec<String> {
-    let data = String::from_utf8(data).unwrap();
-    let result: Vec<String> = data.lines().map(String::from).collect();
-    result
+use crate::git::{Git, SyncStatus, SyncResult};
+use crate::utils::{SpawnOwner, now_seconds};
+use crate::root_watch::RootPublisher;
+
+const SYNC_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+pub struct SyncState {
+    pub status: SyncStatus,
+    pub last_attempt: i64,          //0 - jeszcze nie było próby
+    pub last_success: i64,
+    pub ahead: usize,
+    pub behind: usize,
+    pub last_error: Option<String>,
 }
 
-struct Executor {
-    git_sync: String,
+#[derive(Clone)]
+pub struct SyncHandle {
+    state: watch::Receiver<SyncState>,
+    notify: Arc<Notify>,
 }
 
-impl Executor {
-    fn new(git_sync: String) -> Executor {
-        Executor {
-            git_sync,
-        }
-    }
-
-    async fn exec_command_inner(&self, command: &mut Command, ignore_error: bool) -> Vec<String> {
-        command.current_dir(&self.git_sync);
-
-        let command_text = format!("{:?}", command);
-        let output = match timeout(Duration::from_secs(7), command.output()).await {
-            Ok(Ok(data)) => data,
-            Ok(Err(err)) => {
-                panic!("Niepowodzenie0 ==> {} ==> {}", command_text, err);
-            },
-            Err(_err) => {
-                panic!("timeout");
-            }
-        };
-
-        if !ignore_error {
-            if !output.status.success() {
-                println!("status code -> {:?}", output.status.code());
-                panic!("Niepowodzenie1 ==> {}", command_text);
-            }
-        
-            let stderr = convert_to_lines(output.stderr);
-            if stderr.len() > 0 {
-                println!("stderr -> {:?}", stderr);
-                panic!("Niepowodzenie2 ==> {}", command_text);
-            }
-        }
-
-        convert_to_lines(output.stdout)
-    }
-
-    async fn exec_command(&self, command: &mut Command) -> Vec<String> {
-        self.exec_command_inner(command, false).await
+impl SyncHandle {
+    pub fn get(&self) -> SyncState {
+        self.state.borrow().clone()
     }
 
-    async fn exec_command_ignore_error(&self, command: &mut Command) -> Vec<String> {
-        self.exec_command_inner(command, true).await
+    pub fn force(&self) {
+        self.notify.notify_one();
     }
 }
 
-fn get_exact_one(mut response: Vec<String>) -> String {
-    let line = response.pop().unwrap();
-    assert_eq!(response.len(), 0);
-    line
+//Udana synchronizacja - gałęzie zostały uzgodnione, konflikt lub rozjazd gałęzi nie jest sukcesem
+fn is_success(result: &SyncResult) -> bool {
+    result.error.is_none() && matches!(result.status, SyncStatus::InSync | SyncStatus::Ahead | SyncStatus::Behind)
 }
 
-async fn get_current_branch(exec: &Executor) -> String {
-    let response = exec.exec_command(Command::new("git").arg("branch").arg("--show-current")).await;
-    get_exact_one(response)
-}
-
-async fn get_local_commit(exec: &Executor, branch: &String) -> String {
-    let response = exec.exec_command(Command::new("git")
-        .arg("log")
-        .arg("-1")
-        .arg(format!("origin/{branch}"))
-        .arg(r#"--pretty=format:"%H""#)
-    ).await;
-
-    get_exact_one(response)
-}
-
-async fn get_remote_commit(exec: &Executor, branch: &String) -> String {
-    let response = exec.exec_command(Command::new("git")
-        .arg("log")
-        .arg("-1")
-        .arg(branch)
-        .arg(r#"--pretty=format:"%H""#)
-    ).await;
-
-    get_exact_one(response)
-}
+//Synchronizacja po każdym lokalnym commicie (notify) oraz cyklicznie, żeby pobierać zmiany z innych maszyn.
+//Nowy root jest publikowany przed synchronizacją (lokalny commit) i po niej (zmiany pobrane z remote)
+pub async fn start_sync(notify: Arc<Notify>, git: Git, remote_name: String, root: RootPublisher) -> (SpawnOwner, SyncHandle) {
+    let (sender, receiver) = watch::channel(SyncState {
+        status: SyncStatus::Offline,
+        last_attempt: 0,
+        last_success: 0,
+        ahead: 0,
+        behind: 0,
+        last_error: None,
+    });
+
+    let handle = SyncHandle {
+        state: receiver,
+        notify: notify.clone(),
+    };
+
+    let task = SpawnOwner::new(async move {
+        loop {
+            root.refresh().await;
 
-async fn has_commit_synchronized(exec: &Executor, branch: &String) -> bool {
-    let local = get_local_commit(exec, branch).await;
-    let remote = get_remote_commit(exec, branch).await;
+            log::info!("Start sync ...");
+            let last_attempt = now_seconds();
+            let result = git.sync(&remote_name).await;
+            log::info!("Sync result {result:?}");
 
-    local == remote
-}
+            root.refresh().await;
 
+            sender.send_modify(|state| {
+                state.status = result.status;
+                state.last_attempt = last_attempt;
+                state.ahead = result.ahead;
+                state.behind = result.behind;
 
-pub async fn start_sync(notify: Arc<Notify>, git_sync: String) -> SpawnOwner {    
-    SpawnOwner::new(async move {
-        loop {
-            spawn_and_wait({
-                let notify = notify.clone();
-                let git_sync = git_sync.clone();
-
-                notify.notify_one();
-
-                async move {
-                    loop {
-                        notify.notified().await;
-                        sync(git_sync.clone()).await;
-                    }
+                if is_success(&result) {
+                    state.last_success = last_attempt;
                 }
-            }).await;
 
-            sleep(Duration::from_millis(5000)).await;
+                state.last_error = result.error;
+            });
 
-            log::info!("Restart sync process ...");
+            tokio::select! {
+                _ = notify.notified() => {},
+                _ = sleep(SYNC_INTERVAL) => {},
+            }
         }
-    })
-}
-
-
-async fn sync(git_sync: String) {
-    let exec = Executor::new(git_sync);
-
-    let current_branch = get_current_branch(&exec).await;
-    log::info!("Start sync {} ...", current_branch);
-
-
-
-    log::info!("Try git pull origin");
-    exec.exec_command_ignore_error(
-        Command::new("git")
-        .arg("pull")
-        .arg("origin")
-        .arg(&current_branch)
-    ).await;
-
-    exec.exec_command_ignore_error(
-        Command::new("git")
-        .arg("merge")
-        .arg("--abort")
-    ).await;
-
-    if has_commit_synchronized(&exec, &current_branch).await {
-        log::info!("Sync ok...");
-        return;
-    }
-
-
-    log::info!("Try git rebase and push");
-
-    exec.exec_command_ignore_error(
-        Command::new("git")
-        .arg("rebase")
-        .arg(format!("origin/{}", &current_branch))
-    ).await;
-
-    exec.exec_command_ignore_error(
-        Command::new("git")
-        .arg("rebase")
-        .arg("--abort")
-    ).await;
-
-    exec.exec_command_ignore_error(
-        Command::new("git")
-        .arg("push")
-        .arg("origin")
-        .arg(format!("{}:{}", &current_branch, &current_branch))
-    ).await;
-
-    if has_commit_synchronized(&exec, &current_branch).await {
-        log::info!("Sync ok...");
-        return;
-    }
+    });
 
+    (task, handle)
+}
 
-    panic!("Dalsze kroki synchronizacyjne");
-}
\ No newline at end of file
+#[test]
+fn test_is_success() {
+    let result = |status: SyncStatus, error: Option<&str>| SyncResult {
+        status,
+        ahead: 0,
+        behind: 0,
+        error: error.map(String::from),
+    };
+
+    assert!(is_success(&result(SyncStatus::InSync, None)));
+    assert!(!is_success(&result(SyncStatus::Ahead, Some("push rejected"))));
+    assert!(!is_success(&result(SyncStatus::Conflict, None)));
+    assert!(!is_success(&result(SyncStatus::Diverged, None)));
+    assert!(!is_success(&result(SyncStatus::Offline, Some("offline"))));
+}
@@ -0,0 +1,232 @@
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use image::{ColorType, codecs::{jpeg::JpegEncoder, webp::WebPEncoder}, imageops::FilterType, io::{Limits, Reader}};
+use crate::utils::ErrorProcess;
+
+//Szerokości miniatur, żądana szerokość zaokrąglana jest w górę do najbliższej z listy,
+//dzięki temu w pamięci podręcznej na dysku jest co najwyżej kilka wersji jednego obrazka
+const WIDTHS: [u32; 5] = [64, 128, 256, 512, 1024];
+
+//Ograniczenia przy dekodowaniu, żeby spreparowany plik nie zajął całej pamięci serwera
+const MAX_DIMENSION: u32 = 12_000;
+const MAX_ALLOC: u64 = 256 * 1024 * 1024;
+
+const JPEG_QUALITY: u8 = 80;
+
+pub fn normalize_width(width: u32) -> u32 {
+    for item in WIDTHS {
+        if width <= item {
+            return item;
+        }
+    }
+
+    WIDTHS[WIDTHS.len() - 1]
+}
+
+fn is_valid_id(id: &str) -> bool {
+    id.len() == 40 && id.chars().all(|char| char.is_ascii_hexdigit())
+}
+
+pub struct Thumbnail {
+    pub content_type: &'static str,
+    pub data: Vec<u8>,
+}
+
+impl Thumbnail {
+    //Typ miniatury z pamięci podręcznej rozpoznajemy po sygnaturze
+    fn from_cache(data: Vec<u8>) -> Thumbnail {
+        let content_type = match data.starts_with(b"\xff\xd8\xff") {
+            true => "image/jpeg",
+            false => "image/webp",
+        };
+
+        Thumbnail { content_type, data }
+    }
+}
+
+fn decode(content: &[u8]) -> Result<image::DynamicImage, ErrorProcess> {
+    let mut reader = match Reader::new(Cursor::new(content)).with_guessed_format() {
+        Ok(reader) => reader,
+        Err(err) => {
+            return ErrorProcess::user_result(format!("Problem with decode image {err}"));
+        }
+    };
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DIMENSION);
+    limits.max_image_height = Some(MAX_DIMENSION);
+    limits.max_alloc = Some(MAX_ALLOC);
+
+    reader.limits(limits);
+
+    match reader.decode() {
+        Ok(image) => Ok(image),
+        Err(err) => ErrorProcess::user_result(format!("Problem with decode image {err}")),
+    }
+}
+
+//Dekoduje obrazek i zmniejsza do zadanej szerokości (nigdy nie powiększa). Obrazki bez przezroczystości
+//(zdjęcia) kodowane są stratnie do jpeg, pozostałe do bezstratnego webp
+pub fn resize(content: &[u8], width: u32) -> Result<Thumbnail, ErrorProcess> {
+    let image = decode(content)?;
+
+    let image = match image.width() > width {
+        true => image.resize(width, u32::MAX, FilterType::Triangle),
+        false => image,
+    };
+
+    let mut data = Vec::new();
+
+    let (content_type, encoded) = match image.color().has_alpha() {
+        true => {
+            let image = image.to_rgba8();
+            let encoded = WebPEncoder::new_lossless(&mut data)
+                .encode(image.as_raw(), image.width(), image.height(), ColorType::Rgba8);
+            ("image/webp", encoded)
+        },
+        false => {
+            let image = image.to_rgb8();
+            let encoded = JpegEncoder::new_with_quality(&mut data, JPEG_QUALITY)
+                .encode(image.as_raw(), image.width(), image.height(), ColorType::Rgb8);
+            ("image/jpeg", encoded)
+        }
+    };
+
+    if let Err(err) = encoded {
+        return ErrorProcess::server_result(format!("Problem with encode {content_type} {err}"));
+    }
+
+    Ok(Thumbnail { content_type, data })
+}
+
+//Pamięć podręczna miniatur na dysku, kluczem jest id bloba i szerokość.
+//Blob o danym id nigdy się nie zmienia, więc wpisy nie wymagają unieważniania
+#[derive(Clone)]
+pub struct Thumbnails {
+    cache_dir: Option<PathBuf>,
+}
+
+impl Thumbnails {
+    pub fn new(cache_dir: Option<String>) -> Thumbnails {
+        Thumbnails {
+            cache_dir: cache_dir.map(PathBuf::from),
+        }
+    }
+
+    fn cache_path(&self, id: &str, width: u32) -> Option<PathBuf> {
+        if !is_valid_id(id) {
+            return None;
+        }
+
+        let cache_dir = self.cache_dir.as_ref()?;
+        Some(cache_dir.join(format!("{id}_{width}.thumb")))
+    }
+
+    //Sprawdzane przed wczytaniem bloba z repozytorium
+    pub async fn cached(&self, id: &str, width: u32) -> Option<Thumbnail> {
+        let cache_path = self.cache_path(id, width)?;
+        let data = tokio::fs::read(cache_path).await.ok()?;
+        Some(Thumbnail::from_cache(data))
+    }
+
+    pub async fn create(
+        &self,
+        id: &str,
+        width: u32,
+        content: Vec<u8>,
+    ) -> Result<Thumbnail, ErrorProcess> {
+        let thumbnail = match tokio::task::spawn_blocking(move || resize(&content, width)).await {
+            Ok(thumbnail) => thumbnail?,
+            Err(err) => {
+                return ErrorProcess::server_result(format!("Problem with resize task {err}"));
+            }
+        };
+
+        if let Some(cache_path) = self.cache_path(id, width) {
+            if let Err(err) = self.save(&cache_path, &thumbnail.data).await {
+                log::error!("Problem with save thumbnail {cache_path:?} {err}");
+            }
+        }
+
+        Ok(thumbnail)
+    }
+
+    //Zapis przez plik tymczasowy, żeby równoległe żądanie nie odczytało niepełnej miniatury.
+    //Każdy zapis ma własny plik tymczasowy, więc równoległe zapisy tej samej miniatury się nie mieszają
+    async fn save(&self, cache_path: &PathBuf, data: &[u8]) -> std::io::Result<()> {
+        if let Some(parent) = cache_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let tmp_path = tmp_path(cache_path);
+
+        if let Err(err) = tokio::fs::write(&tmp_path, data).await {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(err);
+        }
+
+        tokio::fs::rename(&tmp_path, cache_path).await
+    }
+}
+
+//Unikalna nazwa w obrębie procesu (licznik) i pomiędzy procesami (pid)
+fn tmp_path(cache_path: &Path) -> PathBuf {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let counter = COUNTER.fetch_add(1, Ordering::Relaxed);
+    cache_path.with_extension(format!("tmp{}-{counter}", std::process::id()))
+}
+
+#[test]
+fn test_tmp_path_unique() {
+    let cache_path = PathBuf::from("/cache/ab/cdef-256.thumb");
+    let first = tmp_path(&cache_path);
+    let second = tmp_path(&cache_path);
+
+    assert_ne!(first, second);
+    assert_eq!(first.parent(), cache_path.parent());
+}
+
+#[test]
+fn test_normalize_width() {
+    assert_eq!(normalize_width(1), 64);
+    assert_eq!(normalize_width(100), 128);
+    assert_eq!(normalize_width(128), 128);
+    assert_eq!(normalize_width(1000), 1024);
+    assert_eq!(normalize_width(10000), 1024);
+}
+
+#[cfg(test)]
+fn test_png(image: image::DynamicImage) -> Vec<u8> {
+    let mut png = Cursor::new(Vec::new());
+    image.write_to(&mut png, image::ImageOutputFormat::Png).unwrap();
+    png.into_inner()
+}
+
+#[test]
+fn test_resize() {
+    let png = test_png(image::RgbImage::from_pixel(300, 200, image::Rgb([200, 10, 10])).into());
+
+    let thumbnail = resize(&png, 128).unwrap();
+    assert_eq!(thumbnail.content_type, "image/jpeg");
+    let thumbnail = image::load_from_memory(&thumbnail.data).unwrap();
+    assert_eq!((thumbnail.width(), thumbnail.height()), (128, 85));
+
+    //mniejszy obrazek nie jest powiększany
+    let thumbnail = resize(&png, 512).unwrap();
+    let thumbnail = image::load_from_memory(&thumbnail.data).unwrap();
+    assert_eq!((thumbnail.width(), thumbnail.height()), (300, 200));
+
+    //przezroczystość zostaje zachowana
+    let png = test_png(image::RgbaImage::from_pixel(300, 200, image::Rgba([200, 10, 10, 100])).into());
+    let thumbnail = resize(&png, 128).unwrap();
+    assert_eq!(thumbnail.content_type, "image/webp");
+    assert_eq!(Thumbnail::from_cache(thumbnail.data).content_type, "image/webp");
+}
+
+#[test]
+fn test_resize_limits() {
+    let png = test_png(image::GrayImage::new(MAX_DIMENSION + 1, 1).into());
+    assert!(resize(&png, 128).is_err());
+}
@@ -13,6 +13,10 @@ pub enum ErrorProcess {
     Server {
         context: Vec<String>,
         message: String,
+    },
+    Forbidden {
+        context: Vec<String>,
+        message: String,
     }
 }
 
@@ -38,6 +42,17 @@ impl ErrorProcess {
         })
     }
 
+    pub fn forbidden_result<K, T: Into<String>>(message: T) -> Result<K, ErrorProcess> {
+        Err(ErrorProcess::Forbidden {
+            context: Vec::new(),
+            message: message.into(),
+        })
+    }
+
+    pub fn is_forbidden(&self) -> bool {
+        matches!(self, ErrorProcess::Forbidden { .. })
+    }
+
     pub fn to_response<T: Send + ToJSON + ParseFromJSON>(self) -> ApiResponseHttp<T> {
         ApiResponseHttp::from(Err(self))
     }
@@ -52,6 +67,10 @@ impl ErrorProcess {
                 context.push(format!("{label} = {label_message:?}"));
                 ErrorProcess::User { context, message }
             },
+            ErrorProcess::Forbidden { mut context, message } => {
+                context.push(format!("{label} = {label_message:?}"));
+                ErrorProcess::Forbidden { context, message }
+            },
         }
     }
 
@@ -60,7 +79,8 @@ impl ErrorProcess {
             ErrorProcess::Server { message, context } => {
                 (true, format_message(context, message))
             },
-            ErrorProcess::User { message, context } => {
+            ErrorProcess::User { message, context } |
+            ErrorProcess::Forbidden { message, context } => {
                 (false, format_message(context, message))
             }
         }
@@ -1,6 +1,7 @@
 mod spawn;
-// pub mod time;
-pub use spawn::{SpawnOwner, spawn_and_wait};
+mod time;
+pub use spawn::{SpawnOwner};
+pub use time::{now_seconds, local_offset_seconds};
 
 
 
@@ -16,6 +16,8 @@ pub enum ApiResponseHttp<T: Send + ToJSON + ParseFromJSON> {
     User(Json<String>),
     #[oai(status = 401)]
     Unauthorized(Json<String>),
+    #[oai(status = 403)]
+    Forbidden(Json<String>),
     #[oai(status = 404)]
     NotFound(Json<String>),
     #[oai(status = 500)]
@@ -38,6 +40,11 @@ impl<T: Send + ToJSON + ParseFromJSON> ApiResponseHttp<T> {
     }
 
     pub fn from_error_process(error: ErrorProcess) -> ApiResponseHttp<T> {
+        if error.is_forbidden() {
+            let (_, message) = error.to_string();
+            return ApiResponseHttp::Forbidden(Json(message));
+        }
+
         let (internal, message) = error.to_string();
         match internal {
             true => ApiResponseHttp::Internal(Json(message)),
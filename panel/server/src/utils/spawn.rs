@@ -1,6 +1,5 @@
 use tokio::task::{self, JoinHandle};
 use std::future::Future;
-use tokio::sync::oneshot::channel;
 
 pub struct SpawnOwner {
     handler: JoinHandle<()>,
@@ -22,16 +21,3 @@ impl Drop for SpawnOwner {
         self.handler.abort();
     }
 }
-
-pub async fn spawn_and_wait(fut: impl Future<Output=()> + Send + 'static) {
-
-    let (sender, receiver) = channel::<()>();
-
-    let spawn_owner = SpawnOwner::new(async move {
-        fut.await;
-        let _ = sender.send(());
-    });
-
-    let _ = receiver.await;
-    spawn_owner.off();
-}
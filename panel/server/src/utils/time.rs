@@ -1,10 +1,14 @@
 use std::time::{SystemTime, UNIX_EPOCH};
-use common::TimestampType;
+use chrono::Local;
 
-pub fn get_current_timestamp() -> TimestampType {
-    let now = SystemTime::now();
-
-    let since_the_epoch = now.duration_since(UNIX_EPOCH).unwrap();
+pub fn now_seconds() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0)
+}
 
-    since_the_epoch.as_millis()
+//Przesunięcie lokalnej strefy czasowej serwera względem UTC, w sekundach
+pub fn local_offset_seconds() -> i64 {
+    i64::from(Local::now().offset().local_minus_utc())
 }
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+use tokio::sync::Notify;
+use crate::git::Git;
+use crate::sync::{start_sync, SyncHandle};
+use crate::root_watch::RootWatch;
+use crate::utils::SpawnOwner;
+
+pub const DEFAULT_REPO_NAME: &str = "default";
+
+#[derive(Debug, Clone)]
+pub struct RepoConfig {
+    pub name: String,
+    pub path: String,
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|char| char.is_ascii_alphanumeric() || char == '-' || char == '_')
+}
+
+//git_repo - pojedyncze repozytorium (nazwa "default"), git_repos - lista w formacie "nazwa=ścieżka"
+pub fn parse_repos(git_repo: Option<String>, git_repos: Vec<String>) -> Result<Vec<RepoConfig>, String> {
+    let mut list = Vec::new();
+
+    if let Some(path) = git_repo {
+        list.push(RepoConfig {
+            name: DEFAULT_REPO_NAME.into(),
+            path,
+        });
+    }
+
+    for item in git_repos {
+        let Some((name, path)) = item.split_once('=') else {
+            return Err(format!("Invalid repository entry {item:?}, expected name=path"));
+        };
+
+        let name = name.trim();
+        let path = path.trim();
+
+        if !is_valid_name(name) {
+            return Err(format!("Invalid repository name {name:?}"));
+        }
+
+        if path.is_empty() {
+            return Err(format!("Missing path for repository {name:?}"));
+        }
+
+        if list.iter().any(|repo: &RepoConfig| repo.name == name) {
+            return Err(format!("Duplicated repository name {name:?}"));
+        }
+
+        list.push(RepoConfig {
+            name: name.into(),
+            path: path.into(),
+        });
+    }
+
+    if list.is_empty() {
+        return Err("No repository configured, set GIT_REPO or GIT_REPOS".into());
+    }
+
+    Ok(list)
+}
+
+//Każde repozytorium ma osobną synchronizację i obserwatora roota
+pub struct Workspace {
+    pub name: String,
+    pub git: Git,
+    pub sync: SyncHandle,
+    pub root_watch: RootWatch,
+    task_synchronize: SpawnOwner,
+}
+
+impl Workspace {
+    pub async fn start(repo: RepoConfig, branch: String, remote: String, trash_dir: Option<String>) -> Workspace {
+        let notify = Arc::new(Notify::new());
+
+        println!("start git test: {} {} (branch: {}, remote: {})", &repo.name, &repo.path, &branch, &remote);
+        let git = match Git::new(notify.clone(), repo.path.clone(), branch, trash_dir) {
+            Ok(git) => git,
+            Err(error) => panic!("Service started with invalid git configuration {} {:?}", repo.name, error.to_string().1)
+        };
+
+        let (root_watch, root_publisher) = RootWatch::new(git.clone()).await;
+        let (task_synchronize, sync) = start_sync(notify, git.clone(), remote, root_publisher).await;
+
+        Workspace {
+            name: repo.name,
+            git,
+            sync,
+            root_watch,
+            task_synchronize,
+        }
+    }
+
+    pub fn off(self) {
+        self.task_synchronize.off();
+    }
+}